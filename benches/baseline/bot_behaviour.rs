@@ -0,0 +1,7 @@
+use super::game::{Board, GameState};
+
+pub trait Behaviour {
+    fn name(&self) -> String;
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String>;
+    fn post_baza_actions(&self, board: &mut Board, player: usize);
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+// Còpia congelada del motor i dels bots del commit de partida (abans del CardSet), perquè el
+// benchmark de play_game mesuri la millora contra el codi antic a la mateixa màquina i amb el
+// mateix perfil de compilació en lloc de contra una xifra apuntada. No s'ha de tocar: només
+// s'hi han canviat els `use crate::` per `use super::`.
+#![allow(clippy::all, dead_code)]
+
+mod bot_behaviour;
+mod game;
+mod random_bot;
+mod smart_bot;
+mod utils;
+
+use rand::{thread_rng, Rng};
+use game::Board;
+
+pub use bot_behaviour::Behaviour;
+pub use game::GameState;
+pub use random_bot::RandomBot;
+pub use smart_bot::SmartBot;
+
+// El play_game de l'antic main.rs
+pub fn play_game(bots: &Vec<Box<dyn Behaviour>>) -> GameState {
+    let starting_player: usize = thread_rng().gen_range(0..=3);
+    let mut board: Board = Board::new(starting_player);
+
+    loop {
+        match board.current_player {
+            0..=3 => match bots[board.current_player].play_card(&mut board) {
+                Ok(game_state) => match game_state {
+                    GameState::BazaEnded => post_baza_actions(&bots, &mut board),
+                    GameState::Continuation => (),
+                    GameState::Team0Won | GameState::Team1Won => {
+                        return game_state;
+                    },
+                    _ => ()
+                },
+                Err(error) => println!("Error: {}", error)
+            }
+            _ => {
+                println!("Wrong player");
+                return GameState::None;
+            }
+        }
+    }
+}
+
+fn post_baza_actions(bots: &Vec<Box<dyn Behaviour>>, board: &mut Board) {
+    for (player, bot) in bots.iter().enumerate() {
+        bot.post_baza_actions(board, player);
+    }
+}
+
+pub fn bots() -> Vec<Box<dyn Behaviour>> {
+    vec![
+        Box::new(RandomBot {}),
+        Box::new(SmartBot {}),
+        Box::new(RandomBot {}),
+        Box::new(SmartBot {})
+    ]
+}
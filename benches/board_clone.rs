@@ -0,0 +1,33 @@
+use std::hint::black_box;
+use std::process::ExitCode;
+use std::time::Instant;
+use rust_guinote::game::Board;
+
+// Pressupost per clonar un Board. Els bots de simulació en fan milers per jugada,
+// per tant un clon ha de costar menys que jugar una carta.
+const TARGET_NS_PER_CLONE: f64 = 100.0;
+const ITERATIONS: u32 = 1_000_000;
+
+fn main() -> ExitCode {
+    let board: Board = Board::new(0);
+
+    // Warm up
+    for _ in 0..ITERATIONS / 10 {
+        black_box(board.clone());
+    }
+
+    let start: Instant = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(black_box(&board).clone());
+    }
+    let ns_per_clone: f64 = start.elapsed().as_nanos() as f64 / ITERATIONS as f64;
+
+    println!("Board::clone: {:.1} ns/clone (target: {:.1} ns)", ns_per_clone, TARGET_NS_PER_CLONE);
+
+    if ns_per_clone > TARGET_NS_PER_CLONE {
+        println!("Board::clone is over target");
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
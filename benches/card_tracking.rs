@@ -0,0 +1,55 @@
+use std::process::ExitCode;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::card_set::CardSet;
+use rust_guinote::card_tracking::HandBeliefs;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{mixed_bots, visit_positions};
+
+// Mesura quant es dedueix de les mans ocultes i quant costa construir les creences. La
+// comprovació que no contradiuen les mans reals és a tests/card_tracking.rs
+const GAMES: u64 = 500;
+
+fn main() -> ExitCode {
+    let bots: Vec<Box<dyn Behaviour>> = mixed_bots();
+
+    let mut beliefs_built: usize = 0;
+    let mut arrastre_views: usize = 0;
+    let mut arrastre_hidden_cards: usize = 0;
+    let mut arrastre_possible_cards: usize = 0;
+    let mut arrastre_certain_cards: usize = 0;
+
+    let start: Instant = Instant::now();
+    for seed in 0..GAMES {
+        visit_positions(&bots, seed, |board| {
+            for observer in 0..4 {
+                let beliefs: HandBeliefs = HandBeliefs::new(board, observer);
+                beliefs_built += 1;
+
+                for player in (0..4).filter(|&player| player != observer) {
+                    if board.is_arrastre() {
+                        let hand: CardSet = board.get_hand(player).cards;
+                        arrastre_views += 1;
+                        arrastre_hidden_cards += hand.len();
+                        arrastre_possible_cards += beliefs.get_possible(player).len();
+                        arrastre_certain_cards += beliefs.get_certain(player).len();
+                    }
+                }
+            }
+
+            true
+        });
+    }
+    let us_per_beliefs: f64 = start.elapsed().as_secs_f64() * 1e6 / beliefs_built as f64;
+
+    println!("{} beliefs built ({:.2} us each, including the games)", beliefs_built, us_per_beliefs);
+    println!("Arrastre, per hidden hand: {:.2} cards, {:.2} possible, {:.2} certain",
+             arrastre_hidden_cards as f64 / arrastre_views as f64,
+             arrastre_possible_cards as f64 / arrastre_views as f64,
+             arrastre_certain_cards as f64 / arrastre_views as f64);
+
+    ExitCode::SUCCESS
+}
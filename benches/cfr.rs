@@ -0,0 +1,54 @@
+use std::process::ExitCode;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{expert_bot::{ExpertBot, ExpertConfig}, random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::cfr::{CfrConfig, CfrSolver, Exploitability, StrategyTable};
+use rust_guinote::game::Board;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::endgame;
+
+// Resol finals de 2 cartes per jugador, compara l'explotabilitat (NashConv, en punts) de
+// l'estratègia de CFR amb la dels bots heurístics i mesura el temps. Les comprovacions de la
+// convergència són a tests/cfr.rs
+const POSITIONS: usize = 5;
+const CHECKPOINTS: [usize; 3] = [10, 50, 200];
+
+fn main() -> ExitCode {
+    let heuristics: Vec<(&str, Box<dyn Behaviour>)> = vec![
+        ("random", Box::new(RandomBot {})),
+        ("smart", Box::new(SmartBot {})),
+        ("expert", Box::new(ExpertBot::new(ExpertConfig::default())))
+    ];
+    let mut totals: Vec<f64> = vec![0.0; CHECKPOINTS.len() + heuristics.len()];
+
+    let start: Instant = Instant::now();
+    let boards: Vec<Board> = (0..).filter_map(endgame).take(POSITIONS).collect();
+    for board in boards.iter() {
+        let mut solver: CfrSolver = CfrSolver::new(board, CfrConfig::default()).unwrap();
+
+        for (i, &iterations) in CHECKPOINTS.iter().enumerate() {
+            solver.run(iterations - solver.get_iterations());
+            let exploitability: Exploitability = solver.exploitability(&solver.strategy());
+            totals[i] += exploitability.total();
+        }
+
+        for (i, (_, bot)) in heuristics.iter().enumerate() {
+            let table: StrategyTable = solver.bot_strategy(bot.as_ref());
+            totals[CHECKPOINTS.len() + i] += solver.exploitability(&table).total();
+        }
+    }
+    let seconds: f64 = start.elapsed().as_secs_f64();
+
+    for (i, iterations) in CHECKPOINTS.iter().enumerate() {
+        println!("CFR {} iterations: {:.2} points", iterations, totals[i] / POSITIONS as f64);
+    }
+    for (i, (name, _)) in heuristics.iter().enumerate() {
+        println!("{}: {:.2} points", name, totals[CHECKPOINTS.len() + i] / POSITIONS as f64);
+    }
+    println!("Mean exploitability over {} positions ({:.1} s)", POSITIONS, seconds);
+
+    ExitCode::SUCCESS
+}
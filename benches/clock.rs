@@ -0,0 +1,47 @@
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::ismcts_bot::{IsmctsBot, IsmctsConfig};
+use rust_guinote::clock::{ClockConfig, TimeoutPolicy};
+use rust_guinote::driver::{play_logged_game, DriverOptions};
+use rust_guinote::game_log::GameLog;
+
+// Quant costa portar el rellotge en partides ràpides i quant temps del rellotge fa servir l'ISMCTS
+// limitat només pel temps. Les comprovacions de les polítiques són a tests/clock.rs
+const GAMES: u64 = 500;
+const SEARCH_GAMES: u64 = 3;
+
+fn main() -> ExitCode {
+    let experts: Vec<Box<dyn Behaviour>> = (0..4).map(|_| Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>).collect();
+    let clock: ClockConfig = ClockConfig { initial: Duration::from_secs(60), increment: Duration::ZERO, policy: TimeoutPolicy::Forfeit };
+    let without: f64 = us_per_game(&experts, &DriverOptions::default());
+    let with: f64 = us_per_game(&experts, &DriverOptions { clock: Some(clock), ..DriverOptions::default() });
+    println!("ExpertBot games: {:.0} us without clock, {:.0} us with clock", without, with);
+
+    let clock: ClockConfig = ClockConfig { initial: Duration::from_millis(500), increment: Duration::from_millis(40), policy: TimeoutPolicy::Forfeit };
+    let search: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| match seat % 2 {
+        0 => Box::new(IsmctsBot::new(IsmctsConfig { iterations: usize::MAX, time_budget: Some(Duration::from_millis(80)), ..IsmctsConfig::default() })) as Box<dyn Behaviour>,
+        _ => Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>
+    }).collect();
+    let mut used: Duration = Duration::ZERO;
+    let mut timeouts: usize = 0;
+    for seed in 0..SEARCH_GAMES {
+        let mut log: GameLog = GameLog::new();
+        play_logged_game(&search, seed, &DriverOptions { clock: Some(clock), ..DriverOptions::default() }, &mut log);
+        used += log.time_used()[0] + log.time_used()[2];
+        timeouts += log.timeouts();
+    }
+    println!("IsmctsBot used {:.2} s over {} games, {} timeouts", used.as_secs_f64(), SEARCH_GAMES, timeouts);
+
+    ExitCode::SUCCESS
+}
+
+fn us_per_game(bots: &[Box<dyn Behaviour>], options: &DriverOptions) -> f64 {
+    let start: Instant = Instant::now();
+    for seed in 0..GAMES {
+        play_logged_game(bots, seed, options, &mut GameLog::new());
+    }
+
+    start.elapsed().as_secs_f64() * 1e6 / GAMES as f64
+}
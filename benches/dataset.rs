@@ -0,0 +1,24 @@
+use std::process::ExitCode;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::dataset::{generate_shard, SelfPlayConfig, RECORD_SIZE};
+
+// Genera un shard petit a memòria i mesura els registres per segon. Les comprovacions del format
+// i de la reproductibilitat són a tests/dataset.rs
+const GAMES: u64 = 200;
+
+fn main() -> ExitCode {
+    let config: SelfPlayConfig = SelfPlayConfig { games_per_shard: GAMES, first_seed: 1000 };
+    let make_bot = |_seat: usize, _seed: u64| Ok(Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>);
+
+    let start: Instant = Instant::now();
+    let mut bytes: Vec<u8> = Vec::new();
+    let records: usize = generate_shard(&config, &make_bot, 3, &mut bytes).unwrap();
+    let elapsed: f64 = start.elapsed().as_secs_f64();
+
+    println!("{} games, {} records, {} bytes/record", GAMES, records, RECORD_SIZE);
+    println!("{:.0} records/s", records as f64 / elapsed);
+
+    ExitCode::SUCCESS
+}
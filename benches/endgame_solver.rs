@@ -0,0 +1,44 @@
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use rust_guinote::endgame_solver::EndgameSolver;
+use rust_guinote::game::Board;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::arrastre_positions;
+
+// Un final de 6 cartes per jugador s'ha de resoldre en mil·lisegons. Les comprovacions contra
+// el minimax sense poda són a tests/endgame_solver.rs
+const TARGET_MS_PER_SOLVE: f64 = 10.0;
+const GAMES: u64 = 200;
+
+fn main() -> ExitCode {
+    let (full_endgames, positions): (Vec<Board>, Vec<Board>) = arrastre_positions(GAMES);
+    println!("{} full endgames, {} arrastre positions", full_endgames.len(), positions.len());
+
+    let mut total: Duration = Duration::ZERO;
+    let mut slowest: Duration = Duration::ZERO;
+    let mut nodes: u64 = 0;
+    for board in full_endgames.iter() {
+        let mut solver: EndgameSolver = EndgameSolver::new();
+        let start: Instant = Instant::now();
+        solver.solve(board).unwrap();
+        let elapsed: Duration = start.elapsed();
+
+        total += elapsed;
+        slowest = slowest.max(elapsed);
+        nodes += solver.get_nodes();
+    }
+    let ms_per_solve: f64 = total.as_secs_f64() * 1000.0 / full_endgames.len() as f64;
+
+    println!("6-card endgame: {:.2} ms/solve (slowest {:.2} ms, {:.0} nodes/solve, target: {:.1} ms)",
+             ms_per_solve, slowest.as_secs_f64() * 1000.0, nodes as f64 / full_endgames.len() as f64, TARGET_MS_PER_SOLVE);
+
+    if ms_per_solve > TARGET_MS_PER_SOLVE {
+        println!("Endgame solver is over target");
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
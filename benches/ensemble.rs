@@ -0,0 +1,37 @@
+use std::process::ExitCode;
+use std::rc::Rc;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::ensemble_bot::{EnsembleBot, EnsembleRule, EnsembleStats};
+use rust_guinote::driver::play_seeded_game;
+use rust_guinote::game::GameState;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{ensemble, Shared};
+
+// Enfronta la majoria d'ExpertBot, SmartBot i WeightedBot amb l'ExpertBot sol i mesura el temps
+// per partida. Les comprovacions de les regles i dels vots són a tests/ensemble.rs
+const GAMES: u64 = 500;
+
+fn main() -> ExitCode {
+    let majority: Rc<EnsembleBot> = Rc::new(ensemble(EnsembleRule::Majority, &[1.0; 3]));
+    let bots: Vec<Box<dyn Behaviour>> = vec![
+        Box::new(Shared(majority.clone())),
+        Box::new(ExpertBot::new(ExpertConfig::default())),
+        Box::new(Shared(majority.clone())),
+        Box::new(ExpertBot::new(ExpertConfig::default()))
+    ];
+
+    let start: Instant = Instant::now();
+    let wins: usize = (0..GAMES).filter(|&seed| play_seeded_game(&bots, seed) == GameState::Team0Won).count();
+    let us_per_game: f64 = start.elapsed().as_secs_f64() * 1e6 / GAMES as f64;
+
+    let stats: EnsembleStats = majority.get_stats();
+    println!("Majority of expert, smart and weighted vs expert: {}/{} ({:.0} us/game)", wins, GAMES, us_per_game);
+    print!("{}", stats);
+
+    ExitCode::SUCCESS
+}
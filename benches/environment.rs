@@ -0,0 +1,60 @@
+use std::process::ExitCode;
+use std::hint::black_box;
+use std::time::Instant;
+use rand::{thread_rng, Rng};
+use rand::rngs::ThreadRng;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::game::GameState;
+use rust_guinote::encoding::encode;
+use rust_guinote::environment::{EnvConfig, Environment, Observation, RewardMode, Step, ACTION_COUNT};
+
+// Un agent aleatori contra SmartBots, que codifica cada observació: mesura els passos per segon.
+// Les comprovacions són a tests/environment.rs i tests/encoding.rs
+const EPISODES: u64 = 2000;
+
+fn main() -> ExitCode {
+    let mut rng: ThreadRng = thread_rng();
+    let mut environment: Environment = Environment::new(config(1, RewardMode::PointDifference)).unwrap();
+    let mut steps: usize = 0;
+    let mut wins: usize = 0;
+
+    let start: Instant = Instant::now();
+    for seed in 0..EPISODES {
+        let mut observation: Observation = environment.reset(seed).unwrap();
+
+        loop {
+            let legal_actions: Vec<usize> = (0..ACTION_COUNT).filter(|&action| observation.legal_mask[action]).collect();
+
+            black_box(encode(&observation));
+
+            let step: Step = environment.step(legal_actions[rng.gen_range(0..legal_actions.len())]).unwrap();
+            steps += 1;
+
+            if step.done {
+                if step.info.result == GameState::Team1Won {
+                    wins += 1;
+                }
+                break;
+            }
+            observation = step.observation;
+        }
+    }
+    let us_per_step: f64 = start.elapsed().as_secs_f64() * 1e6 / steps as f64;
+
+    println!("{} episodes, {} agent steps, {:.2} us/step (including bot moves)", EPISODES, steps, us_per_step);
+    println!("Random agent win rate vs SmartBot: {:.1}%", wins as f64 * 100.0 / EPISODES as f64);
+
+    ExitCode::SUCCESS
+}
+
+fn config(seat: usize, reward: RewardMode) -> EnvConfig {
+    EnvConfig {
+        seat,
+        bots: (0..4).map(|seat| match seat == 1 {
+            true => Box::new(RandomBot {}) as Box<dyn Behaviour>,
+            false => Box::new(SmartBot {}) as Box<dyn Behaviour>
+        }).collect(),
+        reward
+    }
+}
@@ -0,0 +1,33 @@
+use std::process::ExitCode;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::driver::{play_logged_game, play_seeded_game, DriverOptions};
+use rust_guinote::faults::{FaultConfig, FaultPolicy};
+use rust_guinote::game_log::GameLog;
+
+// Cost de protegir cada jugada amb bots que no fallen: partides amb registre i política de
+// fallades contra partides normals. Les comprovacions de les polítiques són a tests/faults.rs
+const GAMES: u64 = 2000;
+
+fn main() -> ExitCode {
+    let experts: Vec<Box<dyn Behaviour>> = (0..4).map(|_| Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>).collect();
+    let forfeit: DriverOptions = DriverOptions { faults: FaultConfig { policy: FaultPolicy::Forfeit, ..FaultConfig::default() }, ..DriverOptions::default() };
+
+    let start: Instant = Instant::now();
+    for seed in 0..GAMES {
+        play_seeded_game(&experts, seed);
+    }
+    let plain_us: f64 = start.elapsed().as_secs_f64() * 1e6 / GAMES as f64;
+
+    let start: Instant = Instant::now();
+    let mut log: GameLog = GameLog::new();
+    for seed in 0..GAMES {
+        log.clear();
+        play_logged_game(&experts, seed, &forfeit, &mut log);
+    }
+    let guarded_us: f64 = start.elapsed().as_secs_f64() * 1e6 / GAMES as f64;
+
+    println!("faults: {:.1} us/game plain, {:.1} us/game logged with fault handling", plain_us, guarded_us);
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,41 @@
+use std::process::ExitCode;
+use std::time::Instant;
+use rand::thread_rng;
+use rand::rngs::ThreadRng;
+use rust_guinote::determinization::HandSampler;
+use rust_guinote::game::Board;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{collect_positions, mixed_bots};
+
+// Mesura quant costa construir el HandSampler i treure'n repartiments a totes les posicions de
+// partides senceres, vistes pel jugador que ha de jugar. La comprovació que és uniforme és a
+// tests/hand_sampler.rs
+const GAMES: u64 = 200;
+const SAMPLES: usize = 20;
+
+fn main() -> ExitCode {
+    let mut rng: ThreadRng = thread_rng();
+    let positions: Vec<(Board, usize)> = collect_positions(&mixed_bots(), GAMES).into_iter().map(|board| {
+        let observer: usize = board.current_player;
+        (board, observer)
+    }).collect();
+
+    let start: Instant = Instant::now();
+    let samplers: Vec<HandSampler> = positions.iter().map(|(board, observer)| HandSampler::new(board, *observer).unwrap()).collect();
+    let us_per_sampler: f64 = start.elapsed().as_secs_f64() * 1e6 / samplers.len() as f64;
+
+    let start: Instant = Instant::now();
+    for sampler in samplers.iter() {
+        for _ in 0..SAMPLES {
+            sampler.sample(&mut rng);
+        }
+    }
+    let us_per_sample: f64 = start.elapsed().as_secs_f64() * 1e6 / (samplers.len() * SAMPLES) as f64;
+
+    println!("{} positions: {:.2} us/sampler, {:.2} us/sample", positions.len(), us_per_sampler, us_per_sample);
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,62 @@
+use std::hint::black_box;
+use std::process::ExitCode;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::game::Board;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+#[path = "../tests/common/allocator.rs"]
+mod allocator;
+
+use allocator::allocations;
+use common::{collect_positions, random_bots, reference_legal_cards};
+
+// Temps de get_legal_cards contra la implementació amb Vec i HashSet, i allocacions de cada una.
+// Les comprovacions (mateixes cartes, cap allocació) són a tests/legal_cards.rs
+const GAMES: u64 = 50;
+const ROUNDS: usize = 500;
+
+fn main() -> ExitCode {
+    let positions: Vec<Board> = collect_positions(&random_bots(), GAMES);
+    let arrastre_positions: usize = positions.iter().filter(|board| board.is_arrastre()).count();
+    println!("{} positions ({} in arrastre)", positions.len(), arrastre_positions);
+
+    let allocations_before: usize = allocations();
+    let start: Instant = Instant::now();
+    for _ in 0..ROUNDS {
+        for board in positions.iter() {
+            black_box(black_box(board).get_legal_cards());
+        }
+    }
+    let legal_cards_ns: f64 = start.elapsed().as_nanos() as f64 / (ROUNDS * positions.len()) as f64;
+    let legal_cards_allocations: usize = allocations() - allocations_before;
+
+    let allocations_before: usize = allocations();
+    let start: Instant = Instant::now();
+    for _ in 0..ROUNDS {
+        for board in positions.iter() {
+            black_box(reference_legal_cards(black_box(board)));
+        }
+    }
+    let reference_ns: f64 = start.elapsed().as_nanos() as f64 / (ROUNDS * positions.len()) as f64;
+    let reference_allocations: usize = allocations() - allocations_before;
+
+    // Una jugada sencera d'un bot tampoc ha d'allocar
+    let bots: [Box<dyn Behaviour>; 2] = [Box::new(RandomBot {}), Box::new(SmartBot {})];
+    let mut boards: Vec<Board> = positions.clone();
+    let allocations_before: usize = allocations();
+    for (i, board) in boards.iter_mut().enumerate() {
+        black_box(bots[i % 2].play_card(board).is_ok());
+    }
+    let bot_move_allocations: usize = allocations() - allocations_before;
+
+    println!("get_legal_cards:       {:>7.1} ns/call, {} allocations", legal_cards_ns, legal_cards_allocations);
+    println!("Vec + HashSet version: {:>7.1} ns/call, {:.1} allocations/call",
+             reference_ns, reference_allocations as f64 / (ROUNDS * positions.len()) as f64);
+    println!("Speedup: {:.1}x", reference_ns / legal_cards_ns);
+    println!("Bot moves: {} allocations in {} moves", bot_move_allocations, boards.len());
+
+    ExitCode::SUCCESS
+}
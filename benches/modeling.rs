@@ -0,0 +1,42 @@
+use std::process::ExitCode;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::adaptive_bot::{AdaptiveBot, AdaptiveConfig};
+use rust_guinote::driver::play_seeded_game;
+use rust_guinote::game::GameState;
+use rust_guinote::modeling::{ModelStore, PlayerModel, SharedModels, Tendency};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::observed;
+
+// Juga una sessió entre ExpertBot i RandomBot observant els dos equips i després l'AdaptiveBot
+// contra RandomBot amb els models de la sessió; mostra els models, les victòries i el temps per
+// partida. Les comprovacions són a tests/modeling.rs
+const GAMES: u64 = 500;
+
+fn main() -> ExitCode {
+    let models: SharedModels = ModelStore::new().shared();
+    let session: Vec<Box<dyn Behaviour>> = observed(&models, || Box::new(ExpertBot::new(ExpertConfig::default())), "expert");
+    let start: Instant = Instant::now();
+    let expert_wins: usize = (0..GAMES).filter(|&seed| play_seeded_game(&session, seed) == GameState::Team0Won).count();
+    let expert_us_per_game: f64 = start.elapsed().as_secs_f64() * 1e6 / GAMES as f64;
+
+    let expert: PlayerModel = models.borrow().get("expert");
+    let random: PlayerModel = models.borrow().get("random");
+
+    let adaptive: Vec<Box<dyn Behaviour>> = observed(&models, || Box::new(AdaptiveBot::new(AdaptiveConfig::default(), models.clone())), "adaptive");
+    let start: Instant = Instant::now();
+    let adaptive_wins: usize = (0..GAMES).filter(|&seed| play_seeded_game(&adaptive, seed) == GameState::Team0Won).count();
+    let adaptive_us_per_game: f64 = start.elapsed().as_secs_f64() * 1e6 / GAMES as f64;
+
+    for tendency in [Tendency::LeadTrumfo, Tendency::SaveSeven, Tendency::LoadPartner, Tendency::Overtake, Tendency::Cante, Tendency::Exchange] {
+        println!("{:?}: expert {:.2}, random {:.2}", tendency, expert.rate(tendency), random.rate(tendency));
+    }
+    println!("Observed ExpertBot vs RandomBot: {}/{} ({:.0} us/game)", expert_wins, GAMES, expert_us_per_game);
+    println!("Observed AdaptiveBot vs RandomBot: {}/{} ({:.0} us/game)", adaptive_wins, GAMES, adaptive_us_per_game);
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,49 @@
+use std::process::ExitCode;
+use std::hint::black_box;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{neural_bot::{NeuralBot, NeuralConfig}, random_bot::RandomBot};
+use rust_guinote::driver::play_seeded_game;
+use rust_guinote::encoding::{encode_board, OBSERVATION_SIZE};
+use rust_guinote::game::Board;
+use rust_guinote::neural::Network;
+
+// Mesura el temps d'una avaluació de la xarxa i d'una partida del bot amb cada mode. Les
+// comprovacions de la lectura i de les partides són a tests/neural.rs
+const EVALUATIONS: usize = 10000;
+const GAMES: u64 = 50;
+
+fn main() -> ExitCode {
+    let network: Network = Network::random(&[128, 64], 7);
+
+    let board: Board = Board::new_seeded(0, 1);
+    let observation: [f32; OBSERVATION_SIZE] = encode_board(&board, 0).0;
+
+    let start: Instant = Instant::now();
+    let mut checksum: f32 = 0.0;
+    for _ in 0..EVALUATIONS {
+        checksum += network.evaluate(&observation).1;
+    }
+    let us_per_evaluation: f64 = start.elapsed().as_secs_f64() * 1e6 / EVALUATIONS as f64;
+    black_box(checksum);
+
+    let configs: [NeuralConfig; 2] = [
+        NeuralConfig::default(),
+        NeuralConfig { value_weight: 1.0, value_cantes: true, value_canvi: true, ..NeuralConfig::default() }
+    ];
+    println!("Network 591-128-64 evaluation: {:.1} us", us_per_evaluation);
+
+    for (mode, config) in configs.into_iter().enumerate() {
+        let neural: NeuralBot = NeuralBot::new(network.clone(), config);
+        let bots: Vec<Box<dyn Behaviour>> = vec![Box::new(neural), Box::new(RandomBot {}), Box::new(NeuralBot::new(network.clone(), NeuralConfig::default())), Box::new(RandomBot {})];
+
+        let start: Instant = Instant::now();
+        for seed in 0..GAMES {
+            play_seeded_game(&bots, seed);
+        }
+        let ms_per_game: f64 = start.elapsed().as_secs_f64() * 1e3 / GAMES as f64;
+        println!("Mode {}: {:.2} ms/game", mode, ms_per_game);
+    }
+
+    ExitCode::SUCCESS
+}
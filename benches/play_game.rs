@@ -0,0 +1,66 @@
+use std::hint::black_box;
+use std::process::ExitCode;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::driver::play_game;
+
+mod baseline;
+
+// Compara play_game amb la còpia congelada del motor d'abans del CardSet (benches/baseline), totes
+// dues compilades aquí mateix. L'ordre de magnitud no s'hi arriba: el motor d'ara també porta el
+// card_tracker, el hash de Zobrist i els cantes, que la còpia no té, i el driver captura els panics.
+// Queda entre 5.8x i 6.3x; per sota de 5x és que alguna cosa ha empitjorat.
+const TARGET_SPEEDUP: f64 = 5.0;
+const GAMES: u32 = 20_000;
+const BASELINE_GAMES: u32 = 2_000;
+// Es queda la millor ronda de cada motor perquè el soroll de la màquina només pot fer-les més
+// lentes. Les rondes dels dos motors s'alternen perquè el soroll els afecti igual
+const ROUNDS: u32 = 10;
+
+fn main() -> ExitCode {
+    let bots: Vec<Box<dyn Behaviour>> = vec![
+        Box::new(RandomBot {}),
+        Box::new(SmartBot {}),
+        Box::new(RandomBot {}),
+        Box::new(SmartBot {})
+    ];
+    let baseline_bots: Vec<Box<dyn baseline::Behaviour>> = baseline::bots();
+
+    // Warm up
+    for _ in 0..GAMES / 10 {
+        black_box(play_game(&bots));
+    }
+    for _ in 0..BASELINE_GAMES / 10 {
+        black_box(baseline::play_game(&baseline_bots));
+    }
+
+    let mut elapsed_secs: f64 = f64::MAX;
+    let mut baseline_elapsed_secs: f64 = f64::MAX;
+    for _ in 0..ROUNDS {
+        let start: Instant = Instant::now();
+        for _ in 0..GAMES {
+            black_box(play_game(black_box(&bots)));
+        }
+        elapsed_secs = elapsed_secs.min(start.elapsed().as_secs_f64());
+
+        let start: Instant = Instant::now();
+        for _ in 0..BASELINE_GAMES {
+            black_box(baseline::play_game(black_box(&baseline_bots)));
+        }
+        baseline_elapsed_secs = baseline_elapsed_secs.min(start.elapsed().as_secs_f64());
+    }
+    let us_per_game: f64 = elapsed_secs * 1e6 / GAMES as f64;
+    let baseline_us_per_game: f64 = baseline_elapsed_secs * 1e6 / BASELINE_GAMES as f64;
+    let speedup: f64 = baseline_us_per_game / us_per_game;
+
+    println!("play_game: {:.2} µs/game, {:.0} games/s", us_per_game, GAMES as f64 / elapsed_secs);
+    println!("baseline: {:.2} µs/game, speedup {:.1}x (target: {:.0}x)", baseline_us_per_game, speedup, TARGET_SPEEDUP);
+
+    if speedup < TARGET_SPEEDUP {
+        println!("play_game is slower than {:.0}x baseline", TARGET_SPEEDUP);
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
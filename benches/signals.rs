@@ -0,0 +1,44 @@
+use std::process::ExitCode;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::signal_bot::SignalBot;
+use rust_guinote::driver::{play_logged_game, DriverOptions};
+use rust_guinote::game_log::GameLog;
+use rust_guinote::signals::SignalConfig;
+
+// Partides amb senyes entre SignalBots: mesura el temps per partida amb el canal obert i tancat
+// i quantes senyes veuen els rivals. Les comprovacions de les senyes són a tests/signals.rs
+const GAMES: u64 = 500;
+const VISIBILITY: f64 = 0.3;
+
+fn main() -> ExitCode {
+    let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|_| Box::new(SignalBot::new(Box::new(ExpertBot::new(ExpertConfig::default())))) as Box<dyn Behaviour>).collect();
+    let options: DriverOptions = DriverOptions { signals: Some(SignalConfig { visibility: VISIBILITY }), ..DriverOptions::default() };
+
+    let mut signals: usize = 0;
+    let mut rival_views: usize = 0;
+    let start: Instant = Instant::now();
+    for seed in 0..GAMES {
+        let mut log: GameLog = GameLog::new();
+        play_logged_game(&bots, seed, &options, &mut log);
+
+        for (seat, _, seen_by) in log.signals() {
+            rival_views += [(seat + 1) % 4, (seat + 3) % 4].iter().filter(|&&rival| seen_by[rival]).count();
+            signals += 1;
+        }
+    }
+    let us_per_game: f64 = start.elapsed().as_secs_f64() * 1e6 / GAMES as f64;
+
+    let start: Instant = Instant::now();
+    for seed in 0..GAMES {
+        play_logged_game(&bots, seed, &DriverOptions::default(), &mut GameLog::new());
+    }
+    let silent_us_per_game: f64 = start.elapsed().as_secs_f64() * 1e6 / GAMES as f64;
+
+    let rate: f64 = rival_views as f64 / (2 * signals) as f64;
+    println!("{} signals in {} games, rivals saw {:.1}% (expected {:.0}%)", signals, GAMES, rate * 100.0, VISIBILITY * 100.0);
+    println!("With signals: {:.0} us/game, without: {:.0} us/game", us_per_game, silent_us_per_game);
+
+    ExitCode::SUCCESS
+}
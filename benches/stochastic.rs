@@ -0,0 +1,43 @@
+use std::process::ExitCode;
+use std::time::Instant;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::weighted_bot::{WeightedBot, Weights};
+use rust_guinote::bot_behaviours::stochastic_bot::{EpsilonGreedyBot, EpsilonGreedyConfig, SoftmaxBot, SoftmaxConfig};
+use rust_guinote::driver::play_seeded_game;
+use rust_guinote::game::GameState;
+
+// Força i temps per partida dels embolcalls amb diferents epsilon i temperatures contra
+// l'ExpertBot. Les comprovacions dels embolcalls són a tests/stochastic.rs
+const GAMES: u64 = 400;
+
+fn main() -> ExitCode {
+    for epsilon in [0.0, 0.25, 1.0] {
+        let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| {
+            let expert: Box<dyn Behaviour> = Box::new(ExpertBot::new(ExpertConfig::default()));
+            match seat % 2 {
+                0 => Box::new(EpsilonGreedyBot::new(expert, EpsilonGreedyConfig { epsilon, seed: Some(seat as u64) })),
+                _ => expert
+            }
+        }).collect();
+        report(&format!("Epsilon {:.2}", epsilon), &bots);
+    }
+
+    for temperature in [0.1, 0.5, 2.0] {
+        let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| match seat % 2 {
+            0 => Box::new(SoftmaxBot::new(Box::new(WeightedBot::new(Weights::default())), SoftmaxConfig { temperature, seed: Some(seat as u64) })),
+            _ => Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>
+        }).collect();
+        report(&format!("Softmax {:.1}", temperature), &bots);
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn report(name: &str, bots: &[Box<dyn Behaviour>]) {
+    let start: Instant = Instant::now();
+    let wins: usize = (0..GAMES).filter(|&seed| play_seeded_game(bots, seed) == GameState::Team0Won).count();
+    let us_per_game: f64 = start.elapsed().as_secs_f64() * 1e6 / GAMES as f64;
+
+    println!("{} vs ExpertBot: {}/{} ({:.0} us/game)", name, wins, GAMES, us_per_game);
+}
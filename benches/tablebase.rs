@@ -0,0 +1,46 @@
+use std::process::ExitCode;
+use std::hint::black_box;
+use std::time::Instant;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rust_guinote::game::{Board, Pal};
+use rust_guinote::tablebase::Tablebase;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{baza_start_cards, last_baza_positions};
+
+// Temps de generar la taula completa d'una carta per jugador, un bloc de 3 cartes per jugador
+// (amb els de les bazas següents) i de consultar posicions de les últimes bazas de partides reals. Les comprovacions són a tests/tablebase.rs
+const GAMES: u64 = 2000;
+const PROBES: usize = 1_000_000;
+const CARDS_PER_PLAYER: usize = 3;
+
+fn main() -> ExitCode {
+    let start: Instant = Instant::now();
+    let full: Tablebase = Tablebase::generate_all(Pal::Orus, 1).unwrap();
+    println!("Generated the full table of 1 card per player: {} entries in {} blocks in {:.1} s", full.len(), full.get_blocks(), start.elapsed().as_secs_f64());
+
+    let start: Instant = Instant::now();
+    let mut tablebase: Tablebase = Tablebase::generate(Pal::Orus, CARDS_PER_PLAYER, 1, &mut StdRng::seed_from_u64(0)).unwrap();
+    let generation_seconds: f64 = start.elapsed().as_secs_f64();
+    println!("Generated {} entries in {} blocks in {:.1} s", tablebase.len(), tablebase.get_blocks(), generation_seconds);
+
+    let positions: Vec<Board> = last_baza_positions(GAMES, 1);
+    for board in positions.iter() {
+        tablebase.generate_block(baza_start_cards(board)).unwrap();
+    }
+
+    let start: Instant = Instant::now();
+    let mut checksum: u32 = 0;
+    for i in 0..PROBES {
+        checksum += tablebase.probe(&positions[i % positions.len()]).unwrap()[0];
+    }
+    let ns_per_probe: f64 = start.elapsed().as_secs_f64() * 1e9 / PROBES as f64;
+    black_box(checksum);
+
+    println!("{:.0} ns/probe over {} last-baza positions", ns_per_probe, positions.len());
+
+    ExitCode::SUCCESS
+}
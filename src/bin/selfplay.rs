@@ -0,0 +1,66 @@
+use std::env;
+use std::process::ExitCode;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{expert_bot::{ExpertBot, ExpertConfig}, random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::bot_behaviours::weighted_bot::{WeightedBot, Weights};
+use rust_guinote::bot_behaviours::stochastic_bot::{EpsilonGreedyBot, EpsilonGreedyConfig};
+use rust_guinote::dataset::{generate_shard_file, shard_path, SelfPlayConfig};
+
+const USAGE: &str = "Usage: selfplay <output directory> [shards] [games per shard] [bot] [first shard]";
+
+// Genera shards de dades de self-play amb el mateix bot als quatre seients.
+// Bots: random, smart, expert, weighted, weighted:<weights file>, i epsilon:<epsilon>:<bot> per
+// explorar. L'atzar d'epsilon surt de la llavor de cada shard i seient, perquè qualsevol shard
+// es pugui regenerar sol
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let Some(directory) = args.get(1) else {
+        println!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    let (Some(shards), Some(games_per_shard), Some(first_shard)) = (parse(args.get(2), 1), parse(args.get(3), 1000), parse(args.get(5), 0)) else {
+        println!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    let bot_name: &str = args.get(4).map_or("expert", String::as_str);
+
+    let config: SelfPlayConfig = SelfPlayConfig { games_per_shard: games_per_shard as u64, ..SelfPlayConfig::default() };
+    let make_bot = |_seat: usize, seed: u64| create_bot(bot_name, seed);
+
+    for shard in first_shard..first_shard + shards {
+        match generate_shard_file(&config, &make_bot, shard, directory) {
+            Ok(records) => println!("{}: {} records (seeds {:?})", shard_path(directory, shard).display(), records, config.shard_header(shard).seeds()),
+            Err(error) => {
+                println!("{}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn parse(arg: Option<&String>, default: usize) -> Option<usize> {
+    match arg {
+        None => Some(default),
+        Some(arg) => arg.parse::<usize>().ok()
+    }
+}
+
+fn create_bot(name: &str, seed: u64) -> Result<Box<dyn Behaviour>, String> {
+    if let Some(path) = name.strip_prefix("weighted:") {
+        return Ok(Box::new(WeightedBot::new(Weights::load(path)?)));
+    }
+    if let Some((epsilon, inner)) = name.strip_prefix("epsilon:").and_then(|rest| rest.split_once(':')) {
+        let epsilon: f64 = epsilon.parse::<f64>().map_err(|_| format!("Invalid epsilon: {}", epsilon))?;
+        return Ok(Box::new(EpsilonGreedyBot::new(create_bot(inner, seed)?, EpsilonGreedyConfig { epsilon, seed: Some(seed) })));
+    }
+
+    match name {
+        "random" => Ok(Box::new(RandomBot {})),
+        "smart" => Ok(Box::new(SmartBot {})),
+        "expert" => Ok(Box::new(ExpertBot::new(ExpertConfig::default()))),
+        "weighted" => Ok(Box::new(WeightedBot::new(Weights::default()))),
+        _ => Err(format!("Unknown bot: {}", name))
+    }
+}
@@ -0,0 +1,59 @@
+use std::env;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Instant;
+use rand::{thread_rng, SeedableRng};
+use rand::rngs::StdRng;
+use rust_guinote::game::Pal;
+use rust_guinote::tablebase::Tablebase;
+
+const USAGE: &str = "Usage: tablebase generate <trumfo pal> <cards per player> <blocks | all> <file> [seed]\n       tablebase verify <file> [samples]";
+
+// Genera o verifica una taula de finals de l'arrastre
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let result: Result<(), String> = match args.get(1).map(String::as_str) {
+        Some("generate") if args.len() == 6 || args.len() == 7 => generate(&args[2], &args[3], &args[4], &args[5], args.get(6).map(String::as_str)),
+        Some("verify") if args.len() == 3 || args.len() == 4 => verify(&args[2], args.get(3).map(String::as_str)),
+        _ => Err(USAGE.to_string())
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            println!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Amb "all" genera tots els blocs. Si no, genera els de `blocks` conjunts de cartes triats a
+// l'atzar amb la llavor `seed` (0 per defecte) i els de les bazas següents: la taula és parcial
+fn generate(trumfo: &str, cards_per_player: &str, blocks: &str, path: &str, seed: Option<&str>) -> Result<(), String> {
+    let trumfo: Pal = Pal::from_str(trumfo)?;
+    let cards_per_player: usize = cards_per_player.parse::<usize>().map_err(|_| USAGE.to_string())?;
+    let seed: u64 = seed.map_or(Ok(0), str::parse::<u64>).map_err(|_| USAGE.to_string())?;
+
+    let start: Instant = Instant::now();
+    let tablebase: Tablebase = if blocks == "all" {
+        Tablebase::generate_all(trumfo, cards_per_player)?
+    } else {
+        let blocks: usize = blocks.parse::<usize>().map_err(|_| USAGE.to_string())?;
+        Tablebase::generate(trumfo, cards_per_player, blocks, &mut StdRng::seed_from_u64(seed))?
+    };
+    tablebase.save(path)?;
+    println!("{} entries in {} blocks written to {} in {:.1} s", tablebase.len(), tablebase.get_blocks(), path, start.elapsed().as_secs_f64());
+
+    Ok(())
+}
+
+fn verify(path: &str, samples: Option<&str>) -> Result<(), String> {
+    let samples: usize = samples.map_or(Ok(10000), str::parse::<usize>).map_err(|_| USAGE.to_string())?;
+
+    let tablebase: Tablebase = Tablebase::load(path)?;
+    tablebase.verify(samples, &mut thread_rng())?;
+    println!("{}: {} random entries match the solver ({} cards per player, trumfo {})",
+             path, samples, tablebase.get_cards_per_player(), tablebase.get_trumfo());
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{expert_bot::{ExpertBot, ExpertConfig}, smart_bot::SmartBot};
+use rust_guinote::bot_behaviours::weighted_bot::{WeightedBot, Weights, FEATURE_COUNT};
+use rust_guinote::stats::WinRate;
+use rust_guinote::tuning::{Candidate, CandidateFactory, ReferenceFactory, Tuner, TunerConfig};
+
+const VALIDATION_GAMES: usize = 2000;
+
+// Ajusta els pesos del WeightedBot contra l'ExpertBot i el SmartBot.
+// Usage: tune [generations] [checkpoint file] [output weights file]
+// Si el checkpoint existeix es continua des d'ell.
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let generations: usize = match args.get(1).map(|generations| generations.parse::<usize>()) {
+        None => 20,
+        Some(Ok(generations)) => generations,
+        Some(Err(_)) => {
+            println!("Invalid number of generations. Usage: tune [generations] [checkpoint file] [output weights file]");
+            return ExitCode::FAILURE;
+        }
+    };
+    let checkpoint: &str = args.get(2).map_or("tuning.checkpoint", String::as_str);
+    let output: &str = args.get(3).map_or("tuned.weights", String::as_str);
+
+    let make_candidate: CandidateFactory = Box::new(|params: &[f64]| Box::new(WeightedBot::new(weights(params))) as Box<dyn Behaviour>);
+    let references: Vec<ReferenceFactory> = vec![
+        Box::new(|| Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>),
+        Box::new(|| Box::new(SmartBot {}) as Box<dyn Behaviour>)
+    ];
+
+    let tuner: Result<Tuner, String> = if Path::new(checkpoint).exists() {
+        Tuner::resume(TunerConfig::default(), checkpoint, FEATURE_COUNT, make_candidate, references)
+    } else {
+        Tuner::new(TunerConfig::default(), &Weights::default().0, make_candidate, references)
+    };
+    let mut tuner: Tuner = match tuner {
+        Ok(tuner) => tuner,
+        Err(error) => {
+            println!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut best: Option<Candidate> = None;
+    while tuner.get_generation() < generations {
+        let candidate: Candidate = tuner.step();
+        let (low, high): (f64, f64) = candidate.win_rate.confidence_interval(WinRate::Z_95);
+        println!("Generation {}: best win rate {:.1}% (95% CI {:.1}%-{:.1}%)",
+                 tuner.get_generation(), candidate.win_rate.rate() * 100.0, low * 100.0, high * 100.0);
+
+        if let Err(error) = tuner.save_checkpoint(checkpoint) {
+            println!("{}", error);
+            return ExitCode::FAILURE;
+        }
+        best = Some(candidate);
+    }
+
+    let Some(best) = best else {
+        println!("Checkpoint {} is already at generation {}", checkpoint, tuner.get_generation());
+        return ExitCode::SUCCESS;
+    };
+
+    // La taxa de la selecció està esbiaixada a l'alça: es torna a mesurar amb partides noves
+    let win_rate: WinRate = tuner.validate(&best.params, VALIDATION_GAMES);
+    let (low, high): (f64, f64) = win_rate.confidence_interval(WinRate::Z_95);
+    println!("Best weights:\n{}", weights(&best.params));
+    println!("Validation win rate: {:.1}% (95% CI {:.1}%-{:.1}%) over {} games",
+             win_rate.rate() * 100.0, low * 100.0, high * 100.0, win_rate.games);
+
+    if let Err(error) = weights(&best.params).save(output) {
+        println!("{}", error);
+        return ExitCode::FAILURE;
+    }
+    println!("Saved to {}", output);
+
+    ExitCode::SUCCESS
+}
+
+// Tuner::resume ja ha comprovat que hi ha FEATURE_COUNT paràmetres
+fn weights(params: &[f64]) -> Weights {
+    let mut weights: [f64; FEATURE_COUNT] = [0.0; FEATURE_COUNT];
+    weights.copy_from_slice(params);
+
+    Weights(weights)
+}
@@ -1,7 +1,62 @@
-use crate::{game::GameState, Board};
+use std::time::{Duration, Instant};
+use crate::driver::played_card;
+use crate::game::{Board, Card, GameState};
+use crate::signals::Signal;
+
+// Temps que els bots que cerquen es reserven abans del `deadline` per acabar la iteració en curs,
+// alliberar l'arbre i jugar la carta, a més d'un 5% del temps disponible
+pub const DEADLINE_MARGIN: Duration = Duration::from_millis(2);
+
+// Moment en què ha de parar de cercar un bot que ha de jugar abans de `deadline`
+pub fn search_deadline(deadline: Instant) -> Instant {
+    let now: Instant = Instant::now();
+    let available: Duration = deadline.saturating_duration_since(now);
+
+    now + available.saturating_sub(DEADLINE_MARGIN + available / 20)
+}
 
 pub trait Behaviour {
     fn name(&self) -> String;
     fn play_card(&self, board: &mut Board) -> Result<GameState, String>;
     fn post_baza_actions(&self, board: &mut Board, player: usize);
-}
\ No newline at end of file
+
+    // Com play_card, però amb la jugada acabada abans de `deadline`, que és el temps que li queda
+    // al rellotge del seient. Els bots que no cerquen no en fan cas
+    fn play_card_until(&self, board: &mut Board, _deadline: Instant) -> Result<GameState, String> {
+        self.play_card(board)
+    }
+
+    // Carta que jugaria el bot, sense jugar-la i sense canviar l'estat del bot. `deadline` és com
+    // el de play_card_until. Per defecte None: els bots amb atzar propi, comptadors o memòries
+    // que canvien en jugar no ho poden saber sense jugar
+    fn query_card(&self, _board: &Board, _deadline: Option<Instant>) -> Option<Card> {
+        None
+    }
+
+    // Com post_baza_actions, acabant abans de `deadline`
+    fn post_baza_actions_until(&self, board: &mut Board, player: usize, _deadline: Instant) {
+        self.post_baza_actions(board, player)
+    }
+
+    // Puntuació de cada carta legal, més alta com més la vol jugar el bot. `deadline` és com el de
+    // query_card. Per defecte és 1 per a la carta que jugaria (sobre una còpia del Board) i 0 per
+    // a la resta
+    fn card_scores(&self, board: &Board, deadline: Option<Instant>) -> Vec<(Card, f64)> {
+        let mut copy: Board = board.clone();
+        let result: Result<GameState, String> = match deadline {
+            Some(deadline) => self.play_card_until(&mut copy, deadline),
+            None => self.play_card(&mut copy)
+        };
+        let chosen: Option<Card> = result.ok().and_then(|_| played_card(board, &copy));
+
+        board.get_legal_cards().iter().map(|card| (card, if Some(card) == chosen { 1.0 } else { 0.0 })).collect()
+    }
+
+    // Seña per al company abans de cada baza (veure signals.rs). Per defecte no se'n fa cap
+    fn send_signal(&self, _board: &Board, _player: usize) -> Option<Signal> {
+        None
+    }
+
+    // `from` ha fet la seña `signal` i `player` l'ha vista
+    fn receive_signal(&self, _board: &Board, _player: usize, _from: usize, _signal: Signal) {}
+}
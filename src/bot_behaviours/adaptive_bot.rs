@@ -0,0 +1,98 @@
+use std::cell::Ref;
+use std::time::Instant;
+use crate::{bot_behaviour::Behaviour, card_set::CardSet, game::{Board, Card, GameState, Hand, Pal}};
+use crate::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use crate::modeling::{ModelStore, SharedModels, Tendency};
+
+// ExpertBot que s'adapta als rivals segons els models de la sessió (veure modeling.rs). Quan el
+// company va guanyant la baza i cap dels rivals que encara han de jugar sol guanyar les bazas que
+// pot (o ja no en queda cap), hi carrega la carta de més punts que no sigui trumfo en lloc de
+// tirar la més baixa.
+
+pub struct AdaptiveConfig {
+    pub expert: ExpertConfig,
+    // Taxa de "overtake" per sota de la qual es considera que un rival no sol tallar
+    pub load_threshold: f64
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        AdaptiveConfig {
+            expert: ExpertConfig::default(),
+            load_threshold: 0.35
+        }
+    }
+}
+
+pub struct AdaptiveBot {
+    pub expert: ExpertBot,
+    pub load_threshold: f64,
+    models: SharedModels
+}
+
+impl AdaptiveBot {
+    pub fn new(config: AdaptiveConfig, models: SharedModels) -> Self {
+        AdaptiveBot {
+            expert: ExpertBot::new(config.expert),
+            load_threshold: config.load_threshold,
+            models
+        }
+    }
+
+    // Carta que jugaria el bot i la regla que l'ha triada
+    pub fn explain(&self, board: &Board) -> (Card, &'static str) {
+        match self.choose_load(board) {
+            Some(card) => (card, "els rivals no solen guanyar, carregar al company"),
+            None => self.expert.explain(board)
+        }
+    }
+
+    fn choose_load(&self, board: &Board) -> Option<Card> {
+        let player: usize = board.current_player;
+        let trumfo: Pal = board.current_trumfo.pal;
+        let baza: [Option<Card>; 4] = board.get_current_baza();
+        if Board::is_baza_empty(baza) {
+            return None;
+        }
+
+        let winner: usize = Board::determine_baza_winner(Board::baza_starting_player(player, baza), trumfo, baza);
+        if winner != (player + 2) % 4 {
+            return None;
+        }
+
+        // Els rivals que encara no han jugat
+        let models: Ref<ModelStore> = self.models.borrow();
+        let cautious_rivals: bool = [(player + 1) % 4, (player + 3) % 4].iter()
+                                                                         .filter(|&&rival| baza[rival].is_none())
+                                                                         .all(|&rival| models.at_seat(rival).rate(Tendency::Overtake) < self.load_threshold);
+        if !cautious_rivals {
+            return None;
+        }
+
+        let loads: CardSet = board.get_legal_cards().iter().filter(|card| card.pal != trumfo && card.value() >= 10).collect();
+        loads.iter().max_by_key(|card| (card.value(), card.rank.strength()))
+    }
+}
+
+impl Behaviour for AdaptiveBot {
+    fn name(&self) -> String {
+        "AdaptiveBot".to_string()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        let (card, _): (Card, &'static str) = self.explain(board);
+        let hand: Hand = board.get_current_player_hand();
+        let card_index: usize = hand.get_index(&card).unwrap();
+
+        board.play_card(card_index)
+    }
+
+    // Només llegeix els models
+    fn query_card(&self, board: &Board, _deadline: Option<Instant>) -> Option<Card> {
+        Some(self.explain(board).0)
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        self.expert.post_baza_actions(board, player);
+    }
+}
@@ -0,0 +1,81 @@
+use std::cell::RefCell;
+use std::time::Instant;
+use rand::thread_rng;
+use crate::{bot_behaviour::Behaviour, game::{Board, Card, GameState, Hand}};
+use crate::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use crate::cfr::{CfrConfig, CfrSolver, StrategyTable};
+
+// Bot que juga les últimes bazas de l'arrastre amb l'estratègia de CFR i la resta amb `fallback`.
+// La taula d'estratègia del final es guarda i es fa servir fins que s'acaba la ronda.
+
+pub struct CfrBotConfig {
+    pub iterations: usize,
+    pub solver: CfrConfig,
+    pub fallback: Box<dyn Behaviour>
+}
+
+impl Default for CfrBotConfig {
+    fn default() -> Self {
+        CfrBotConfig {
+            iterations: 100,
+            solver: CfrConfig::default(),
+            fallback: Box::new(ExpertBot::new(ExpertConfig::default()))
+        }
+    }
+}
+
+pub struct CfrBot {
+    pub config: CfrBotConfig,
+    table: RefCell<StrategyTable>
+}
+
+impl CfrBot {
+    pub fn new(config: CfrBotConfig) -> Self {
+        CfrBot {
+            config,
+            table: RefCell::new(StrategyTable::default())
+        }
+    }
+
+    // Carta de l'estratègia de CFR, o None si la posició és massa gran per al solver
+    fn choose_card(&self, board: &Board) -> Option<Card> {
+        if self.table.borrow().get(board).is_none() {
+            let mut solver: CfrSolver = CfrSolver::new(board, self.config.solver).ok()?;
+            solver.run(self.config.iterations);
+            *self.table.borrow_mut() = solver.strategy();
+        }
+
+        self.table.borrow().sample(board, &mut thread_rng())
+    }
+}
+
+impl Behaviour for CfrBot {
+    fn name(&self) -> String {
+        "CfrBot".to_string()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        let Some(card) = self.choose_card(board) else {
+            return self.config.fallback.play_card(board);
+        };
+        let hand: Hand = board.get_current_player_hand();
+        let card_index: usize = hand.get_index(&card).unwrap();
+
+        board.play_card(card_index)
+    }
+
+    fn play_card_until(&self, board: &mut Board, deadline: Instant) -> Result<GameState, String> {
+        match self.choose_card(board) {
+            Some(_) => self.play_card(board),
+            None => self.config.fallback.play_card_until(board, deadline)
+        }
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        self.config.fallback.post_baza_actions(board, player);
+    }
+
+    fn post_baza_actions_until(&self, board: &mut Board, player: usize, deadline: Instant) {
+        self.config.fallback.post_baza_actions_until(board, player, deadline);
+    }
+}
@@ -0,0 +1,244 @@
+use std::cell::{RefCell, RefMut};
+use std::fmt;
+use std::time::Instant;
+use crate::{bot_behaviour::Behaviour, card_set::CardSet, game::{Board, Card, GameState, Hand}};
+use crate::signals::Signal;
+
+// Meta-bot que pregunta la carta a cada membre i juga la que surt de `rule`. Els membres es
+// consulten amb query_card, que no toca ni el Board ni l'estat del membre: els vots que no
+// guanyen no es juguen i no han de deixar rastre. Els membres que no la implementen (els que
+// tenen atzar propi, com els de stochastic_bot.rs, o memòries, com el CfrBot) no poden votar i
+// compten com a fallades. Els cantes, el canvi i les senyes són cosa del primer membre, que fa
+// de líder; totes les senyes que arriben es passen a tots els membres.
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EnsembleRule {
+    // La carta amb més vots; en cas d'empat, la del primer membre que l'ha votada
+    Majority,
+    // Com Majority, però cada vot compta el pes del membre
+    Weighted,
+    // Juga el líder, excepte si almenys `vetoes` dels altres membres voten una mateixa carta
+    // diferent; llavors es juga aquesta
+    Veto { vetoes: usize }
+}
+
+pub struct Member {
+    pub bot: Box<dyn Behaviour>,
+    pub weight: f64
+}
+
+impl Member {
+    pub fn new(bot: Box<dyn Behaviour>) -> Self {
+        Member { bot, weight: 1.0 }
+    }
+}
+
+pub struct EnsembleConfig {
+    pub members: Vec<Member>,
+    pub rule: EnsembleRule
+}
+
+// Desacords acumulats. Només compten les jugades amb més d'una carta legal
+#[derive(Clone, Default, Debug)]
+pub struct EnsembleStats {
+    pub decisions: usize,
+    // Tots els membres que han votat han triat la mateixa carta
+    pub unanimous: usize,
+    // El líder no ha jugat la carta que volia
+    pub overruled: usize,
+    // Per membre: vegades que s'ha jugat la seva carta i vegades que no ha pogut votar
+    pub agreed: Vec<usize>,
+    pub failures: Vec<usize>
+}
+
+impl EnsembleStats {
+    pub fn disagreement_rate(&self) -> f64 {
+        if self.decisions == 0 {
+            return 0.0;
+        }
+
+        1.0 - self.unanimous as f64 / self.decisions as f64
+    }
+}
+
+pub struct EnsembleBot {
+    pub config: EnsembleConfig,
+    stats: RefCell<EnsembleStats>
+}
+
+impl EnsembleBot {
+    pub fn new(config: EnsembleConfig) -> Result<Self, String> {
+        if config.members.is_empty() {
+            return Err("L'ensemble necessita almenys un membre.".to_string());
+        }
+        if config.members.iter().any(|member| member.weight.is_nan() || member.weight < 0.0) {
+            return Err("Els pesos dels membres han de ser positius.".to_string());
+        }
+
+        let stats: EnsembleStats = EnsembleStats {
+            agreed: vec![0; config.members.len()],
+            failures: vec![0; config.members.len()],
+            ..EnsembleStats::default()
+        };
+
+        Ok(EnsembleBot { config, stats: RefCell::new(stats) })
+    }
+
+    pub fn get_stats(&self) -> EnsembleStats {
+        self.stats.borrow().clone()
+    }
+
+    pub fn reset_stats(&self) {
+        let members: usize = self.config.members.len();
+        *self.stats.borrow_mut() = EnsembleStats {
+            agreed: vec![0; members],
+            failures: vec![0; members],
+            ..EnsembleStats::default()
+        };
+    }
+
+    // Carta que proposa cada membre, o None si no ha pogut votar
+    pub fn votes(&self, board: &Board) -> Vec<Option<Card>> {
+        self.votes_until(board, None)
+    }
+
+    // Amb `deadline` cada membre té una part igual del temps que queda quan li toca, així el que
+    // no gasta un membre passa als següents i una cerca no es menja el temps de tots
+    fn votes_until(&self, board: &Board, deadline: Option<Instant>) -> Vec<Option<Card>> {
+        let members: usize = self.config.members.len();
+        self.config.members.iter().enumerate().map(|(member, Member { bot, .. })| {
+            let member_deadline: Option<Instant> = deadline.map(|deadline| {
+                let now: Instant = Instant::now();
+                now + deadline.saturating_duration_since(now) / (members - member) as u32
+            });
+            bot.query_card(board, member_deadline)
+        }).collect()
+    }
+
+    // Carta guanyadora segons `rule`, o None si no ha votat ningú
+    pub fn tally(&self, votes: &[Option<Card>]) -> Option<Card> {
+        match self.config.rule {
+            EnsembleRule::Majority => EnsembleBot::most_voted(votes, |_| 1.0),
+            EnsembleRule::Weighted => EnsembleBot::most_voted(votes, |member| self.config.members[member].weight),
+            EnsembleRule::Veto { vetoes } => {
+                let challengers: Vec<Option<Card>> = votes[1..].iter().map(|&vote| vote.filter(|&card| Some(card) != votes[0])).collect();
+                let challenger: Option<Card> = EnsembleBot::most_voted(&challengers, |_| 1.0);
+                let support: usize = challengers.iter().filter(|&&vote| vote.is_some() && vote == challenger).count();
+
+                match (votes[0], challenger) {
+                    (Some(_), Some(card)) if vetoes > 0 && support >= vetoes => Some(card),
+                    (Some(card), _) => Some(card),
+                    (None, challenger) => challenger
+                }
+            }
+        }
+    }
+
+    fn play_voted(&self, board: &mut Board, deadline: Option<Instant>) -> Result<GameState, String> {
+        let legal_cards: CardSet = board.get_legal_cards();
+        let card: Card = if legal_cards.len() == 1 {
+            legal_cards.nth(0).unwrap()
+        } else {
+            let votes: Vec<Option<Card>> = self.votes_until(board, deadline);
+            let Some(card) = self.tally(&votes) else {
+                return Err("Cap membre de l'ensemble ha pogut triar carta.".to_string());
+            };
+            self.record(&votes, card);
+            card
+        };
+
+        let hand: Hand = board.get_current_player_hand();
+        let card_index: usize = hand.get_index(&card).unwrap();
+
+        board.play_card(card_index)
+    }
+
+    fn most_voted<F: Fn(usize) -> f64>(votes: &[Option<Card>], weight: F) -> Option<Card> {
+        let mut scores: [f64; 40] = [0.0; 40];
+        let mut voted: CardSet = CardSet::EMPTY;
+        let mut order: Vec<Card> = Vec::new();
+        for (member, vote) in votes.iter().enumerate() {
+            let Some(card) = *vote else {
+                continue;
+            };
+            if !voted.contains(card) {
+                voted.insert(card);
+                order.push(card);
+            }
+            scores[card.index()] += weight(member);
+        }
+
+        // max_by es queda amb l'últim dels empatats, per això es recorre al revés
+        order.into_iter().rev().max_by(|a, b| scores[a.index()].total_cmp(&scores[b.index()]))
+    }
+
+    fn record(&self, votes: &[Option<Card>], card: Card) {
+        let mut stats: RefMut<EnsembleStats> = self.stats.borrow_mut();
+        let cast: Vec<Card> = votes.iter().flatten().copied().collect();
+
+        stats.decisions += 1;
+        if cast.iter().all(|&vote| vote == cast[0]) {
+            stats.unanimous += 1;
+        }
+        if votes[0] != Some(card) {
+            stats.overruled += 1;
+        }
+        for (member, vote) in votes.iter().enumerate() {
+            match vote {
+                Some(vote) if *vote == card => stats.agreed[member] += 1,
+                Some(_) => (),
+                None => stats.failures[member] += 1
+            }
+        }
+    }
+}
+
+impl Behaviour for EnsembleBot {
+    fn name(&self) -> String {
+        let names: Vec<String> = self.config.members.iter().map(|member| member.bot.name()).collect();
+        format!("EnsembleBot({})", names.join(", "))
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        self.play_voted(board, None)
+    }
+
+    fn play_card_until(&self, board: &mut Board, deadline: Instant) -> Result<GameState, String> {
+        self.play_voted(board, Some(deadline))
+    }
+
+    // Com play_card, però sense apuntar la decisió a les estadístiques
+    fn query_card(&self, board: &Board, deadline: Option<Instant>) -> Option<Card> {
+        self.tally(&self.votes_until(board, deadline))
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        self.config.members[0].bot.post_baza_actions(board, player);
+    }
+
+    fn post_baza_actions_until(&self, board: &mut Board, player: usize, deadline: Instant) {
+        self.config.members[0].bot.post_baza_actions_until(board, player, deadline);
+    }
+
+    fn send_signal(&self, board: &Board, player: usize) -> Option<Signal> {
+        self.config.members[0].bot.send_signal(board, player)
+    }
+
+    fn receive_signal(&self, board: &Board, player: usize, from: usize, signal: Signal) {
+        for member in self.config.members.iter() {
+            member.bot.receive_signal(board, player, from, signal);
+        }
+    }
+}
+
+impl fmt::Display for EnsembleStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} decisions, {:.1}% with disagreement, leader overruled {} times",
+                 self.decisions, self.disagreement_rate() * 100.0, self.overruled)?;
+        for (member, (agreed, failures)) in self.agreed.iter().zip(self.failures.iter()).enumerate() {
+            writeln!(f, "  member {}: {} cards played, {} failed queries", member, agreed, failures)?;
+        }
+
+        Ok(())
+    }
+}
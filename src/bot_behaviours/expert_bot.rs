@@ -0,0 +1,193 @@
+use std::cmp::Reverse;
+use std::time::Instant;
+use strum::IntoEnumIterator;
+use crate::{bot_behaviour::Behaviour, card_set::CardSet, card_tracking::HandBeliefs, game::{Board, Card, GameState, Hand, Pal, Rank}};
+
+// Bot de regles que juga com un bon jugador de club. Cada heurística es pot desactivar per
+// separat, i `explain` diu quina regla ha decidit la carta.
+
+pub struct ExpertConfig {
+    // No jugar el 7 de trumfo mentre es pugui canviar pel trumfo girat
+    pub keep_trumfo_seven: bool,
+    // Si el company va guanyant la baza, tirar la carta més baixa; si no, carregar-li punts
+    pub low_when_partner_wins: bool,
+    // En arrastre, sortir de trumfo mentre els rivals en puguin tenir
+    pub lead_trumfos_in_arrastre: bool,
+    // No separar la sota i el rey d'un pal que encara no s'ha cantat
+    pub keep_cante_pairs: bool,
+    // A la penúltima baza, guardar la carta forta per guanyar l'última i les 10 d'últimes
+    pub careful_ultimas: bool
+}
+
+impl Default for ExpertConfig {
+    fn default() -> Self {
+        ExpertConfig {
+            keep_trumfo_seven: true,
+            low_when_partner_wins: true,
+            lead_trumfos_in_arrastre: true,
+            keep_cante_pairs: true,
+            careful_ultimas: true
+        }
+    }
+}
+
+pub struct ExpertBot {
+    pub config: ExpertConfig
+}
+
+impl ExpertBot {
+    pub fn new(config: ExpertConfig) -> Self {
+        ExpertBot { config }
+    }
+
+    // Carta que jugaria el bot i la regla que l'ha triada
+    pub fn explain(&self, board: &Board) -> (Card, &'static str) {
+        let player: usize = board.current_player;
+        let hand: CardSet = board.get_current_player_hand().cards;
+        let legal_cards: CardSet = board.get_legal_cards();
+        let trumfo: Pal = board.current_trumfo.pal;
+
+        if legal_cards.len() == 1 {
+            return (legal_cards.nth(0).unwrap(), "única carta legal");
+        }
+
+        // Cartes que no es volen jugar si hi ha alternativa
+        let protected: CardSet = self.protected_cards(board, hand);
+        let candidates: CardSet = if (legal_cards - protected).is_empty() { legal_cards } else { legal_cards - protected };
+
+        let baza: [Option<Card>; 4] = board.get_current_baza();
+        let winner: Option<usize> = if Board::is_baza_empty(baza) {
+            None
+        } else {
+            Some(Board::determine_baza_winner(Board::baza_starting_player(player, baza), trumfo, baza))
+        };
+
+        if self.config.careful_ultimas && board.is_arrastre() && hand.len() == 2 && candidates.len() == 2 {
+            return (self.play_penultimate(candidates, baza, winner, player, trumfo), "guardar la carta forta per a les 10 d'últimes");
+        }
+
+        match winner {
+            // Sortida
+            None => {
+                if self.config.lead_trumfos_in_arrastre && board.is_arrastre() {
+                    let trumfos: CardSet = candidates & CardSet::suit(trumfo);
+                    let beliefs: HandBeliefs = HandBeliefs::new(board, player);
+                    let rivals_have_trumfos: bool = [(player + 1) % 4, (player + 3) % 4].iter()
+                                                                                        .any(|&rival| !beliefs.is_void(rival, trumfo));
+                    if !trumfos.is_empty() && rivals_have_trumfos {
+                        return (ExpertBot::strongest(trumfos, trumfo), "arrossegar trumfos");
+                    }
+                }
+
+                (ExpertBot::cheapest(candidates, trumfo), "sortir amb la carta més baixa")
+            },
+            // El company va guanyant
+            Some(winner) if winner == (player + 2) % 4 => {
+                if self.config.low_when_partner_wins {
+                    (ExpertBot::cheapest(candidates, trumfo), "el company guanya, carta baixa")
+                } else {
+                    (ExpertBot::richest(candidates, trumfo), "el company guanya, carregar punts")
+                }
+            },
+            Some(winner) => {
+                let winning_card: Card = baza[winner].unwrap();
+                let winning_cards: CardSet = candidates & CardSet::better_than(winning_card, trumfo);
+                if winner % 2 != player % 2 && !winning_cards.is_empty() {
+                    (ExpertBot::cheapest(winning_cards, trumfo), "guanyar la baza amb la carta més barata")
+                } else {
+                    (ExpertBot::cheapest(candidates, trumfo), "no es pot guanyar, carta baixa")
+                }
+            }
+        }
+    }
+
+    fn protected_cards(&self, board: &Board, hand: CardSet) -> CardSet {
+        let mut protected: CardSet = CardSet::EMPTY;
+
+        // El canvi només es pot fer mentre queda mazo
+        let seven: Card = Card { pal: board.current_trumfo.pal, rank: Rank::Siete };
+        if self.config.keep_trumfo_seven && !board.is_arrastre() && hand.contains(seven) {
+            protected.insert(seven);
+        }
+
+        if self.config.keep_cante_pairs {
+            for pal in Pal::iter().filter(|pal| !board.get_cantes().contains(pal)) {
+                let pair: CardSet = CardSet::single(Card { pal, rank: Rank::Sota }) | CardSet::single(Card { pal, rank: Rank::Rey });
+                if (pair - hand).is_empty() {
+                    protected |= pair;
+                }
+            }
+        }
+
+        protected
+    }
+
+    // Amb dues cartes es juga la fluixa i es guarda la forta per a l'última baza, excepte si
+    // només la forta guanya una baza dels rivals que ja val 10 punts o més
+    fn play_penultimate(&self, candidates: CardSet, baza: [Option<Card>; 4], winner: Option<usize>, player: usize, trumfo: Pal) -> Card {
+        let strong: Card = ExpertBot::strongest(candidates, trumfo);
+        let weak: Card = (candidates - CardSet::single(strong)).nth(0).unwrap();
+
+        if let Some(winner) = winner {
+            let winning_card: Card = baza[winner].unwrap();
+            let baza_points: u32 = baza.iter().flatten().map(|card| card.value()).sum();
+            let rivals_winning: bool = winner % 2 != player % 2;
+            if rivals_winning && baza_points >= 10 && strong.is_better_than(winning_card, trumfo) && !weak.is_better_than(winning_card, trumfo) {
+                return strong;
+            }
+        }
+
+        weak
+    }
+
+    // La carta que menys es perd: millor que no sigui trumfo, i de pocs punts i poca força
+    fn cheapest(cards: CardSet, trumfo: Pal) -> Card {
+        cards.iter()
+             .min_by_key(|card| (card.pal == trumfo, card.value(), card.rank.strength()))
+             .unwrap()
+    }
+
+    // La que dona més punts a la baza sense gastar trumfos, i de les que en donen igual la més fluixa
+    fn richest(cards: CardSet, trumfo: Pal) -> Card {
+        cards.iter()
+             .max_by_key(|card| (card.pal != trumfo, card.value(), Reverse(card.rank.strength())))
+             .unwrap()
+    }
+
+    fn strongest(cards: CardSet, trumfo: Pal) -> Card {
+        cards.iter()
+             .reduce(|a, b| if a.is_better_than(b, trumfo) { a } else { b })
+             .unwrap()
+    }
+}
+
+impl Behaviour for ExpertBot {
+    fn name(&self) -> String {
+        "ExpertBot".to_string()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        let (card, _): (Card, &'static str) = self.explain(board);
+        let hand: Hand = board.get_current_player_hand();
+        let card_index: usize = hand.get_index(&card).unwrap();
+
+        board.play_card(card_index)
+    }
+
+    fn query_card(&self, board: &Board, _deadline: Option<Instant>) -> Option<Card> {
+        Some(self.explain(board).0)
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        // Cantes
+        let available_pals: Vec<Pal> = board.get_available_cantes(player);
+        for pal in available_pals {
+            board.cantar(player, pal).unwrap();
+        }
+
+        // Canvi trumfo
+        if board.can_change_trumfo(player) {
+            board.change_trumfo_card(player).unwrap();
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use std::time::{Duration, Instant};
+use rand::{thread_rng, Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::{bot_behaviour::{search_deadline, Behaviour}, card_set::CardSet, determinization::HandSampler, evaluation::outcome_reward, game::{Board, Card, GameState, Hand, Pal}};
+
+// Information Set Monte Carlo Tree Search (single observer, Cowling et al. 2012).
+// A cada iteració es reparteixen a l'atzar les cartes que el bot no veu i es recorre un únic
+// arbre amb les accions compatibles amb aquest repartiment. L'arbre inclou les jugades de
+// tots els jugadors, els cantes i el canvi del 7.
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Play(Card),
+    Cantar(Pal),
+    CanviTrumfo,
+    // Acabar els cantes i el canvi de trumfo d'aquest jugador
+    Passar
+}
+
+pub struct IsmctsConfig {
+    pub iterations: usize,
+    pub time_budget: Option<Duration>,
+    pub exploration: f64,
+    // Amb llavor, una cerca limitada només per iteracions sempre tria el mateix a la mateixa posició
+    pub seed: Option<u64>
+}
+
+impl Default for IsmctsConfig {
+    fn default() -> Self {
+        IsmctsConfig {
+            iterations: 2000,
+            time_budget: None,
+            exploration: 0.7,
+            seed: None
+        }
+    }
+}
+
+pub struct IsmctsBot {
+    pub config: IsmctsConfig
+}
+
+impl IsmctsBot {
+    pub fn new(config: IsmctsConfig) -> Self {
+        IsmctsBot { config }
+    }
+
+    // `deadline` s'afegeix al `time_budget` de la configuració: s'atura al primer dels dos
+    fn search(&self, root: &SearchState, observer: usize, deadline: Option<Instant>) -> Action {
+        let root_actions: Vec<Action> = root.legal_actions();
+        if root_actions.len() == 1 {
+            return root_actions[0];
+        }
+
+        let mut rng: StdRng = match self.config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(thread_rng()).unwrap()     // Can't fail, thread_rng never errs
+        };
+        let sampler: HandSampler = HandSampler::new(&root.board, observer).unwrap();     // Can't fail, the real deal is consistent
+        let mut tree: Vec<Node> = vec![Node::new(None, observer, None)];
+        let budget_deadline: Option<Instant> = self.config.time_budget.map(|budget| Instant::now() + budget);
+        let deadline: Option<Instant> = match (deadline, budget_deadline) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b)
+        };
+
+        for _ in 0..self.config.iterations {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
+            }
+
+            let mut state: SearchState = SearchState {
+                board: sampler.sample(&mut rng),
+                post_baza_player: root.post_baza_player,
+                result: None
+            };
+
+            // 1. Selecció i expansió
+            let mut node: usize = 0;
+            while !state.is_terminal() {
+                let actions: Vec<Action> = state.legal_actions();
+                let player: usize = state.player_to_act();
+
+                let mut untried: Vec<Action> = actions.clone();
+                for i in 0..tree[node].children.len() {
+                    let child: usize = tree[node].children[i];
+                    let child_action: Action = tree[child].action.unwrap();
+                    if actions.contains(&child_action) {
+                        tree[child].availability += 1;
+                        untried.retain(|&action| action != child_action);
+                    }
+                }
+
+                if !untried.is_empty() {
+                    let action: Action = untried[rng.gen_range(0..untried.len())];
+                    state.apply(action);
+
+                    let child: usize = tree.len();
+                    tree.push(Node::new(Some(action), player, Some(node)));
+                    tree[node].children.push(child);
+                    node = child;
+                    break;
+                }
+
+                node = self.select_child(&tree, node, &actions);
+                state.apply(tree[node].action.unwrap());
+            }
+
+            // 2. Simulació
+            while !state.is_terminal() {
+                let action: Action = state.default_action(&mut rng);
+                state.apply(action);
+            }
+
+            // 3. Retropropagació
+            let mut current: Option<usize> = Some(node);
+            while let Some(index) = current {
+                let team: usize = state.board.get_team(tree[index].player);
+                tree[index].visits += 1;
+                tree[index].reward += state.reward(team);
+                current = tree[index].parent;
+            }
+        }
+
+        // L'acció més visitada de l'arrel
+        tree[0].children.iter()
+                        .map(|&child| &tree[child])
+                        .filter(|child| root_actions.contains(&child.action.unwrap()))
+                        .max_by_key(|child| child.visits)
+                        .and_then(|child| child.action)
+                        .unwrap_or(root_actions[0])
+    }
+
+    fn play_searched(&self, board: &mut Board, deadline: Option<Instant>) -> Result<GameState, String> {
+        let card: Card = self.searched_card(board, deadline)?;
+        let hand: Hand = board.get_current_player_hand();
+        let card_index: usize = hand.get_index(&card).unwrap();
+
+        board.play_card(card_index)
+    }
+
+    fn searched_card(&self, board: &Board, deadline: Option<Instant>) -> Result<Card, String> {
+        let root: SearchState = SearchState {
+            board: board.clone(),
+            post_baza_player: None,
+            result: None
+        };
+
+        match self.search(&root, board.current_player, deadline) {
+            Action::Play(card) => Ok(card),
+            action => Err(format!("Acció invàlida per jugar carta: {:?}", action))
+        }
+    }
+
+    // Pot fer una cerca per cada cante i pel canvi del 7; totes comparteixen el `time_budget`
+    fn post_baza_searched(&self, board: &mut Board, player: usize, deadline: Option<Instant>) {
+        let budget_deadline: Option<Instant> = self.config.time_budget.map(|budget| Instant::now() + budget);
+        let deadline: Option<Instant> = match (deadline, budget_deadline) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b)
+        };
+
+        loop {
+            let root: SearchState = SearchState {
+                board: board.clone(),
+                post_baza_player: Some(player),
+                result: None
+            };
+
+            // Si no té res a fer l'única acció és passar
+            match self.search(&root, player, deadline) {
+                Action::Cantar(pal) => board.cantar(player, pal).unwrap(),
+                Action::CanviTrumfo => board.change_trumfo_card(player).unwrap(),
+                _ => break
+            }
+        }
+    }
+
+    // UCB1 amb el nombre de vegades que el fill estava disponible en lloc de les visites del pare
+    fn select_child(&self, tree: &[Node], node: usize, actions: &[Action]) -> usize {
+        let mut best_child: usize = 0;
+        let mut best_score: f64 = f64::NEG_INFINITY;
+
+        for &child in tree[node].children.iter() {
+            if !actions.contains(&tree[child].action.unwrap()) {
+                continue;
+            }
+
+            let visits: f64 = tree[child].visits as f64;
+            let score: f64 = tree[child].reward / visits
+                            + self.config.exploration * ((tree[child].availability as f64).ln() / visits).sqrt();
+            if score > best_score {
+                best_score = score;
+                best_child = child;
+            }
+        }
+
+        best_child
+    }
+}
+
+impl Behaviour for IsmctsBot {
+    fn name(&self) -> String {
+        "IsmctsBot".to_string()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        self.play_searched(board, None)
+    }
+
+    fn play_card_until(&self, board: &mut Board, deadline: Instant) -> Result<GameState, String> {
+        self.play_searched(board, Some(search_deadline(deadline)))
+    }
+
+    fn query_card(&self, board: &Board, deadline: Option<Instant>) -> Option<Card> {
+        self.searched_card(board, deadline.map(search_deadline)).ok()
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        self.post_baza_searched(board, player, None);
+    }
+
+    fn post_baza_actions_until(&self, board: &mut Board, player: usize, deadline: Instant) {
+        self.post_baza_searched(board, player, Some(search_deadline(deadline)));
+    }
+}
+
+
+struct Node {
+    action: Option<Action>,
+    // Jugador que ha fet l'acció que porta a aquest node
+    player: usize,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    visits: u32,
+    availability: u32,
+    reward: f64
+}
+
+impl Node {
+    fn new(action: Option<Action>, player: usize, parent: Option<usize>) -> Self {
+        Node {
+            action,
+            player,
+            parent,
+            children: Vec::new(),
+            visits: 0,
+            availability: 1,
+            reward: 0.0
+        }
+    }
+}
+
+
+// Estat de la partida tal com el veu la cerca. A diferència del Board, també modela el moment
+// entre bazas en què cada jugador (de l'0 al 3, com fa el driver) pot cantar i canviar el 7.
+#[derive(Clone)]
+struct SearchState {
+    board: Board,
+    post_baza_player: Option<usize>,
+    result: Option<GameState>
+}
+
+impl SearchState {
+    fn is_terminal(&self) -> bool {
+        self.result.is_some()
+    }
+
+    fn player_to_act(&self) -> usize {
+        self.post_baza_player.unwrap_or(self.board.current_player)
+    }
+
+    fn legal_actions(&self) -> Vec<Action> {
+        match self.post_baza_player {
+            Some(player) => {
+                let mut actions: Vec<Action> = self.board.get_available_cantes(player)
+                                                          .into_iter()
+                                                          .map(Action::Cantar)
+                                                          .collect();
+                if self.board.can_change_trumfo(player) {
+                    actions.push(Action::CanviTrumfo);
+                }
+                actions.push(Action::Passar);
+
+                actions
+            },
+            None => self.board.get_legal_cards().iter().map(Action::Play).collect()
+        }
+    }
+
+    fn apply(&mut self, action: Action) {
+        match action {
+            Action::Play(card) => {
+                let card_index: usize = self.board.get_current_player_hand().get_index(&card).unwrap();
+                match self.board.play_card(card_index).unwrap() {
+                    GameState::BazaEnded => {
+                        self.post_baza_player = Some(0);
+                        self.skip_idle_players();
+                    },
+                    game_state @ (GameState::Continuation | GameState::Team0Won | GameState::Team1Won) => {
+                        self.result = Some(game_state);
+                    },
+                    _ => ()
+                }
+            },
+            Action::Cantar(pal) => self.board.cantar(self.player_to_act(), pal).unwrap(),
+            Action::CanviTrumfo => self.board.change_trumfo_card(self.player_to_act()).unwrap(),
+            Action::Passar => {
+                self.post_baza_player = self.post_baza_player.map(|player| player + 1);
+                self.skip_idle_players();
+            }
+        }
+    }
+
+    // Salta els jugadors que no poden cantar ni canviar el trumfo
+    fn skip_idle_players(&mut self) {
+        while let Some(player) = self.post_baza_player {
+            if player >= 4 {
+                self.post_baza_player = None;
+            } else if self.board.get_available_cantes(player).is_empty() && !self.board.can_change_trumfo(player) {
+                self.post_baza_player = Some(player + 1);
+            } else {
+                break;
+            }
+        }
+    }
+
+    // Política de les simulacions: carta legal aleatòria, i cantar i canviar sempre que es pugui
+    fn default_action<R: Rng>(&self, rng: &mut R) -> Action {
+        match self.post_baza_player {
+            Some(player) => {
+                if let Some(&pal) = self.board.get_available_cantes(player).first() {
+                    Action::Cantar(pal)
+                } else if self.board.can_change_trumfo(player) {
+                    Action::CanviTrumfo
+                } else {
+                    Action::Passar
+                }
+            },
+            None => {
+                let legal_cards: CardSet = self.board.get_legal_cards();
+                Action::Play(legal_cards.nth(rng.gen_range(0..legal_cards.len())).unwrap())
+            }
+        }
+    }
+
+    fn reward(&self, team: usize) -> f64 {
+        outcome_reward(self.result.unwrap(), &self.board, team)
+    }
+}
@@ -0,0 +1,138 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::time::Instant;
+use crate::{bot_behaviour::Behaviour, card_set::CardSet, determinization::HandSampler, encoding::encode_board, game::{Board, Card, GameState, Hand, Pal}};
+use crate::neural::Network;
+
+// Bot que juga amb una xarxa neuronal (veure neural.rs). Cada carta legal es puntua amb el
+// log-softmax de la política restringit a les cartes legals i, si `value_weight` no és 0, amb el
+// valor de la posició després de jugar-la. Els cantes i el canvi del 7 es poden decidir amb el cap
+// de valor; si no, es fan sempre que es pot, com la resta de bots.
+
+// Jugar sobre el Board de veritat roba cartes reals del mazo (o reparteix la ronda següent), i el
+// valor les veuria. Per això es juga sobre repartiments de les cartes que el bot no veu; la llavor
+// és fixa perquè la puntuació només depengui del que sap el bot
+const VALUE_DEALS: usize = 4;
+const VALUE_SEED: u64 = 0x6e65_7572_616c;
+
+pub struct NeuralConfig {
+    pub policy_weight: f32,
+    pub value_weight: f32,
+    // Cantar només si el valor no baixa
+    pub value_cantes: bool,
+    // Canviar el 7 només si el valor no baixa
+    pub value_canvi: bool
+}
+
+impl Default for NeuralConfig {
+    fn default() -> Self {
+        NeuralConfig {
+            policy_weight: 1.0,
+            value_weight: 0.0,
+            value_cantes: false,
+            value_canvi: false
+        }
+    }
+}
+
+pub struct NeuralBot {
+    pub network: Network,
+    pub config: NeuralConfig
+}
+
+impl NeuralBot {
+    pub fn new(network: Network, config: NeuralConfig) -> Self {
+        NeuralBot { network, config }
+    }
+
+    // Puntuació de cada carta legal
+    pub fn score_cards(&self, board: &Board) -> Vec<(Card, f32)> {
+        let seat: usize = board.current_player;
+        let legal_cards: CardSet = board.get_legal_cards();
+        let (logits, _): ([f32; 40], f32) = self.network.evaluate(&encode_board(board, seat).0);
+        let mut deals: Vec<Board> = Vec::new();
+        if self.config.value_weight != 0.0 {
+            let sampler: HandSampler = HandSampler::new(board, seat).unwrap();     // Can't fail, the real deal is consistent
+            let mut rng: StdRng = StdRng::seed_from_u64(VALUE_SEED);
+            deals = (0..VALUE_DEALS).map(|_| sampler.sample(&mut rng)).collect();
+        }
+
+        let max_logit: f32 = legal_cards.iter().map(|card| logits[card.index()]).fold(f32::NEG_INFINITY, f32::max);
+        let log_sum: f32 = legal_cards.iter().map(|card| (logits[card.index()] - max_logit).exp()).sum::<f32>().ln();
+
+        legal_cards.iter().map(|card| {
+            let mut score: f32 = self.config.policy_weight * (logits[card.index()] - max_logit - log_sum);
+            if !deals.is_empty() {
+                let value: f32 = deals.iter().map(|deal| {
+                    let mut next: Board = deal.clone();
+                    let card_index: usize = next.get_current_player_hand().get_index(&card).unwrap();
+                    next.play_card(card_index).unwrap();    // Can't fail, the card is legal
+                    self.value(&next, seat)
+                }).sum::<f32>();
+                score += self.config.value_weight * value / VALUE_DEALS as f32;
+            }
+
+            (card, score)
+        }).collect()
+    }
+
+    // Valor de la posició per a l'equip de `seat`, entre -1 i 1
+    pub fn value(&self, board: &Board, seat: usize) -> f32 {
+        self.network.value(&encode_board(board, seat).0)
+    }
+
+    fn choose_card(&self, board: &Board) -> Card {
+        self.score_cards(board).into_iter()
+                               .reduce(|a, b| if b.1 > a.1 { b } else { a })
+                               .unwrap().0
+    }
+
+    // Fa `action` si el valor per a `player` no baixa
+    fn try_action<F: Fn(&mut Board) -> Result<(), String>>(&self, board: &mut Board, player: usize, action: F) {
+        let mut next: Board = board.clone();
+        if action(&mut next).is_ok() && self.value(&next, player) >= self.value(board, player) {
+            *board = next;
+        }
+    }
+}
+
+impl Behaviour for NeuralBot {
+    fn name(&self) -> String {
+        "NeuralBot".to_string()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        let card: Card = self.choose_card(board);
+        let hand: Hand = board.get_current_player_hand();
+        let card_index: usize = hand.get_index(&card).unwrap();
+
+        board.play_card(card_index)
+    }
+
+    fn query_card(&self, board: &Board, _deadline: Option<Instant>) -> Option<Card> {
+        Some(self.choose_card(board))
+    }
+
+    fn card_scores(&self, board: &Board, _deadline: Option<Instant>) -> Vec<(Card, f64)> {
+        self.score_cards(board).into_iter().map(|(card, score)| (card, score as f64)).collect()
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        // Cantes
+        let available_pals: Vec<Pal> = board.get_available_cantes(player);
+        for pal in available_pals {
+            match self.config.value_cantes {
+                true => self.try_action(board, player, |board| board.cantar(player, pal)),
+                false => board.cantar(player, pal).unwrap()
+            }
+        }
+
+        // Canvi trumfo
+        if board.can_change_trumfo(player) {
+            match self.config.value_canvi {
+                true => self.try_action(board, player, |board| board.change_trumfo_card(player)),
+                false => board.change_trumfo_card(player).unwrap()
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::Instant;
+use crate::{bot_behaviour::Behaviour, game::{Board, Card, GameState, Pal}};
+use crate::driver::played_card;
+use crate::modeling::SharedModels;
+use crate::signals::Signal;
+
+// Embolcall que juga com `inner` i apunta cada decisió seva al model del jugador `player`.
+// Tots els seients d'una sessió comparteixen el mateix ModelStore.
+
+pub struct ObservedBot {
+    pub inner: Box<dyn Behaviour>,
+    pub player: String,
+    models: SharedModels
+}
+
+impl ObservedBot {
+    pub fn new(inner: Box<dyn Behaviour>, player: &str, models: SharedModels) -> Self {
+        ObservedBot {
+            inner,
+            player: player.to_string(),
+            models
+        }
+    }
+}
+
+impl Behaviour for ObservedBot {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        let before: Board = board.clone();
+        let state: GameState = self.inner.play_card(board)?;
+        let Some(card) = played_card(&before, board) else {
+            return Err("El bot no ha jugat cap carta.".to_string());
+        };
+        self.models.borrow_mut().observe_play(&self.player, &before, card);
+
+        Ok(state)
+    }
+
+    fn play_card_until(&self, board: &mut Board, deadline: Instant) -> Result<GameState, String> {
+        let before: Board = board.clone();
+        let state: GameState = self.inner.play_card_until(board, deadline)?;
+        let Some(card) = played_card(&before, board) else {
+            return Err("El bot no ha jugat cap carta.".to_string());
+        };
+        self.models.borrow_mut().observe_play(&self.player, &before, card);
+
+        Ok(state)
+    }
+
+    // Una consulta no és una jugada, no s'apunta al model
+    fn query_card(&self, board: &Board, deadline: Option<Instant>) -> Option<Card> {
+        self.inner.query_card(board, deadline)
+    }
+
+    fn card_scores(&self, board: &Board, deadline: Option<Instant>) -> Vec<(Card, f64)> {
+        self.inner.card_scores(board, deadline)
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        let available: Vec<Pal> = board.get_available_cantes(player);
+        let could_exchange: bool = board.can_change_trumfo(player);
+        self.inner.post_baza_actions(board, player);
+        self.models.borrow_mut().observe_post_baza(&self.player, player, board, &available, could_exchange);
+    }
+
+    fn post_baza_actions_until(&self, board: &mut Board, player: usize, deadline: Instant) {
+        let available: Vec<Pal> = board.get_available_cantes(player);
+        let could_exchange: bool = board.can_change_trumfo(player);
+        self.inner.post_baza_actions_until(board, player, deadline);
+        self.models.borrow_mut().observe_post_baza(&self.player, player, board, &available, could_exchange);
+    }
+
+    fn send_signal(&self, board: &Board, player: usize) -> Option<Signal> {
+        self.inner.send_signal(board, player)
+    }
+
+    fn receive_signal(&self, board: &Board, player: usize, from: usize, signal: Signal) {
+        self.inner.receive_signal(board, player, from, signal);
+    }
+}
@@ -0,0 +1,124 @@
+use std::time::Instant;
+use rand::thread_rng;
+use rand::rngs::ThreadRng;
+use crate::{bot_behaviour::{search_deadline, Behaviour}, card_set::CardSet, determinization::HandSampler, evaluation::outcome_reward, game::{Board, Card, GameState, Hand, Pal}};
+use crate::bot_behaviours::smart_bot::SmartBot;
+
+// Perfect Information Monte Carlo: es generen `samples` repartiments de les cartes amagades
+// compatibles amb el que veu el bot i, per a cada un, es juga cada carta legal i es simula la
+// resta de la ronda amb `rollout_policy` a tots els seients. Es tria la carta amb millor mitjana.
+
+pub struct PimcConfig {
+    pub samples: usize,
+    pub rollout_policy: Box<dyn Behaviour>
+}
+
+impl Default for PimcConfig {
+    fn default() -> Self {
+        PimcConfig {
+            samples: 100,
+            rollout_policy: Box::new(SmartBot {})
+        }
+    }
+}
+
+pub struct PimcBot {
+    pub config: PimcConfig
+}
+
+impl PimcBot {
+    pub fn new(config: PimcConfig) -> Self {
+        PimcBot { config }
+    }
+
+    // Amb `deadline` es para abans de fer totes les mostres, però sempre se'n fa almenys una
+    fn choose_card(&self, board: &Board, deadline: Option<Instant>) -> Card {
+        let legal_cards: CardSet = board.get_legal_cards();
+        if legal_cards.len() == 1 {
+            return legal_cards.nth(0).unwrap();
+        }
+
+        let observer: usize = board.current_player;
+        let team: usize = board.get_team(observer);
+        let mut rng: ThreadRng = thread_rng();
+        let sampler: HandSampler = HandSampler::new(board, observer).unwrap();     // Can't fail, the real deal is consistent
+        let mut rewards: [f64; 40] = [0.0; 40];
+
+        for i in 0..self.config.samples {
+            if i > 0 && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
+            }
+
+            let sample: Board = sampler.sample(&mut rng);
+            for card in legal_cards.iter() {
+                let mut rollout: Board = sample.clone();
+                let card_index: usize = rollout.get_current_player_hand().get_index(&card).unwrap();
+                let game_state: GameState = self.rollout(&mut rollout, card_index);
+
+                rewards[card.index()] += outcome_reward(game_state, &rollout, team);
+            }
+        }
+
+        // Totes les cartes s'han provat el mateix nombre de vegades, n'hi ha prou amb la suma
+        legal_cards.iter()
+                   .max_by(|a, b| rewards[a.index()].total_cmp(&rewards[b.index()]))
+                   .unwrap()
+    }
+
+    // Juga la carta i deixa que la política acabi la ronda
+    fn rollout(&self, board: &mut Board, card_index: usize) -> GameState {
+        let mut game_state: GameState = board.play_card(card_index).unwrap();
+        loop {
+            match game_state {
+                GameState::BazaEnded => {
+                    for player in 0..4 {
+                        self.config.rollout_policy.post_baza_actions(board, player);
+                    }
+                },
+                GameState::Continuation | GameState::Team0Won | GameState::Team1Won => return game_state,
+                _ => ()
+            }
+
+            game_state = self.config.rollout_policy.play_card(board).unwrap();
+        }
+    }
+}
+
+impl Behaviour for PimcBot {
+    fn name(&self) -> String {
+        "PimcBot".to_string()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        let card: Card = self.choose_card(board, None);
+        let hand: Hand = board.get_current_player_hand();
+        let card_index: usize = hand.get_index(&card).unwrap();
+
+        board.play_card(card_index)
+    }
+
+    fn play_card_until(&self, board: &mut Board, deadline: Instant) -> Result<GameState, String> {
+        let card: Card = self.choose_card(board, Some(search_deadline(deadline)));
+        let hand: Hand = board.get_current_player_hand();
+        let card_index: usize = hand.get_index(&card).unwrap();
+
+        board.play_card(card_index)
+    }
+
+    fn query_card(&self, board: &Board, deadline: Option<Instant>) -> Option<Card> {
+        Some(self.choose_card(board, deadline.map(search_deadline)))
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        // Cantes
+        let available_pals: Vec<Pal> = board.get_available_cantes(player);
+        for pal in available_pals {
+            board.cantar(player, pal).unwrap();
+        }
+
+        // Canvi trumfo
+        if board.can_change_trumfo(player) {
+            board.change_trumfo_card(player).unwrap();
+        }
+    }
+}
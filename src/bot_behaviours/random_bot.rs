@@ -1,5 +1,6 @@
-use rand::{seq::SliceRandom, thread_rng};
-use crate::{bot_behaviour::Behaviour, game::{Board, Card, GameState, Hand, Pal}};
+use std::time::Instant;
+use crate::fast_rng::{index_below, with_fast_rng};
+use crate::{bot_behaviour::Behaviour, card_set::CardSet, game::{Board, Card, GameState, Hand, Pal}};
 
 pub struct RandomBot {
 
@@ -12,13 +13,17 @@ impl Behaviour for RandomBot {
 
     fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
         let hand: Hand = board.get_current_player_hand();
-        let mut legal_cards: Vec<Card> = board.get_legal_cards();
-        legal_cards.shuffle(&mut thread_rng());
-        let random_card: Card = legal_cards[0];
+        let random_card: Card = self.query_card(board, None).unwrap();     // Can't panic, there's always a legal card
         let card_index: usize = hand.get_index(&random_card).unwrap();
         
         board.play_card(card_index)
     }
+
+    // Fa servir el generador del fil, el bot no en té cap de propi
+    fn query_card(&self, board: &Board, _deadline: Option<Instant>) -> Option<Card> {
+        let legal_cards: CardSet = board.get_legal_cards();
+        legal_cards.nth(with_fast_rng(|rng| index_below(rng, legal_cards.len())))
+    }
     
     fn post_baza_actions(&self, board: &mut Board, player: usize) {
         // Cantes
@@ -28,9 +33,8 @@ impl Behaviour for RandomBot {
         }
 
         // Canvi trumfo
-        match board.is_canvi_trumfo_available(player) {
-            Ok(()) => board.change_trumfo_card(player).unwrap(),
-            Err(_) => ()
+        if board.can_change_trumfo(player) {
+            board.change_trumfo_card(player).unwrap();
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,66 @@
+use std::cell::RefCell;
+use std::time::Instant;
+use crate::{bot_behaviour::Behaviour, game::{Board, Card, GameState}};
+use crate::signals::Signal;
+
+// Embolcall que juga com `inner` i, abans de cada baza, fa al company la seña certa més
+// important de la seva mà. Guarda l'última seña vista de cada seient perquè `inner` o qui
+// l'hagi creat la puguin consultar.
+
+pub struct SignalBot {
+    pub inner: Box<dyn Behaviour>,
+    received: RefCell<[Option<Signal>; 4]>
+}
+
+impl SignalBot {
+    pub fn new(inner: Box<dyn Behaviour>) -> Self {
+        SignalBot {
+            inner,
+            received: RefCell::new([None; 4])
+        }
+    }
+
+    // Última seña de `seat` que ha vist el bot
+    pub fn get_received(&self, seat: usize) -> Option<Signal> {
+        self.received.borrow()[seat]
+    }
+}
+
+impl Behaviour for SignalBot {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        self.inner.play_card(board)
+    }
+
+    fn play_card_until(&self, board: &mut Board, deadline: Instant) -> Result<GameState, String> {
+        self.inner.play_card_until(board, deadline)
+    }
+
+    fn query_card(&self, board: &Board, deadline: Option<Instant>) -> Option<Card> {
+        self.inner.query_card(board, deadline)
+    }
+
+    fn card_scores(&self, board: &Board, deadline: Option<Instant>) -> Vec<(Card, f64)> {
+        self.inner.card_scores(board, deadline)
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        self.inner.post_baza_actions(board, player);
+    }
+
+    fn post_baza_actions_until(&self, board: &mut Board, player: usize, deadline: Instant) {
+        self.inner.post_baza_actions_until(board, player, deadline);
+    }
+
+    fn send_signal(&self, board: &Board, player: usize) -> Option<Signal> {
+        Some(Signal::honest(board, player))
+    }
+
+    fn receive_signal(&self, board: &Board, player: usize, from: usize, signal: Signal) {
+        self.received.borrow_mut()[from] = Some(signal);
+        self.inner.receive_signal(board, player, from, signal);
+    }
+}
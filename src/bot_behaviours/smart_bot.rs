@@ -1,4 +1,5 @@
-use crate::{bot_behaviour::Behaviour, game::{Board, Card, GameState, Hand, Pal}};
+use std::time::Instant;
+use crate::{bot_behaviour::Behaviour, card_set::CardSet, game::{Board, Card, GameState, Hand, Pal}};
 
 pub struct SmartBot {
 
@@ -11,14 +12,20 @@ impl Behaviour for SmartBot {
 
     fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
         let hand: Hand = board.get_current_player_hand();
-        let legal_cards: Vec<Card> = board.get_legal_cards();
-        let trumfo: Pal = board.current_trumfo.pal;
-
-        let best_card: &Card = legal_cards.iter().reduce(|a, b| if a.is_better_than(*b, trumfo) {a} else {b}).unwrap(); 
-        let card_index: usize = hand.get_index(best_card).unwrap();
+        let best_card: Card = self.query_card(board, None).unwrap();     // Can't panic, there's always a legal card
+        let card_index: usize = hand.get_index(&best_card).unwrap();
         
         board.play_card(card_index)
     }
+
+    fn query_card(&self, board: &Board, _deadline: Option<Instant>) -> Option<Card> {
+        let legal_cards: CardSet = board.get_legal_cards();
+        let trumfo: Pal = board.current_trumfo.pal;
+
+        // La més forta: el trumfo més alt o, sense trumfos, la més alta de l'últim pal
+        let trumfos: CardSet = legal_cards & CardSet::suit(trumfo);
+        if trumfos.is_empty() { legal_cards.last() } else { trumfos.last() }
+    }
     
     fn post_baza_actions(&self, board: &mut Board, player: usize) {
         // Cantes
@@ -28,9 +35,8 @@ impl Behaviour for SmartBot {
         }
 
         // Canvi trumfo
-        match board.is_canvi_trumfo_available(player) {
-            Ok(()) => board.change_trumfo_card(player).unwrap(),
-            Err(_) => ()
+        if board.can_change_trumfo(player) {
+            board.change_trumfo_card(player).unwrap();
         }
     }
 }
\ No newline at end of file
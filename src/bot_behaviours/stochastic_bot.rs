@@ -0,0 +1,197 @@
+use std::cell::{RefCell, RefMut};
+use std::time::Instant;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::{bot_behaviour::Behaviour, card_set::CardSet, game::{Board, Card, GameState, Hand}};
+use crate::signals::Signal;
+
+// Embolcalls que afegeixen atzar a qualsevol bot, per explorar en generar dades o per fer nivells
+// de dificultat més fluixos. Amb llavor la seqüència d'atzar és reproduïble; sense, surt de
+// l'entropia del sistema. Els cantes, el canvi i les senyes són els de `inner`.
+
+pub struct EpsilonGreedyConfig {
+    // Probabilitat de jugar una carta legal a l'atzar en lloc de la de `inner`
+    pub epsilon: f64,
+    pub seed: Option<u64>
+}
+
+impl Default for EpsilonGreedyConfig {
+    fn default() -> Self {
+        EpsilonGreedyConfig {
+            epsilon: 0.1,
+            seed: None
+        }
+    }
+}
+
+pub struct SoftmaxConfig {
+    // Com més alta, més uniforme; a 0 sempre es juga la carta de més puntuació
+    pub temperature: f64,
+    pub seed: Option<u64>
+}
+
+impl Default for SoftmaxConfig {
+    fn default() -> Self {
+        SoftmaxConfig {
+            temperature: 0.5,
+            seed: None
+        }
+    }
+}
+
+pub struct EpsilonGreedyBot {
+    pub inner: Box<dyn Behaviour>,
+    pub config: EpsilonGreedyConfig,
+    rng: RefCell<StdRng>
+}
+
+impl EpsilonGreedyBot {
+    pub fn new(inner: Box<dyn Behaviour>, config: EpsilonGreedyConfig) -> Self {
+        let rng: StdRng = new_rng(config.seed);
+        EpsilonGreedyBot { inner, config, rng: RefCell::new(rng) }
+    }
+
+    fn play_or_explore(&self, board: &mut Board, deadline: Option<Instant>) -> Result<GameState, String> {
+        let mut rng: RefMut<StdRng> = self.rng.borrow_mut();
+        if rng.gen::<f64>() >= self.config.epsilon {
+            drop(rng);
+            return match deadline {
+                Some(deadline) => self.inner.play_card_until(board, deadline),
+                None => self.inner.play_card(board)
+            };
+        }
+
+        let legal_cards: CardSet = board.get_legal_cards();
+        let card: Card = legal_cards.nth(rng.gen_range(0..legal_cards.len())).unwrap();
+        play(board, card)
+    }
+}
+
+impl Behaviour for EpsilonGreedyBot {
+    fn name(&self) -> String {
+        format!("EpsilonGreedyBot({})", self.inner.name())
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        self.play_or_explore(board, None)
+    }
+
+    fn play_card_until(&self, board: &mut Board, deadline: Instant) -> Result<GameState, String> {
+        self.play_or_explore(board, Some(deadline))
+    }
+
+    fn card_scores(&self, board: &Board, deadline: Option<Instant>) -> Vec<(Card, f64)> {
+        self.inner.card_scores(board, deadline)
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        self.inner.post_baza_actions(board, player);
+    }
+
+    fn post_baza_actions_until(&self, board: &mut Board, player: usize, deadline: Instant) {
+        self.inner.post_baza_actions_until(board, player, deadline);
+    }
+
+    fn send_signal(&self, board: &Board, player: usize) -> Option<Signal> {
+        self.inner.send_signal(board, player)
+    }
+
+    fn receive_signal(&self, board: &Board, player: usize, from: usize, signal: Signal) {
+        self.inner.receive_signal(board, player, from, signal);
+    }
+}
+
+// Tria amb probabilitat proporcional a exp(puntuació / temperatura) sobre `card_scores` de
+// `inner`. Els bots que no puntuen les cartes donen 1 a la seva i 0 a la resta
+pub struct SoftmaxBot {
+    pub inner: Box<dyn Behaviour>,
+    pub config: SoftmaxConfig,
+    rng: RefCell<StdRng>
+}
+
+impl SoftmaxBot {
+    pub fn new(inner: Box<dyn Behaviour>, config: SoftmaxConfig) -> Self {
+        let rng: StdRng = new_rng(config.seed);
+        SoftmaxBot { inner, config, rng: RefCell::new(rng) }
+    }
+
+    // Probabilitat de jugar cada carta legal. `deadline` és com el de query_card
+    pub fn probabilities(&self, board: &Board, deadline: Option<Instant>) -> Vec<(Card, f64)> {
+        let scores: Vec<(Card, f64)> = self.inner.card_scores(board, deadline);
+        let max_score: f64 = scores.iter().map(|&(_, score)| score).fold(f64::NEG_INFINITY, f64::max);
+
+        if self.config.temperature <= 0.0 {
+            let best: usize = scores.iter().filter(|&&(_, score)| score == max_score).count();
+            return scores.iter().map(|&(card, score)| (card, if score == max_score { 1.0 / best as f64 } else { 0.0 })).collect();
+        }
+
+        let weights: Vec<f64> = scores.iter().map(|&(_, score)| ((score - max_score) / self.config.temperature).exp()).collect();
+        let total: f64 = weights.iter().sum();
+        scores.iter().zip(weights.iter()).map(|(&(card, _), weight)| (card, weight / total)).collect()
+    }
+
+    fn play_sampled(&self, board: &mut Board, deadline: Option<Instant>) -> Result<GameState, String> {
+        let probabilities: Vec<(Card, f64)> = self.probabilities(board, deadline);
+        let mut target: f64 = self.rng.borrow_mut().gen::<f64>();
+
+        // Si l'arrodoniment deixa `target` per sobre de la suma, es juga l'última carta
+        let mut card: Card = probabilities.last().ok_or("Cap carta legal per puntuar.")?.0;
+        for &(candidate, probability) in probabilities.iter() {
+            if target < probability {
+                card = candidate;
+                break;
+            }
+            target -= probability;
+        }
+
+        play(board, card)
+    }
+}
+
+impl Behaviour for SoftmaxBot {
+    fn name(&self) -> String {
+        format!("SoftmaxBot({})", self.inner.name())
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        self.play_sampled(board, None)
+    }
+
+    fn play_card_until(&self, board: &mut Board, deadline: Instant) -> Result<GameState, String> {
+        self.play_sampled(board, Some(deadline))
+    }
+
+    fn card_scores(&self, board: &Board, deadline: Option<Instant>) -> Vec<(Card, f64)> {
+        self.inner.card_scores(board, deadline)
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        self.inner.post_baza_actions(board, player);
+    }
+
+    fn post_baza_actions_until(&self, board: &mut Board, player: usize, deadline: Instant) {
+        self.inner.post_baza_actions_until(board, player, deadline);
+    }
+
+    fn send_signal(&self, board: &Board, player: usize) -> Option<Signal> {
+        self.inner.send_signal(board, player)
+    }
+
+    fn receive_signal(&self, board: &Board, player: usize, from: usize, signal: Signal) {
+        self.inner.receive_signal(board, player, from, signal);
+    }
+}
+
+fn new_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy()
+    }
+}
+
+fn play(board: &mut Board, card: Card) -> Result<GameState, String> {
+    let hand: Hand = board.get_current_player_hand();
+    let card_index: usize = hand.get_index(&card).unwrap();
+
+    board.play_card(card_index)
+}
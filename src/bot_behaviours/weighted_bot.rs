@@ -0,0 +1,193 @@
+use std::fmt;
+use std::fs;
+use std::time::Instant;
+use crate::{bot_behaviour::Behaviour, card_set::CardSet, determinization::unseen_cards, game::{Board, Card, GameState, Hand, Pal, Rank}};
+
+// Bot que puntua cada carta legal amb una suma ponderada de característiques i juga la millor.
+// Els pesos es poden carregar d'un fitxer de text ("nom = valor" per línia, # per comentaris),
+// així es poden fer personalitats i ajustar-los sense recompilar.
+
+pub const FEATURE_COUNT: usize = 10;
+
+// Totes les característiques estan normalitzades a més o menys [0, 1]
+pub const FEATURES: [&str; FEATURE_COUNT] = [
+    // Punts de la baza (amb la carta) si després de jugar-la guanya el nostre equip
+    "points_won",
+    // Punts de la baza (amb la carta) si després de jugar-la guanyen els rivals
+    "points_given",
+    // Punts de la carta
+    "card_value",
+    // Força de la carta dins del seu pal
+    "card_strength",
+    // La carta és trumfo
+    "trumfo_spent",
+    // La carta és la sota o el rey d'un pal sense cantar i tenim la parella
+    "breaks_cante",
+    // La carta és la sota o el rey d'un pal sense cantar i encara es pot robar l'altra
+    "cante_chance",
+    // Punts que es donen al company quan ja va guanyant la baza
+    "load_partner",
+    // La carta és el 7 de trumfo i encara es pot canviar
+    "trumfo_seven",
+    // Sortir de trumfo en arrastre
+    "lead_trumfo"
+];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Weights(pub [f64; FEATURE_COUNT]);
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights([1.0, -1.0, -0.5, -0.3, -0.4, -1.5, -0.3, 0.3, -2.0, 0.6])
+    }
+}
+
+impl Weights {
+    // Les característiques que no surten al text es queden amb el pes per defecte
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut weights: Weights = Weights::default();
+
+        for (line_number, line) in text.lines().enumerate() {
+            let line: &str = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (name, value): (&str, &str) = match line.split_once('=') {
+                Some((name, value)) => (name.trim(), value.trim()),
+                None => return Err(format!("Línia {}: s'esperava 'nom = valor'.", line_number + 1))
+            };
+
+            let feature: usize = match FEATURES.iter().position(|&feature| feature == name) {
+                Some(feature) => feature,
+                None => return Err(format!("Línia {}: característica desconeguda '{}'.", line_number + 1, name))
+            };
+
+            weights.0[feature] = match value.parse::<f64>() {
+                Ok(value) if value.is_finite() => value,
+                _ => return Err(format!("Línia {}: pes invàlid '{}'.", line_number + 1, value))
+            };
+        }
+
+        Ok(weights)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text: String = fs::read_to_string(path).map_err(|error| format!("No s'ha pogut llegir {}: {}", path, error))?;
+        Weights::parse(&text).map_err(|error| format!("{}: {}", path, error))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_string()).map_err(|error| format!("No s'ha pogut escriure {}: {}", path, error))
+    }
+}
+
+pub struct WeightedBot {
+    pub weights: Weights
+}
+
+impl WeightedBot {
+    pub fn new(weights: Weights) -> Self {
+        WeightedBot { weights }
+    }
+
+    pub fn evaluate(&self, features: &[f64; FEATURE_COUNT]) -> f64 {
+        features.iter().zip(self.weights.0.iter()).map(|(feature, weight)| feature * weight).sum()
+    }
+
+    // Característiques de jugar `card` a la posició actual
+    pub fn features(board: &Board, card: Card) -> [f64; FEATURE_COUNT] {
+        let player: usize = board.current_player;
+        let hand: CardSet = board.get_current_player_hand().cards;
+        let trumfo: Pal = board.current_trumfo.pal;
+        let baza: [Option<Card>; 4] = board.get_current_baza();
+        let mut features: [f64; FEATURE_COUNT] = [0.0; FEATURE_COUNT];
+
+        if !Board::is_baza_empty(baza) {
+            let winner: usize = Board::determine_baza_winner(Board::baza_starting_player(player, baza), trumfo, baza);
+            let new_winner: usize = if card.is_better_than(baza[winner].unwrap(), trumfo) { player } else { winner };
+            let baza_points: f64 = (baza.iter().flatten().map(|card| card.value()).sum::<u32>() + card.value()) as f64 / 10.0;
+
+            if new_winner % 2 == player % 2 {
+                features[0] = baza_points;
+            } else {
+                features[1] = baza_points;
+            }
+
+            if winner == (player + 2) % 4 && new_winner == winner {
+                features[7] = card.value() as f64 / 10.0;
+            }
+        } else if board.is_arrastre() && card.pal == trumfo {
+            features[9] = 1.0;
+        }
+
+        features[2] = card.value() as f64 / 10.0;
+        features[3] = card.rank.strength() as f64 / 9.0;
+        features[4] = if card.pal == trumfo { 1.0 } else { 0.0 };
+
+        if (card.rank == Rank::Sota || card.rank == Rank::Rey) && !board.get_cantes().contains(&card.pal) {
+            let other_rank: Rank = if card.rank == Rank::Sota { Rank::Rey } else { Rank::Sota };
+            let other: Card = Card { pal: card.pal, rank: other_rank };
+            if hand.contains(other) {
+                features[5] = 1.0;
+            } else if !board.is_arrastre() && unseen_cards(board, player).contains(other) {
+                features[6] = 1.0;
+            }
+        }
+
+        if card == (Card { pal: trumfo, rank: Rank::Siete }) && !board.is_arrastre() {
+            features[8] = 1.0;
+        }
+
+        features
+    }
+}
+
+impl Behaviour for WeightedBot {
+    fn name(&self) -> String {
+        "WeightedBot".to_string()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        let hand: Hand = board.get_current_player_hand();
+        let best_card: Card = self.query_card(board, None).unwrap();     // Can't panic, there's always a legal card
+        let card_index: usize = hand.get_index(&best_card).unwrap();
+
+        board.play_card(card_index)
+    }
+
+    fn query_card(&self, board: &Board, _deadline: Option<Instant>) -> Option<Card> {
+        self.card_scores(board, None).into_iter()
+                               .reduce(|a, b| if b.1 > a.1 { b } else { a })
+                               .map(|(card, _)| card)
+    }
+
+    fn card_scores(&self, board: &Board, _deadline: Option<Instant>) -> Vec<(Card, f64)> {
+        board.get_legal_cards().iter()
+                               .map(|card| (card, self.evaluate(&WeightedBot::features(board, card))))
+                               .collect()
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        // Cantes
+        let available_pals: Vec<Pal> = board.get_available_cantes(player);
+        for pal in available_pals {
+            board.cantar(player, pal).unwrap();
+        }
+
+        // Canvi trumfo
+        if board.can_change_trumfo(player) {
+            board.change_trumfo_card(player).unwrap();
+        }
+    }
+}
+
+impl fmt::Display for Weights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, weight) in FEATURES.iter().zip(self.0.iter()) {
+            writeln!(f, "{} = {}", name, weight)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub};
+use crate::game::{Card, Pal};
+
+// Conjunt de cartes sobre 40 bits. Cada pal ocupa 10 bits consecutius (Orus als bits 0-9,
+// Copes als 10-19, ...) i dins de cada pal els bits estan ordenats per força a la baza:
+// el bit 0 és el 2 i el bit 9 és l'as. Així "millor que" és una simple màscara.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub const EMPTY: CardSet = CardSet(0);
+    pub const FULL: CardSet = CardSet((1 << 40) - 1);
+
+    const SUIT_MASK: u64 = (1 << 10) - 1;
+    // Un bit per pal a la mateixa posició de força
+    const STRENGTH_MASK: u64 = 1 | 1 << 10 | 1 << 20 | 1 << 30;
+
+    pub const fn from_bits(bits: u64) -> Self {
+        CardSet(bits & CardSet::FULL.0)
+    }
+
+    pub const fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub fn suit(pal: Pal) -> Self {
+        CardSet(CardSet::SUIT_MASK << (pal as u32 * 10))
+    }
+
+    // Cartes que guanyen a `card` si `card` és la que va guanyant la baza
+    pub fn better_than(card: Card, trumfo: Pal) -> Self {
+        let index: u32 = card.index() as u32;
+        let suit_start: u32 = card.pal as u32 * 10;
+        let stronger_same_suit: u64 = (CardSet::SUIT_MASK << suit_start) & !((2 << index) - 1);
+
+        if card.pal == trumfo {
+            CardSet(stronger_same_suit)
+        } else {
+            CardSet(stronger_same_suit) | CardSet::suit(trumfo)
+        }
+    }
+
+    pub fn single(card: Card) -> Self {
+        CardSet(1 << card.index())
+    }
+
+    pub fn insert(&mut self, card: Card) {
+        self.0 |= 1 << card.index();
+    }
+
+    pub fn remove(&mut self, card: Card) {
+        self.0 &= !(1 << card.index());
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.0 & (1 << card.index()) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    // Posició de la carta dins del conjunt, en ordre d'iteració
+    pub fn position(&self, card: Card) -> Option<usize> {
+        if !self.contains(card) {
+            return None;
+        }
+
+        let lower_cards: u64 = self.0 & ((1 << card.index()) - 1);
+        Some(lower_cards.count_ones() as usize)
+    }
+
+    pub fn nth(&self, index: usize) -> Option<Card> {
+        self.iter().nth(index)
+    }
+
+    // L'última carta en ordre d'iteració: la més forta de l'últim pal
+    pub fn last(&self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        Some(Card::from_index(63 - self.0.leading_zeros() as usize))
+    }
+
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+
+    pub fn points(&self) -> u32 {
+        let count = |strength: u32| (self.0 & (CardSet::STRENGTH_MASK << strength)).count_ones();
+
+        2 * count(5) + 3 * count(6) + 4 * count(7) + 10 * count(8) + 11 * count(9)
+    }
+}
+
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let index: u32 = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(Card::from_index(index as usize))
+    }
+
+    // Sense construir les cartes que se salten
+    fn nth(&mut self, n: usize) -> Option<Card> {
+        for _ in 0..n {
+            self.0 &= self.0.wrapping_sub(1);
+        }
+
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len: usize = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Self {
+        let mut set: CardSet = CardSet::EMPTY;
+        for card in iter {
+            set.insert(card);
+        }
+
+        set
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, rhs: CardSet) -> CardSet {
+        CardSet(self.0 & rhs.0)
+    }
+}
+
+impl BitAndAssign for CardSet {
+    fn bitand_assign(&mut self, rhs: CardSet) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, rhs: CardSet) -> CardSet {
+        CardSet(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for CardSet {
+    fn bitor_assign(&mut self, rhs: CardSet) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, rhs: CardSet) -> CardSet {
+        CardSet(self.0 & !rhs.0)
+    }
+}
+
+impl Not for CardSet {
+    type Output = CardSet;
+
+    fn not(self) -> CardSet {
+        CardSet(!self.0 & CardSet::FULL.0)
+    }
+}
+
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, card) in self.iter().enumerate() {
+            if i > 0 { write!(f, ", ")? }
+            write!(f, "{}", card)?;
+        }
+        Ok(())
+    }
+}
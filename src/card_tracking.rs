@@ -0,0 +1,184 @@
+use crate::card_set::CardSet;
+use crate::determinization::unseen_cards;
+use crate::game::{Board, Card, Pal, Rank};
+
+// Seguiment de les cartes amagades a partir de la informació pública de la partida.
+// El Board porta un CardTracker que consumeix els GameEvent a mesura que passen; a partir
+// d'ell cada bot pot construir les HandBeliefs des del seu punt de vista.
+
+#[derive(Clone, Copy, Debug)]
+pub enum GameEvent {
+    // Carta jugada, amb la baza tal com estava just abans de jugar-la
+    CardPlayed { player: usize, card: Card, baza: [Option<Card>; 4], trumfo: Pal, arrastre: bool },
+    // El jugador ensenya la sota i el rey del pal
+    Cante { player: usize, pal: Pal },
+    // El jugador agafa el trumfo girat i deixa el 7 en el seu lloc
+    CanviTrumfo { player: usize, trumfo: Card },
+    // Sense cartes al mazo, el jugador roba el trumfo girat, que tothom ha vist
+    TrumfoDrawn { player: usize, trumfo: Card },
+    NewRound
+}
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct CardTracker {
+    // Cartes que tothom sap que té cada jugador
+    shown: [CardSet; 4],
+    // Cartes que tothom sap que cada jugador no té
+    excluded: [CardSet; 4],
+    // Cartes jugades per cada jugador aquesta ronda
+    played: [CardSet; 4]
+}
+
+impl CardTracker {
+    pub fn observe(&mut self, event: GameEvent) {
+        match event {
+            GameEvent::CardPlayed { player, card, baza, trumfo, arrastre } => {
+                self.shown[player].remove(card);
+                self.played[player].insert(card);
+                if arrastre {
+                    self.excluded[player] |= CardTracker::skipped_cards(card, player, baza, trumfo);
+                }
+            },
+            GameEvent::Cante { player, pal } => {
+                self.shown[player].insert(Card { pal, rank: Rank::Sota });
+                self.shown[player].insert(Card { pal, rank: Rank::Rey });
+            },
+            GameEvent::CanviTrumfo { player, trumfo } => {
+                self.shown[player].remove(Card { pal: trumfo.pal, rank: Rank::Siete });
+                self.shown[player].insert(trumfo);
+            },
+            GameEvent::TrumfoDrawn { player, trumfo } => self.shown[player].insert(trumfo),
+            GameEvent::NewRound => *self = CardTracker::default()
+        }
+    }
+
+    pub fn get_shown(&self, player: usize) -> CardSet {
+        self.shown[player]
+    }
+
+    pub fn get_excluded(&self, player: usize) -> CardSet {
+        self.excluded[player]
+    }
+
+    pub fn get_played(&self, player: usize) -> CardSet {
+        self.played[player]
+    }
+
+    // El jugador no ha assistit en arrastre, per tant no li queda cap carta del pal
+    pub fn is_void(&self, player: usize, pal: Pal) -> bool {
+        (CardSet::suit(pal) - self.excluded[player]).is_empty()
+    }
+
+    // Les obligacions de l'arrastre (get_legal_cards) s'apliquen per ordre: el jugador juga del primer
+    // grup on té cartes. Si la carta jugada no és d'un grup anterior, és que no en tenia cap.
+    fn skipped_cards(card: Card, player: usize, baza: [Option<Card>; 4], trumfo: Pal) -> CardSet {
+        if Board::is_baza_empty(baza) {
+            return CardSet::EMPTY;
+        }
+
+        let starting_player: usize = Board::baza_starting_player(player, baza);
+        let starting_pal: Pal = baza[starting_player].unwrap().pal;
+        let winning_player: usize = Board::determine_baza_winner(starting_player, trumfo, baza);
+        let winning_card: Card = baza[winning_player].unwrap();
+
+        let baza_pal: CardSet = CardSet::suit(starting_pal);
+        let better_cards: CardSet = CardSet::better_than(winning_card, trumfo);
+        let groups: &[CardSet] = if winning_player % 2 == player % 2 {
+            &[baza_pal]
+        } else {
+            &[baza_pal & better_cards, baza_pal, better_cards]
+        };
+
+        let mut skipped: CardSet = CardSet::EMPTY;
+        for &group in groups {
+            if group.contains(card) {
+                break;
+            }
+            skipped |= group;
+        }
+
+        skipped
+    }
+}
+
+
+// Què pot saber `observer` de les mans dels altres jugadors
+pub struct HandBeliefs {
+    // Cartes que cada jugador pot tenir (inclou les segures)
+    possible: [CardSet; 4],
+    // Cartes que cada jugador segur que té
+    certain: [CardSet; 4]
+}
+
+impl HandBeliefs {
+    pub fn new(board: &Board, observer: usize) -> Self {
+        let tracker: &CardTracker = board.get_card_tracker();
+        let unseen: CardSet = unseen_cards(board, observer);
+        let mut possible: [CardSet; 4] = [CardSet::EMPTY; 4];
+        let mut certain: [CardSet; 4] = [CardSet::EMPTY; 4];
+
+        for player in 0..4 {
+            if player == observer {
+                possible[player] = board.get_hand(player).cards;
+                certain[player] = possible[player];
+            } else {
+                possible[player] = unseen - tracker.get_excluded(player);
+                certain[player] = unseen & tracker.get_shown(player);
+            }
+        }
+
+        // Propagació fins que no es pot deduir res més
+        loop {
+            let mut changed: bool = false;
+
+            for player in 0..4 {
+                let others_certain: CardSet = (0..4).filter(|&other| other != player)
+                                                    .fold(CardSet::EMPTY, |cards, other| cards | certain[other]);
+                let player_possible: CardSet = (possible[player] - others_certain) | certain[player];
+                if player_possible != possible[player] {
+                    possible[player] = player_possible;
+                    changed = true;
+                }
+
+                // Si només pot tenir tantes cartes com té a la mà, les té totes
+                let hand_size: usize = board.get_hand(player).cards.len();
+                if possible[player].len() == hand_size && certain[player] != possible[player] {
+                    certain[player] = possible[player];
+                    changed = true;
+                }
+            }
+
+            // Sense mazo cada carta amagada és en alguna mà: si només un jugador la pot tenir, la té ell
+            if board.is_arrastre() {
+                for card in unseen.iter() {
+                    let mut owners = (0..4).filter(|&player| possible[player].contains(card));
+                    if let (Some(owner), None) = (owners.next(), owners.next()) {
+                        if !certain[owner].contains(card) {
+                            certain[owner].insert(card);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        HandBeliefs { possible, certain }
+    }
+
+    pub fn get_possible(&self, player: usize) -> CardSet {
+        self.possible[player]
+    }
+
+    pub fn get_certain(&self, player: usize) -> CardSet {
+        self.certain[player]
+    }
+
+    // El jugador segur que no té cap carta del pal
+    pub fn is_void(&self, player: usize, pal: Pal) -> bool {
+        (self.possible[player] & CardSet::suit(pal)).is_empty()
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+use rand::Rng;
+use crate::bot_behaviour::Behaviour;
+use crate::card_set::CardSet;
+use crate::card_tracking::CardTracker;
+use crate::endgame_solver::Position;
+use crate::game::{Board, Card, Pal};
+
+// Counterfactual regret minimization (CFR+) per a les últimes cartes de l'arrastre amb cartes
+// amagades. L'arrel és una posició d'un Board i l'atzar tria, amb la mateixa probabilitat,
+// qualsevol repartiment de les cartes que queden compatible amb la informació pública (mides de
+// les mans, cartes ensenyades i pals fallats). Cada jugador veu la seva mà i la taula.
+//
+// Abstracció: un conjunt d'informació és (seient, mà, baza en curs, cartes que queden a les mans,
+// trumfo). Dues històries amb les mateixes cartes jugades en un altre ordre cauen al mateix
+// conjunt, encara que els pals fallats que se'n dedueixen puguin ser diferents.
+//
+// La utilitat són els punts de carta que l'equip 0 guanya des de l'arrel (10 d'últimes
+// incloses); l'equip 1 vol el contrari. Els dos jugadors d'un equip no comparteixen la mà, per
+// tant el que s'obté és un equilibri entre quatre jugadors i l'explotabilitat es mesura seient
+// per seient: quants punts guanyaria l'equip d'un jugador si aquest (i només aquest) canviés
+// a la millor resposta.
+
+#[derive(Clone, Copy)]
+pub struct CfrConfig {
+    // Màxim de cartes a la mà del jugador que comença
+    pub max_hand_size: usize,
+    // Màxim de repartiments compatibles; cada iteració els recorre tots
+    pub max_deals: usize
+}
+
+impl Default for CfrConfig {
+    fn default() -> Self {
+        CfrConfig {
+            max_hand_size: 2,
+            max_deals: 20000
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct InfoSetKey {
+    seat: usize,
+    hand: CardSet,
+    current_baza: [Option<Card>; 4],
+    remaining: CardSet,
+    trumfo: Pal
+}
+
+impl InfoSetKey {
+    pub fn new(board: &Board) -> Self {
+        InfoSetKey::from_position(&Position::new(board))
+    }
+
+    fn from_position(position: &Position) -> Self {
+        InfoSetKey {
+            seat: position.current_player,
+            hand: position.hands[position.current_player],
+            current_baza: position.current_baza,
+            remaining: position.hands.iter().fold(CardSet::EMPTY, |cards, &hand| cards | hand),
+            trumfo: position.trumfo
+        }
+    }
+}
+
+// Probabilitat de jugar cada carta legal a cada conjunt d'informació
+#[derive(Clone, Default)]
+pub struct StrategyTable {
+    entries: HashMap<InfoSetKey, Vec<(Card, f64)>>
+}
+
+impl StrategyTable {
+    pub fn get(&self, board: &Board) -> Option<&[(Card, f64)]> {
+        self.entries.get(&InfoSetKey::new(board)).map(Vec::as_slice)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Tria una carta segons les probabilitats de la taula
+    pub fn sample<R: Rng>(&self, board: &Board, rng: &mut R) -> Option<Card> {
+        let entry: &[(Card, f64)] = self.get(board)?;
+        let mut target: f64 = rng.gen::<f64>() * entry.iter().map(|(_, probability)| probability).sum::<f64>();
+        for &(card, probability) in entry {
+            if target < probability {
+                return Some(card);
+            }
+            target -= probability;
+        }
+
+        entry.last().map(|&(card, _)| card)
+    }
+
+    // Probabilitats alineades amb `legal_cards`; uniforme si el conjunt no hi és
+    fn probabilities(&self, key: &InfoSetKey, legal_cards: CardSet) -> [f64; 10] {
+        let mut probabilities: [f64; 10] = [0.0; 10];
+        match self.entries.get(key) {
+            Some(entry) => {
+                for (i, card) in legal_cards.iter().enumerate() {
+                    probabilities[i] = entry.iter().find(|(entry_card, _)| *entry_card == card).map_or(0.0, |&(_, probability)| probability);
+                }
+            },
+            None => probabilities[..legal_cards.len()].fill(1.0 / legal_cards.len() as f64)
+        }
+
+        probabilities
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Exploitability {
+    // Punts que l'equip 0 espera guanyar des de l'arrel amb l'estratègia avaluada
+    pub value: f64,
+    // Punts que guanyaria l'equip de cada seient si el seient jugués la millor resposta
+    pub gains: [f64; 4]
+}
+
+impl Exploitability {
+    // Suma dels guanys (NashConv): 0 en un equilibri
+    pub fn total(&self) -> f64 {
+        self.gains.iter().sum()
+    }
+}
+
+struct InfoSet {
+    // Alineats amb les cartes legals
+    regrets: [f64; 10],
+    strategy_sum: [f64; 10],
+    actions: usize
+}
+
+impl InfoSet {
+    fn new(actions: usize) -> Self {
+        InfoSet {
+            regrets: [0.0; 10],
+            strategy_sum: [0.0; 10],
+            actions
+        }
+    }
+
+    // Regret matching
+    fn current_strategy(&self) -> [f64; 10] {
+        InfoSet::normalize(&self.regrets, self.actions)
+    }
+
+    fn average_strategy(&self) -> [f64; 10] {
+        InfoSet::normalize(&self.strategy_sum, self.actions)
+    }
+
+    fn normalize(values: &[f64; 10], actions: usize) -> [f64; 10] {
+        let mut strategy: [f64; 10] = [0.0; 10];
+        let total: f64 = values[..actions].iter().sum();
+        for i in 0..actions {
+            strategy[i] = if total > 0.0 { values[i] / total } else { 1.0 / actions as f64 };
+        }
+
+        strategy
+    }
+}
+
+pub struct CfrSolver {
+    board: Board,
+    root: Position,
+    deals: Vec<[CardSet; 4]>,
+    infosets: HashMap<InfoSetKey, InfoSet>,
+    iterations: usize
+}
+
+impl CfrSolver {
+    pub fn new(board: &Board, config: CfrConfig) -> Result<Self, String> {
+        if !board.is_arrastre() {
+            return Err("El solver només funciona en arrastre.".to_string());
+        }
+        let hand_size: usize = board.get_current_player_hand().cards.len();
+        if hand_size == 0 || hand_size > config.max_hand_size {
+            return Err(format!("El jugador actual té {} cartes i el màxim és {}.", hand_size, config.max_hand_size));
+        }
+
+        let deals: Vec<[CardSet; 4]> = CfrSolver::enumerate_deals(board, config.max_deals)?;
+
+        Ok(CfrSolver {
+            board: board.clone(),
+            root: Position::new(board),
+            deals,
+            infosets: HashMap::new(),
+            iterations: 0
+        })
+    }
+
+    pub fn get_deals(&self) -> usize {
+        self.deals.len()
+    }
+
+    pub fn get_iterations(&self) -> usize {
+        self.iterations
+    }
+
+    // Cada iteració recorre l'arbre sencer de tots els repartiments. Es pot cridar més d'un cop
+    pub fn run(&mut self, iterations: usize) {
+        for _ in 0..iterations {
+            self.iterations += 1;
+            // CFR+: la mitjana pondera cada iteració pel seu número
+            let weight: f64 = self.iterations as f64;
+            for deal in 0..self.deals.len() {
+                let position: Position = self.root.with_hands(self.deals[deal]);
+                self.cfr(&position, [1.0; 4], weight);
+            }
+        }
+    }
+
+    // Estratègia mitjana, que és la que convergeix a l'equilibri
+    pub fn strategy(&self) -> StrategyTable {
+        let mut table: StrategyTable = StrategyTable::default();
+        for deal in self.deals.iter() {
+            self.collect_strategy(&self.root.with_hands(*deal), &mut table);
+        }
+
+        table
+    }
+
+    // Estratègia pura que juga `bot` a cada conjunt d'informació. El bot decideix amb el Board de la
+    // primera història del conjunt que es troba
+    pub fn bot_strategy(&self, bot: &dyn Behaviour) -> StrategyTable {
+        let mut table: StrategyTable = StrategyTable::default();
+        for deal in self.deals.iter() {
+            let mut board: Board = self.board.clone();
+            board.redeal_cards(*deal, &[]).unwrap();   // Can't fail, the deal has the same cards and sizes
+            self.collect_bot_strategy(&board, &self.root.with_hands(*deal), bot, &mut table);
+        }
+
+        table
+    }
+
+    pub fn exploitability(&self, table: &StrategyTable) -> Exploitability {
+        let value: f64 = self.root_value(table, None);
+        let mut gains: [f64; 4] = [0.0; 4];
+
+        for (seat, gain) in gains.iter_mut().enumerate() {
+            let responses: HashMap<InfoSetKey, usize> = self.best_response(table, seat);
+            let response_value: f64 = self.root_value(table, Some((seat, &responses)));
+            let team: usize = seat % 2;
+            *gain = if team == 0 { response_value - value } else { value - response_value };
+        }
+
+        Exploitability { value, gains }
+    }
+
+    fn cfr(&mut self, position: &Position, reach: [f64; 4], weight: f64) -> f64 {
+        let player: usize = position.current_player;
+        if position.hands[player].is_empty() {
+            return 0.0;
+        }
+
+        let legal_cards: CardSet = position.legal_cards();
+        let key: InfoSetKey = InfoSetKey::from_position(position);
+        let strategy: [f64; 10] = self.infosets.entry(key)
+                                               .or_insert_with(|| InfoSet::new(legal_cards.len()))
+                                               .current_strategy();
+
+        let mut values: [f64; 10] = [0.0; 10];
+        let mut value: f64 = 0.0;
+        for (i, card) in legal_cards.iter().enumerate() {
+            let mut child: Position = *position;
+            let gain: f64 = child.play(card) as f64;
+            let mut child_reach: [f64; 4] = reach;
+            child_reach[player] *= strategy[i];
+
+            values[i] = gain + self.cfr(&child, child_reach, weight);
+            value += strategy[i] * values[i];
+        }
+
+        let counterfactual_reach: f64 = (0..4).filter(|&other| other != player).map(|other| reach[other]).product();
+        let team: usize = player % 2;
+        let sign: f64 = if team == 0 { 1.0 } else { -1.0 };
+        let infoset: &mut InfoSet = self.infosets.get_mut(&key).unwrap();
+        for i in 0..infoset.actions {
+            infoset.regrets[i] = (infoset.regrets[i] + sign * counterfactual_reach * (values[i] - value)).max(0.0);
+            infoset.strategy_sum[i] += weight * reach[player] * strategy[i];
+        }
+
+        value
+    }
+
+    fn collect_strategy(&self, position: &Position, table: &mut StrategyTable) {
+        if position.hands[position.current_player].is_empty() {
+            return;
+        }
+
+        let legal_cards: CardSet = position.legal_cards();
+        let key: InfoSetKey = InfoSetKey::from_position(position);
+        table.entries.entry(key).or_insert_with(|| {
+            let strategy: [f64; 10] = match self.infosets.get(&key) {
+                Some(infoset) => infoset.average_strategy(),
+                None => InfoSet::new(legal_cards.len()).average_strategy()
+            };
+            legal_cards.iter().zip(strategy).collect()
+        });
+
+        for card in legal_cards.iter() {
+            let mut child: Position = *position;
+            child.play(card);
+            self.collect_strategy(&child, table);
+        }
+    }
+
+    fn collect_bot_strategy(&self, board: &Board, position: &Position, bot: &dyn Behaviour, table: &mut StrategyTable) {
+        let player: usize = position.current_player;
+        if position.hands[player].is_empty() {
+            return;
+        }
+
+        let key: InfoSetKey = InfoSetKey::from_position(position);
+        table.entries.entry(key).or_insert_with(|| {
+            // Si era l'última carta la ronda es pot haver tornat a repartir i la diferència
+            // pot sortir buida, però llavors l'única carta legal és la jugada
+            let mut bot_board: Board = board.clone();
+            let card: Card = match bot.play_card(&mut bot_board) {
+                Ok(_) => (position.hands[player] - bot_board.get_hand(player).cards).nth(0).unwrap_or(position.legal_cards().nth(0).unwrap()),
+                Err(_) => position.legal_cards().nth(0).unwrap()
+            };
+            vec![(card, 1.0)]
+        });
+
+        for card in position.legal_cards().iter() {
+            let mut child_board: Board = board.clone();
+            let card_index: usize = child_board.get_current_player_hand().get_index(&card).unwrap();
+            child_board.play_card(card_index).unwrap();     // Can't fail, the card is legal
+            let mut child: Position = *position;
+            child.play(card);
+            self.collect_bot_strategy(&child_board, &child, bot, table);
+        }
+    }
+
+    // Valor esperat des de l'arrel. Si hi ha `responder`, aquest seient juga les cartes de la
+    // millor resposta (índexs dins de les cartes legals) en lloc de la taula
+    fn root_value(&self, table: &StrategyTable, responder: Option<(usize, &HashMap<InfoSetKey, usize>)>) -> f64 {
+        let total: f64 = self.deals.iter()
+                                   .map(|deal| CfrSolver::evaluate(table, &self.root.with_hands(*deal), responder))
+                                   .sum();
+
+        total / self.deals.len() as f64
+    }
+
+    fn evaluate(table: &StrategyTable, position: &Position, responder: Option<(usize, &HashMap<InfoSetKey, usize>)>) -> f64 {
+        let player: usize = position.current_player;
+        if position.hands[player].is_empty() {
+            return 0.0;
+        }
+
+        let legal_cards: CardSet = position.legal_cards();
+        let key: InfoSetKey = InfoSetKey::from_position(position);
+        let probabilities: [f64; 10] = match responder {
+            Some((seat, responses)) if seat == player => {
+                let mut probabilities: [f64; 10] = [0.0; 10];
+                probabilities[responses[&key]] = 1.0;
+                probabilities
+            },
+            _ => table.probabilities(&key, legal_cards)
+        };
+
+        let mut value: f64 = 0.0;
+        for (i, card) in legal_cards.iter().enumerate() {
+            if probabilities[i] == 0.0 {
+                continue;
+            }
+
+            let mut child: Position = *position;
+            let gain: f64 = child.play(card) as f64;
+            value += probabilities[i] * (gain + CfrSolver::evaluate(table, &child, responder));
+        }
+
+        value
+    }
+
+    // Millor resposta de `seat` contra la taula. Els conjunts d'informació es resolen de l'últim
+    // al primer (menys cartes a les mans primer), així les respostes més profundes ja es coneixen
+    fn best_response(&self, table: &StrategyTable, seat: usize) -> HashMap<InfoSetKey, usize> {
+        let mut histories: HashMap<InfoSetKey, Vec<(Position, f64)>> = HashMap::new();
+        for deal in self.deals.iter() {
+            CfrSolver::collect_histories(table, &self.root.with_hands(*deal), seat, 1.0, &mut histories);
+        }
+
+        let mut keys: Vec<InfoSetKey> = histories.keys().copied().collect();
+        keys.sort_by_key(|key| key.remaining.len());
+
+        let team: usize = seat % 2;
+        let sign: f64 = if team == 0 { 1.0 } else { -1.0 };
+        let mut responses: HashMap<InfoSetKey, usize> = HashMap::new();
+        for key in keys {
+            let legal_cards: CardSet = histories[&key][0].0.legal_cards();
+            let mut action_values: [f64; 10] = [0.0; 10];
+            for &(position, reach) in histories[&key].iter() {
+                for (i, card) in legal_cards.iter().enumerate() {
+                    let mut child: Position = position;
+                    let gain: f64 = child.play(card) as f64;
+                    action_values[i] += reach * (gain + CfrSolver::evaluate(table, &child, Some((seat, &responses))));
+                }
+            }
+
+            let best: usize = (0..legal_cards.len()).max_by(|&a, &b| (sign * action_values[a]).total_cmp(&(sign * action_values[b])))
+                                                     .unwrap();
+            responses.insert(key, best);
+        }
+
+        responses
+    }
+
+    // Històries on juga `seat`, amb la probabilitat que els altres seients hi arribin
+    fn collect_histories(table: &StrategyTable, position: &Position, seat: usize, reach: f64, histories: &mut HashMap<InfoSetKey, Vec<(Position, f64)>>) {
+        let player: usize = position.current_player;
+        if position.hands[player].is_empty() {
+            return;
+        }
+
+        let legal_cards: CardSet = position.legal_cards();
+        let key: InfoSetKey = InfoSetKey::from_position(position);
+        let probabilities: [f64; 10] = if player == seat {
+            histories.entry(key).or_default().push((*position, reach));
+            [1.0; 10]
+        } else {
+            table.probabilities(&key, legal_cards)
+        };
+
+        for (i, card) in legal_cards.iter().enumerate() {
+            let mut child: Position = *position;
+            child.play(card);
+            CfrSolver::collect_histories(table, &child, seat, reach * probabilities[i], histories);
+        }
+    }
+
+    // Repartiments de les cartes de les mans compatibles amb la informació pública
+    fn enumerate_deals(board: &Board, max_deals: usize) -> Result<Vec<[CardSet; 4]>, String> {
+        let tracker: &CardTracker = board.get_card_tracker();
+        let hands: [CardSet; 4] = [0, 1, 2, 3].map(|player| board.get_hand(player).cards);
+        let remaining: CardSet = hands.iter().fold(CardSet::EMPTY, |cards, &hand| cards | hand);
+
+        let fixed: [CardSet; 4] = [0, 1, 2, 3].map(|player| tracker.get_shown(player) & remaining);
+        let all_fixed: CardSet = fixed.iter().fold(CardSet::EMPTY, |cards, &hand| cards | hand);
+        if (0..4).any(|player| fixed[player].len() > hands[player].len()) {
+            return Err("Un jugador no pot tenir totes les cartes ensenyades.".to_string());
+        }
+        let allowed: [CardSet; 4] = [0, 1, 2, 3].map(|player| remaining - all_fixed - tracker.get_excluded(player));
+        let slots: [usize; 4] = [0, 1, 2, 3].map(|player| hands[player].len() - fixed[player].len());
+
+        let free_cards: Vec<Card> = (remaining - all_fixed).iter().collect();
+        let mut deals: Vec<[CardSet; 4]> = Vec::new();
+        CfrSolver::assign(&free_cards, fixed, slots, &allowed, &mut deals, max_deals)?;
+
+        if deals.is_empty() {
+            return Err("No hi ha cap repartiment compatible amb la informació pública.".to_string());
+        }
+
+        Ok(deals)
+    }
+
+    fn assign(cards: &[Card], hands: [CardSet; 4], slots: [usize; 4], allowed: &[CardSet; 4], deals: &mut Vec<[CardSet; 4]>, max_deals: usize) -> Result<(), String> {
+        let Some((&card, rest)) = cards.split_first() else {
+            deals.push(hands);
+            return match deals.len() > max_deals {
+                true => Err(format!("Hi ha més de {} repartiments compatibles.", max_deals)),
+                false => Ok(())
+            };
+        };
+
+        for player in 0..4 {
+            if slots[player] > 0 && allowed[player].contains(card) {
+                let mut next_hands: [CardSet; 4] = hands;
+                let mut next_slots: [usize; 4] = slots;
+                next_hands[player].insert(card);
+                next_slots[player] -= 1;
+                CfrSolver::assign(rest, next_hands, next_slots, allowed, deals, max_deals)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::{Duration, Instant};
+
+// Rellotge d'escacs per seient: cada seient comença amb `initial` i, després de cada jugada i de
+// cada torn de després de la baza fets a temps, guanya `increment`. Si un seient gasta més temps
+// del que li queda, se n'encarrega la `policy`.
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TimeoutPolicy {
+    // L'equip del seient perd la partida
+    Forfeit,
+    // Es juga una carta legal a l'atzar en lloc de la del bot
+    RandomMove
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ClockConfig {
+    pub initial: Duration,
+    pub increment: Duration,
+    pub policy: TimeoutPolicy
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        ClockConfig {
+            initial: Duration::from_secs(60),
+            increment: Duration::from_secs(1),
+            policy: TimeoutPolicy::Forfeit
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Clock {
+    pub config: ClockConfig,
+    remaining: [Duration; 4]
+}
+
+impl Clock {
+    pub fn new(config: ClockConfig) -> Self {
+        Clock {
+            config,
+            remaining: [config.initial; 4]
+        }
+    }
+
+    pub fn get_remaining(&self, seat: usize) -> Duration {
+        self.remaining[seat]
+    }
+
+    // Moment en què s'acaba el temps del seient si comença a pensar a `start`
+    pub fn deadline(&self, seat: usize, start: Instant) -> Instant {
+        start + self.remaining[seat]
+    }
+
+    // Descompta `used` del seient. Retorna false si no hi havia prou temps; llavors el seient es
+    // queda només amb l'increment
+    pub fn spend(&mut self, seat: usize, used: Duration) -> bool {
+        let in_time: bool = used <= self.remaining[seat];
+        self.remaining[seat] = self.remaining[seat].saturating_sub(used) + self.config.increment;
+
+        in_time
+    }
+}
@@ -0,0 +1,298 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use crate::bot_behaviour::Behaviour;
+use crate::driver::play_recorded_game;
+use crate::encoding::{encode, BINARY_SIZE, OBSERVATION_SIZE, SCHEMA_VERSION};
+use crate::environment::{Observation, ACTION_COUNT};
+use crate::game::{Board, Card, GameState};
+
+// Dades de partides entre bots per entrenar models de valor i de política. Cada fitxer és
+// un shard amb un rang de llavors fix: el shard `n` juga les llavors
+// first_seed + n * games_per_shard .. first_seed + (n + 1) * games_per_shard. Els bots es creen
+// de nou per a cada shard amb llavors que surten de la seva primera llavor (ShardHeader::bot_seed),
+// per tant es pot regenerar qualsevol shard per separat amb bots deterministes o que treguin
+// l'atzar d'aquesta llavor.
+//
+// Format v1 (little endian):
+//   capçalera (HEADER_SIZE bytes): "GSPD", DATASET_VERSION u32, SCHEMA_VERSION u32,
+//                                  OBSERVATION_SIZE u32, primera llavor u64, partides u64
+//   registres (RECORD_SIZE bytes): partida dins del shard u32, seient u8, acció u8,
+//                                  resultat i8, màscara legal (5 bytes, bit = Card::index),
+//                                  els BINARY_SIZE primers valors de l'observació (1 bit cadascun),
+//                                  la resta de valors (f32)
+
+pub const DATASET_VERSION: u32 = 1;
+pub const HEADER_SIZE: usize = 32;
+pub const RECORD_SIZE: usize = 7 + MASK_BYTES + BINARY_BYTES + 4 * (OBSERVATION_SIZE - BINARY_SIZE);
+
+const MAGIC: &[u8; 4] = b"GSPD";
+const MASK_BYTES: usize = ACTION_COUNT.div_ceil(8);
+const BINARY_BYTES: usize = BINARY_SIZE.div_ceil(8);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ShardHeader {
+    pub first_seed: u64,
+    pub games: u64
+}
+
+impl ShardHeader {
+    pub fn seeds(&self) -> Range<u64> {
+        self.first_seed..self.first_seed + self.games
+    }
+
+    // Llavor per a l'atzar propi del bot de `seat`, diferent per a cada shard i seient
+    pub fn bot_seed(&self, seat: usize) -> u64 {
+        self.first_seed.wrapping_mul(4).wrapping_add(seat as u64)
+    }
+}
+
+// Un punt de decisió
+#[derive(Clone, Debug)]
+pub struct Record {
+    // Partida dins del shard: la llavor és first_seed + game
+    pub game: u32,
+    pub seat: usize,
+    pub observation: [f32; OBSERVATION_SIZE],
+    pub legal_mask: [bool; ACTION_COUNT],
+    // Card::index de la carta jugada
+    pub action: usize,
+    // 1 si l'equip del seient ha guanyat la partida, -1 si l'ha perduda, 0 si no s'ha acabat
+    pub outcome: i8
+}
+
+impl Record {
+    pub fn get_action_card(&self) -> Card {
+        Card::from_index(self.action)
+    }
+}
+
+////////// WRITER //////////
+
+pub struct DatasetWriter<W: Write> {
+    writer: W,
+    records: usize
+}
+
+impl<W: Write> DatasetWriter<W> {
+    pub fn new(mut writer: W, header: ShardHeader) -> Result<Self, String> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(HEADER_SIZE);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&DATASET_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&SCHEMA_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(OBSERVATION_SIZE as u32).to_le_bytes());
+        bytes.extend_from_slice(&header.first_seed.to_le_bytes());
+        bytes.extend_from_slice(&header.games.to_le_bytes());
+        writer.write_all(&bytes).map_err(|error| error.to_string())?;
+
+        Ok(DatasetWriter { writer, records: 0 })
+    }
+
+    pub fn write(&mut self, record: &Record) -> Result<(), String> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(RECORD_SIZE);
+        bytes.extend_from_slice(&record.game.to_le_bytes());
+        bytes.push(record.seat as u8);
+        bytes.push(record.action as u8);
+        bytes.push(record.outcome as u8);
+        bytes.extend_from_slice(&pack_bits(&record.legal_mask, MASK_BYTES));
+        bytes.extend_from_slice(&pack_bits(&record.observation[..BINARY_SIZE].iter().map(|&value| value != 0.0).collect::<Vec<bool>>(), BINARY_BYTES));
+        for value in record.observation[BINARY_SIZE..].iter() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        self.writer.write_all(&bytes).map_err(|error| error.to_string())?;
+        self.records += 1;
+
+        Ok(())
+    }
+
+    pub fn get_records(&self) -> usize {
+        self.records
+    }
+
+    pub fn finish(mut self) -> Result<W, String> {
+        self.writer.flush().map_err(|error| error.to_string())?;
+        Ok(self.writer)
+    }
+}
+
+////////// READER //////////
+
+pub struct DatasetReader<R: Read> {
+    reader: R,
+    header: ShardHeader
+}
+
+impl DatasetReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let file: File = File::open(path.as_ref()).map_err(|error| format!("No s'ha pogut obrir {}: {}", path.as_ref().display(), error))?;
+        DatasetReader::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read> DatasetReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, String> {
+        let mut bytes: [u8; HEADER_SIZE] = [0; HEADER_SIZE];
+        reader.read_exact(&mut bytes).map_err(|error| format!("Capçalera incompleta: {}", error))?;
+
+        if &bytes[0..4] != MAGIC {
+            return Err("No és un fitxer de dades de self-play.".to_string());
+        }
+        let version: u32 = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        let schema_version: u32 = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        let observation_size: u32 = u32::from_le_bytes(bytes[12..16].try_into().unwrap());
+        if version != DATASET_VERSION || schema_version != SCHEMA_VERSION || observation_size as usize != OBSERVATION_SIZE {
+            return Err(format!("Versió no suportada: format {}, esquema {} ({} valors)", version, schema_version, observation_size));
+        }
+
+        let header: ShardHeader = ShardHeader {
+            first_seed: u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            games: u64::from_le_bytes(bytes[24..32].try_into().unwrap())
+        };
+
+        Ok(DatasetReader { reader, header })
+    }
+
+    pub fn get_header(&self) -> ShardHeader {
+        self.header
+    }
+
+    // Ok(None) al final del fitxer
+    pub fn read(&mut self) -> Result<Option<Record>, String> {
+        let mut bytes: [u8; RECORD_SIZE] = [0; RECORD_SIZE];
+        match self.reader.read_exact(&mut bytes) {
+            Ok(()) => (),
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(error.to_string())
+        }
+
+        let action: usize = bytes[5] as usize;
+        if action >= ACTION_COUNT {
+            return Err(format!("Acció invàlida: {}", action));
+        }
+
+        let mut legal_mask: [bool; ACTION_COUNT] = [false; ACTION_COUNT];
+        unpack_bits(&bytes[7..7 + MASK_BYTES], &mut legal_mask);
+
+        let mut binary: [bool; BINARY_SIZE] = [false; BINARY_SIZE];
+        unpack_bits(&bytes[7 + MASK_BYTES..7 + MASK_BYTES + BINARY_BYTES], &mut binary);
+        let mut observation: [f32; OBSERVATION_SIZE] = [0.0; OBSERVATION_SIZE];
+        for (value, bit) in observation.iter_mut().zip(binary.iter()) {
+            *value = if *bit { 1.0 } else { 0.0 };
+        }
+        for (index, chunk) in bytes[7 + MASK_BYTES + BINARY_BYTES..].chunks_exact(4).enumerate() {
+            observation[BINARY_SIZE + index] = f32::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        Ok(Some(Record {
+            game: u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            seat: bytes[4] as usize,
+            observation,
+            legal_mask,
+            action,
+            outcome: bytes[6] as i8
+        }))
+    }
+}
+
+impl<R: Read> Iterator for DatasetReader<R> {
+    type Item = Result<Record, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read().transpose()
+    }
+}
+
+fn pack_bits(bits: &[bool], size: usize) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![0; size];
+    for (index, _) in bits.iter().enumerate().filter(|(_, &bit)| bit) {
+        bytes[index / 8] |= 1 << (index % 8);
+    }
+
+    bytes
+}
+
+fn unpack_bits(bytes: &[u8], bits: &mut [bool]) {
+    for (index, bit) in bits.iter_mut().enumerate() {
+        *bit = bytes[index / 8] & (1 << (index % 8)) != 0;
+    }
+}
+
+////////// GENERATOR //////////
+
+// Crea el bot d'un seient a partir de la llavor del shard per a aquest seient
+pub type BotFactory<'a> = dyn Fn(usize, u64) -> Result<Box<dyn Behaviour>, String> + 'a;
+
+pub struct SelfPlayConfig {
+    pub games_per_shard: u64,
+    pub first_seed: u64
+}
+
+impl Default for SelfPlayConfig {
+    fn default() -> Self {
+        SelfPlayConfig {
+            games_per_shard: 1000,
+            first_seed: 0
+        }
+    }
+}
+
+impl SelfPlayConfig {
+    pub fn shard_header(&self, shard: usize) -> ShardHeader {
+        ShardHeader {
+            first_seed: self.first_seed.wrapping_add(shard as u64 * self.games_per_shard),
+            games: self.games_per_shard
+        }
+    }
+}
+
+pub fn shard_path<P: AsRef<Path>>(directory: P, shard: usize) -> PathBuf {
+    directory.as_ref().join(format!("shard-{:05}.gspd", shard))
+}
+
+// Juga les partides d'un shard i escriu totes les jugades de tots els seients. Retorna el
+// nombre de registres escrits
+pub fn generate_shard<W: Write>(config: &SelfPlayConfig, make_bot: &BotFactory, shard: usize, writer: W) -> Result<usize, String> {
+    let header: ShardHeader = config.shard_header(shard);
+    let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| make_bot(seat, header.bot_seed(seat))).collect::<Result<_, String>>()?;
+    let mut writer: DatasetWriter<W> = DatasetWriter::new(writer, header)?;
+
+    for (game, seed) in header.seeds().enumerate() {
+        let mut records: Vec<Record> = Vec::new();
+        let winner: GameState = play_recorded_game(&bots, seed, &mut |board: &Board, card: Card| {
+            let observation: Observation = Observation::new(board, board.current_player);
+            records.push(Record {
+                game: game as u32,
+                seat: board.current_player,
+                observation: encode(&observation),
+                legal_mask: observation.legal_mask,
+                action: card.index(),
+                outcome: 0
+            });
+        });
+
+        for record in records.iter_mut() {
+            let team: usize = record.seat % 2;
+            record.outcome = match winner {
+                GameState::Team0Won => if team == 0 { 1 } else { -1 },
+                GameState::Team1Won => if team == 1 { 1 } else { -1 },
+                _ => 0
+            };
+            writer.write(record)?;
+        }
+    }
+
+    let records: usize = writer.get_records();
+    writer.finish()?;
+
+    Ok(records)
+}
+
+pub fn generate_shard_file<P: AsRef<Path>>(config: &SelfPlayConfig, make_bot: &BotFactory, shard: usize, directory: P) -> Result<usize, String> {
+    fs::create_dir_all(directory.as_ref()).map_err(|error| format!("No s'ha pogut crear {}: {}", directory.as_ref().display(), error))?;
+    let path: PathBuf = shard_path(directory, shard);
+    let file: File = File::create(&path).map_err(|error| format!("No s'ha pogut crear {}: {}", path.display(), error))?;
+
+    generate_shard(config, make_bot, shard, BufWriter::new(file))
+}
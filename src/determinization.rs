@@ -0,0 +1,251 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::bot_behaviour::Behaviour;
+use crate::card_set::CardSet;
+use crate::card_tracking::CardTracker;
+use crate::game::{Board, Card};
+
+// Pes relatiu d'un repartiment on el model d'oponent no hauria jugat la carta que es va jugar
+const MISMATCH_WEIGHT: f64 = 0.1;
+
+// Cartes que `observer` no veu: les de les mans dels altres jugadors i les del mazo
+pub fn unseen_cards(board: &Board, observer: usize) -> CardSet {
+    let bazas: [CardSet; 2] = board.get_bazas();
+    let mut seen: CardSet = board.get_hand(observer).cards | bazas[0] | bazas[1];
+
+    for card in board.get_current_baza().into_iter().flatten() {
+        seen.insert(card);
+    }
+
+    // Fora de l'arrastre el trumfo és la carta girada sota el mazo
+    if !board.is_arrastre() {
+        seen.insert(board.current_trumfo);
+    }
+
+    !seen
+}
+
+// Reparteix a l'atzar les cartes que `observer` no veu entre les mans dels altres jugadors i el
+// mazo, respectant el que se'n sap. Per a moltes mostres de la mateixa posició és millor
+// construir un HandSampler i reaprofitar-lo.
+pub fn determinize<R: Rng>(board: &Board, observer: usize, rng: &mut R) -> Board {
+    HandSampler::new(board, observer).unwrap().sample(rng)     // Can't fail, the real deal is consistent
+}
+
+
+// Mostrejador uniforme dels repartiments compatibles amb el que sap `observer`: la mida de cada
+// mà, les cartes ensenyades (cantes i canvi del 7) i els pals fallats en arrastre.
+// Es compten els repartiments amb programació dinàmica sobre les places que queden a cada mà,
+// de manera que cada mostra surt directament, sense rebutjar-ne cap.
+pub struct HandSampler {
+    board: Board,
+    observer: usize,
+    // Els tres jugadors que no són `observer`
+    others: [usize; 3],
+    // Cartes que segur que té cada jugador
+    fixed: [CardSet; 4],
+    // Cartes per repartir (les no vistes menys les fixades) i a qui poden anar
+    free_cards: Vec<Card>,
+    allowed: [CardSet; 3],
+    slots: [usize; 3],
+    // counts[state(i, places)] = repartiments de free_cards[i..] que omplen exactament les places
+    counts: Vec<f64>
+}
+
+impl HandSampler {
+    pub fn new(board: &Board, observer: usize) -> Result<Self, String> {
+        let tracker: &CardTracker = board.get_card_tracker();
+        let unseen: CardSet = unseen_cards(board, observer);
+        let others: [usize; 3] = [(observer + 1) % 4, (observer + 2) % 4, (observer + 3) % 4];
+
+        let mut fixed: [CardSet; 4] = [CardSet::EMPTY; 4];
+        fixed[observer] = board.get_hand(observer).cards;
+        for &player in others.iter() {
+            fixed[player] = tracker.get_shown(player) & unseen;
+        }
+        let all_fixed: CardSet = others.iter().fold(CardSet::EMPTY, |cards, &player| cards | fixed[player]);
+
+        let mut allowed: [CardSet; 3] = [CardSet::EMPTY; 3];
+        let mut slots: [usize; 3] = [0; 3];
+        for (i, &player) in others.iter().enumerate() {
+            let hand_size: usize = board.get_hand(player).cards.len();
+            if fixed[player].len() > hand_size {
+                return Err(format!("El jugador {} no pot tenir totes les cartes ensenyades.", player));
+            }
+
+            allowed[i] = unseen - all_fixed - tracker.get_excluded(player);
+            slots[i] = hand_size - fixed[player].len();
+        }
+
+        let free_cards: Vec<Card> = (unseen - all_fixed).iter().collect();
+        let states: usize = (slots[0] + 1) * (slots[1] + 1) * (slots[2] + 1);
+        let mut sampler: HandSampler = HandSampler {
+            board: board.clone(),
+            observer,
+            others,
+            fixed,
+            free_cards,
+            allowed,
+            slots,
+            counts: Vec::new()
+        };
+        sampler.counts = vec![0.0; (sampler.free_cards.len() + 1) * states];
+        sampler.fill_counts();
+
+        if sampler.count() == 0.0 {
+            return Err("No hi ha cap repartiment compatible amb la informació coneguda.".to_string());
+        }
+
+        Ok(sampler)
+    }
+
+    // Nombre de repartiments compatibles (sense comptar l'ordre del mazo)
+    pub fn count(&self) -> f64 {
+        self.counts[self.state(0, self.slots)]
+    }
+
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Board {
+        let mut hands: [CardSet; 4] = self.fixed;
+        let mut deck: Vec<Card> = Vec::with_capacity(self.board.get_deck_size());
+        let mut places: [usize; 3] = self.slots;
+
+        for (i, &card) in self.free_cards.iter().enumerate() {
+            let mut choice: f64 = rng.gen::<f64>() * self.counts[self.state(i, places)];
+            let mut destination: Option<usize> = None;
+            let mut fallback: Option<usize> = None;
+
+            for k in 0..3 {
+                if places[k] == 0 || !self.allowed[k].contains(card) {
+                    continue;
+                }
+
+                places[k] -= 1;
+                let count: f64 = self.counts[self.state(i + 1, places)];
+                places[k] += 1;
+                if count == 0.0 {
+                    continue;
+                }
+
+                fallback = Some(k);
+                choice -= count;
+                if choice < 0.0 {
+                    destination = Some(k);
+                    break;
+                }
+            }
+
+            // La resta de probabilitat és del mazo. Si no hi cap, el residu és d'arrodoniment
+            if destination.is_none() && (self.deck_places(i, places) == 0 || self.counts[self.state(i + 1, places)] == 0.0) {
+                destination = fallback;
+            }
+
+            match destination {
+                Some(k) => {
+                    places[k] -= 1;
+                    hands[self.others[k]].insert(card);
+                },
+                None => deck.push(card)
+            }
+        }
+
+        deck.shuffle(rng);
+
+        let mut determinization: Board = self.board.clone();
+        determinization.redeal_cards(hands, &deck).unwrap();     // Can't fail, only hidden cards are moved
+
+        determinization
+    }
+
+    // Tria un de `candidates` repartiments uniformes amb probabilitat proporcional a com d'acord
+    // està amb `policy`. Només es poden reconstruir les decisions de la baza en curs (no s'ha robat
+    // des de llavors), així que el pes és per cada carta que `policy` no hauria jugat en aquesta baza.
+    pub fn sample_weighted<R: Rng>(&self, rng: &mut R, policy: &dyn Behaviour, candidates: usize) -> Board {
+        let samples: Vec<Board> = (0..candidates.max(1)).map(|_| self.sample(rng)).collect();
+        let weights: Vec<f64> = samples.iter().map(|sample| self.policy_weight(sample, policy)).collect();
+
+        let mut choice: f64 = rng.gen::<f64>() * weights.iter().sum::<f64>();
+        for (sample, weight) in samples.iter().zip(weights.iter()) {
+            choice -= weight;
+            if choice < 0.0 {
+                return sample.clone();
+            }
+        }
+
+        samples.into_iter().next_back().unwrap()     // Can't panic, there is at least one candidate
+    }
+
+    fn policy_weight(&self, sample: &Board, policy: &dyn Behaviour) -> f64 {
+        let mut weight: f64 = 1.0;
+        let mut past: Board = sample.clone();
+
+        while let Some(card) = past.take_back_card() {
+            let player: usize = past.current_player;
+            if player == self.observer {
+                continue;
+            }
+
+            let mut replay: Board = past.clone();
+            let hand: CardSet = replay.get_hand(player).cards;
+            let agrees: bool = policy.play_card(&mut replay).is_ok() && hand - replay.get_hand(player).cards == CardSet::single(card);
+            if !agrees {
+                weight *= MISMATCH_WEIGHT;
+            }
+        }
+
+        weight
+    }
+
+    fn fill_counts(&mut self) {
+        let free_cards: usize = self.free_cards.len();
+
+        for i in (0..=free_cards).rev() {
+            for a in 0..=self.slots[0] {
+                for b in 0..=self.slots[1] {
+                    for c in 0..=self.slots[2] {
+                        let places: [usize; 3] = [a, b, c];
+                        let count: f64 = if i == free_cards {
+                            if a + b + c == 0 { 1.0 } else { 0.0 }
+                        } else {
+                            self.count_from(i, places)
+                        };
+
+                        let state: usize = self.state(i, places);
+                        self.counts[state] = count;
+                    }
+                }
+            }
+        }
+    }
+
+    // Repartiments de free_cards[i..] segons on va la carta i
+    fn count_from(&self, i: usize, places: [usize; 3]) -> f64 {
+        if places.iter().sum::<usize>() > self.free_cards.len() - i {
+            return 0.0;
+        }
+
+        let card: Card = self.free_cards[i];
+        let mut count: f64 = 0.0;
+        if self.deck_places(i, places) > 0 {
+            count += self.counts[self.state(i + 1, places)];
+        }
+
+        for k in 0..3 {
+            if places[k] > 0 && self.allowed[k].contains(card) {
+                let mut next: [usize; 3] = places;
+                next[k] -= 1;
+                count += self.counts[self.state(i + 1, next)];
+            }
+        }
+
+        count
+    }
+
+    // Places que queden al mazo quan s'han repartit free_cards[..i]
+    fn deck_places(&self, i: usize, places: [usize; 3]) -> usize {
+        (self.free_cards.len() - i).saturating_sub(places.iter().sum())
+    }
+
+    fn state(&self, i: usize, places: [usize; 3]) -> usize {
+        ((i * (self.slots[0] + 1) + places[0]) * (self.slots[1] + 1) + places[1]) * (self.slots[2] + 1) + places[2]
+    }
+}
@@ -0,0 +1,306 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::time::{Duration, Instant};
+use crate::bot_behaviour::Behaviour;
+use crate::card_set::CardSet;
+use crate::clock::{Clock, ClockConfig, TimeoutPolicy};
+use crate::fast_rng::with_fast_rng;
+use crate::faults::{self, Fault, FaultConfig, FaultPolicy};
+use crate::game::{Board, Card, GameState};
+use crate::game_log::{GameLog, LogEvent};
+use crate::signals::SignalConfig;
+
+// Es crida després de cada jugada amb el Board d'abans de jugar i la carta jugada
+pub type MoveRecorder<'a> = &'a mut dyn FnMut(&Board, Card);
+
+// Opcions del bucle de joc
+#[derive(Clone, Copy, Default, Debug)]
+pub struct DriverOptions {
+    // Canal de senyes entre companys; None per jugar sense
+    pub signals: Option<SignalConfig>,
+    // Rellotge per seient; None per jugar sense límit de temps
+    pub clock: Option<ClockConfig>,
+    // Què fer amb els bots que fallen en jugar
+    pub faults: FaultConfig
+}
+
+pub fn play_game(bots: &[Box<dyn Behaviour>]) -> GameState {
+    let starting_player: usize = with_fast_rng(|rng| rng.gen_range(0..=3));
+    play_from(bots, Board::new(starting_player), None, &DriverOptions::default(), None)
+}
+
+// Partida amb repartiments i jugador inicial fixats per `seed`. Si els bots no fan servir
+// l'atzar, la mateixa llavor dona la mateixa partida.
+pub fn play_seeded_game(bots: &[Box<dyn Behaviour>], seed: u64) -> GameState {
+    let starting_player: usize = (seed % 4) as usize;
+    play_from(bots, Board::new_seeded(starting_player, seed), None, &DriverOptions::default(), None)
+}
+
+// Com play_seeded_game, però passant cada jugada a `recorder`
+pub fn play_recorded_game(bots: &[Box<dyn Behaviour>], seed: u64, recorder: MoveRecorder) -> GameState {
+    let starting_player: usize = (seed % 4) as usize;
+    play_from(bots, Board::new_seeded(starting_player, seed), Some(recorder), &DriverOptions::default(), None)
+}
+
+// Com play_game, amb `options` i apuntant la partida a `log`
+pub fn play_game_with(bots: &[Box<dyn Behaviour>], options: &DriverOptions, log: &mut GameLog) -> GameState {
+    let starting_player: usize = with_fast_rng(|rng| rng.gen_range(0..=3));
+    play_from(bots, Board::new(starting_player), None, options, Some(log))
+}
+
+// Com play_seeded_game, amb `options` i apuntant la partida a `log`. L'atzar del driver (qui veu
+// les senyes, la carta quan s'acaba el temps) també surt de `seed`
+pub fn play_logged_game(bots: &[Box<dyn Behaviour>], seed: u64, options: &DriverOptions, log: &mut GameLog) -> GameState {
+    let starting_player: usize = (seed % 4) as usize;
+    play_from(bots, Board::new_seeded(starting_player, seed), None, options, Some(log))
+}
+
+fn play_from(bots: &[Box<dyn Behaviour>], mut board: Board, mut recorder: Option<MoveRecorder>, options: &DriverOptions, mut log: Option<&mut GameLog>) -> GameState {
+    // Es crea quan cal; surt del repartiment perquè una partida amb llavor sigui reproduïble
+    let seed: u64 = board.get_hash();
+    let mut rng: Option<StdRng> = None;
+    let mut clock: Option<Clock> = options.clock.map(Clock::new);
+    // Fallades seguides del bot que ha de jugar
+    let mut failures: usize = 0;
+
+    loop {
+        // Una vegada per baza: quan es torna a provar un bot que ha fallat no es repeteixen
+        if let Some(config) = options.signals.as_ref() {
+            if failures == 0 && Board::is_baza_empty(board.get_current_baza()) {
+                let rng: &mut StdRng = rng.get_or_insert_with(|| StdRng::seed_from_u64(seed));
+                exchange_signals(bots, &board, config, rng, log.as_deref_mut());
+            }
+        }
+
+        let seat: usize = board.current_player;
+        if seat > 3 {
+            println!("Wrong player");
+            return GameState::None;
+        }
+        // El log només necessita la mà per saber quina carta s'ha jugat; el Board sencer, només el recorder
+        let before: Option<Board> = recorder.is_some().then(|| board.clone());
+        let hand: CardSet = board.get_hand(seat).cards;
+        let hash: u64 = board.get_hash();
+
+        // Temps gastat i el que queda, si hi ha rellotge
+        let mut timing: Option<(Duration, Duration)> = None;
+        let result: Result<GameState, Fault> = match clock.as_mut() {
+            None => faults::guard(|| bots[seat].play_card(&mut board)),
+            Some(clock) => {
+                // El bot juga sobre una còpia perquè, si s'excedeix o falla, la seva jugada no compti
+                let start: Instant = Instant::now();
+                let mut next: Board = board.clone();
+                let result: Result<GameState, Fault> = faults::guard(|| bots[seat].play_card_until(&mut next, clock.deadline(seat, start)));
+                let used: Duration = start.elapsed();
+                let in_time: bool = clock.spend(seat, used);
+                timing = Some((used, clock.get_remaining(seat)));
+
+                if in_time {
+                    if result.is_ok() {
+                        board = next;
+                    }
+                    result
+                } else {
+                    if let Some(log) = log.as_deref_mut() {
+                        log.push(LogEvent::Timeout { seat });
+                    }
+
+                    match clock.config.policy {
+                        TimeoutPolicy::Forfeit => {
+                            if let Some(log) = log.as_deref_mut() {
+                                log.push(LogEvent::Clock { seat, used, remaining: clock.get_remaining(seat) });
+                                log.push(LogEvent::Forfeit { seat });
+                            }
+                            return forfeit(seat, log);
+                        },
+                        TimeoutPolicy::RandomMove => Ok(play_random_card(&mut board, rng.get_or_insert_with(|| StdRng::seed_from_u64(seed))))
+                    }
+                }
+            }
+        };
+
+        // Un bot que torna Ok sense haver jugat deixaria el driver esperant una carta per sempre
+        let result: Result<GameState, Fault> = result.and_then(|game_state| match card_left_hand(hand, hash, &board, seat) {
+            Some(_) => Ok(game_state),
+            None => Err(Fault::Error("El bot no ha jugat cap carta.".to_string()))
+        });
+
+        let game_state: GameState = match result {
+            Ok(game_state) => {
+                failures = 0;
+                game_state
+            },
+            Err(fault) => {
+                failures += 1;
+                if let Some(log) = log.as_deref_mut() {
+                    log.push(LogEvent::Fault { seat, fault });
+                }
+
+                // Si el bot ha deixat la partida a mitges no es pot continuar
+                let corrupted: bool = board.get_hash() != hash || board.current_player != seat;
+                if corrupted || (failures > options.faults.max_retries && options.faults.policy == FaultPolicy::Forfeit) {
+                    if let Some(log) = log.as_deref_mut() {
+                        log.push(LogEvent::Forfeit { seat });
+                    }
+                    return forfeit(seat, log);
+                }
+                if failures <= options.faults.max_retries {
+                    continue;
+                }
+
+                failures = 0;
+                if let Some(log) = log.as_deref_mut() {
+                    log.push(LogEvent::Substitute { seat });
+                }
+                play_random_card(&mut board, rng.get_or_insert_with(|| StdRng::seed_from_u64(seed)))
+            }
+        };
+
+        if recorder.is_some() || log.is_some() {
+            let card: Card = card_left_hand(hand, hash, &board, seat).unwrap();     // Can't fail, checked above or played by the driver
+            if let (Some(recorder), Some(before)) = (recorder.as_mut(), before.as_ref()) {
+                recorder(before, card);
+            }
+            if let Some(log) = log.as_deref_mut() {
+                log.push(LogEvent::Play { seat, card });
+            }
+        }
+        if let (Some(log), Some((used, remaining))) = (log.as_deref_mut(), timing) {
+            log.push(LogEvent::Clock { seat, used, remaining });
+        }
+
+        match game_state {
+            GameState::BazaEnded => {
+                if let Some(game_state) = guarded_post_baza_actions(bots, &mut board, clock.as_mut(), log.as_deref_mut()) {
+                    return game_state;
+                }
+            },
+            GameState::Continuation => (),
+            GameState::Team0Won | GameState::Team1Won => {
+                if let Some(log) = log.as_deref_mut() {
+                    log.push(LogEvent::Result { state: game_state });
+                }
+                return game_state;
+            },
+            _ => ()
+        }
+    }
+}
+
+// Guanya l'altre equip
+fn forfeit(seat: usize, log: Option<&mut GameLog>) -> GameState {
+    let team: usize = seat % 2;
+    let game_state: GameState = if team == 0 { GameState::Team1Won } else { GameState::Team0Won };
+    if let Some(log) = log {
+        log.push(LogEvent::Result { state: game_state });
+    }
+
+    game_state
+}
+
+// Una carta legal a l'atzar. Sempre n'hi ha alguna, així que el motor no la pot rebutjar
+fn play_random_card(board: &mut Board, rng: &mut StdRng) -> GameState {
+    let legal_cards: CardSet = board.get_legal_cards();
+    let card: Card = legal_cards.nth(rng.gen_range(0..legal_cards.len())).unwrap();
+    let card_index: usize = board.get_current_player_hand().get_index(&card).unwrap();
+
+    board.play_card(card_index).unwrap()     // Can't fail, the card is legal
+}
+
+// Torn de senyes abans d'una baza, començant pel jugador que surt
+fn exchange_signals(bots: &[Box<dyn Behaviour>], board: &Board, config: &SignalConfig, rng: &mut StdRng, mut log: Option<&mut GameLog>) {
+    for offset in 0..4 {
+        let seat: usize = (board.current_player + offset) % 4;
+        let Some(signal) = bots[seat].send_signal(board, seat) else {
+            continue;
+        };
+
+        let partner: usize = (seat + 2) % 4;
+        let mut seen_by: [bool; 4] = [false; 4];
+        for (player, seen) in seen_by.iter_mut().enumerate() {
+            *seen = player == partner || (player != seat && rng.gen::<f64>() < config.visibility);
+        }
+
+        for player in (0..4).filter(|&player| seen_by[player]) {
+            bots[player].receive_signal(board, player, seat, signal);
+        }
+        if let Some(log) = log.as_deref_mut() {
+            log.push(LogEvent::Signal { seat, signal, seen_by });
+        }
+    }
+}
+
+// La carta que ha desaparegut de la mà. Si era l'última, després de jugar-la es pot haver
+// repartit una ronda nova. None si el jugador no ha jugat cap carta
+pub(crate) fn played_card(before: &Board, after: &Board) -> Option<Card> {
+    card_left_hand(before.get_hand(before.current_player).cards, before.get_hash(), after, before.current_player)
+}
+
+// La carta que falta a la mà de `player` respecte a `hand`, si n'ha jugat exactament una. Amb
+// una sola carta a la mà només es pot saber per `hash`, el de la posició abans de jugar
+fn card_left_hand(hand: CardSet, hash: u64, after: &Board, player: usize) -> Option<Card> {
+    if after.get_hash() == hash {
+        return None;
+    }
+
+    let left: CardSet = match hand.len() {
+        1 => hand,
+        _ => hand - after.get_hand(player).cards
+    };
+    match left.len() {
+        1 => left.nth(0),
+        _ => None
+    }
+}
+
+pub fn post_baza_actions(bots: &[Box<dyn Behaviour>], board: &mut Board) {
+    for (player, bot) in bots.iter().enumerate() {
+        bot.post_baza_actions(board, player);
+    }
+}
+
+// Com post_baza_actions, però si un bot fa panic es continua amb el següent. Amb rellotge, el temps
+// de cada bot es descompta del seu seient com el d'una jugada. Si s'excedeix, les seves accions no
+// compten i, amb TimeoutPolicy::Forfeit, el seu equip perd: llavors retorna el resultat
+fn guarded_post_baza_actions(bots: &[Box<dyn Behaviour>], board: &mut Board, mut clock: Option<&mut Clock>, mut log: Option<&mut GameLog>) -> Option<GameState> {
+    for (seat, bot) in bots.iter().enumerate() {
+        let start: Instant = Instant::now();
+        // Amb rellotge el bot treballa sobre una còpia, com a les jugades
+        let mut next: Option<Board> = clock.is_some().then(|| board.clone());
+        let target: &mut Board = next.as_mut().unwrap_or(board);
+        let result: Result<(), Fault> = faults::guard(|| {
+            match clock.as_deref() {
+                Some(clock) => bot.post_baza_actions_until(target, seat, clock.deadline(seat, start)),
+                None => bot.post_baza_actions(target, seat)
+            }
+            Ok(())
+        });
+
+        if let (Err(fault), Some(log)) = (result, log.as_deref_mut()) {
+            log.push(LogEvent::Fault { seat, fault });
+        }
+
+        let (Some(clock), Some(next)) = (clock.as_deref_mut(), next) else {
+            continue;
+        };
+        let used: Duration = start.elapsed();
+        let in_time: bool = clock.spend(seat, used);
+        if in_time {
+            *board = next;
+        } else if let Some(log) = log.as_deref_mut() {
+            log.push(LogEvent::Timeout { seat });
+        }
+        if let Some(log) = log.as_deref_mut() {
+            log.push(LogEvent::Clock { seat, used, remaining: clock.get_remaining(seat) });
+        }
+
+        if !in_time && clock.config.policy == TimeoutPolicy::Forfeit {
+            if let Some(log) = log.as_deref_mut() {
+                log.push(LogEvent::Forfeit { seat });
+            }
+            return Some(forfeit(seat, log));
+        }
+    }
+
+    None
+}
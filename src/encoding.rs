@@ -0,0 +1,117 @@
+use crate::card_set::CardSet;
+use crate::environment::{Observation, ACTION_COUNT};
+use crate::game::{Board, Pal};
+use strum::IntoEnumIterator;
+
+// Codificació numèrica del que sap un seient, per a experiments d'aprenentatge automàtic.
+// Els seients són relatius a l'observador (0 = ell, 1 = el següent, 2 = el company, 3 = l'anterior)
+// i els equips també (0 = el de l'observador). Les cartes van per Card::index (pal * 10 + força).
+//
+// Esquema v1 (SCHEMA conté el mateix en forma de taula):
+//   offset  mida  camp
+//        0    40  hand            cartes a la mà
+//       40    40  trumfo_card     carta de trumfo girada (o l'última que es va girar, en arrastre)
+//       80     4  trumfo_pal      pal de trumfo
+//       84   160  current_baza    carta de la baza en curs de cada seient (4 x 40)
+//      244   160  played          cartes jugades aquesta ronda per cada seient (4 x 40)
+//      404   160  shown           cartes que se sap que té cada seient: cantes i canvi del 7 (4 x 40)
+//      564    16  voids           pals fallats en arrastre per cada seient (4 x 4)
+//      580     4  cantes          pals cantats
+//      584     2  points          punts de cada equip segons el Board / 101, com a màxim 1
+//      586     2  round_points    punts de carta guanyats aquesta ronda per cada equip / 130
+//      588     1  deck_size       cartes al mazo / 40
+//      589     1  arrastre        1 si el mazo és buit
+//      590     1  baza_progress   cartes jugades a la baza en curs / 4
+// La màscara d'accions té 40 posicions (una per Card::index): true si la carta és legal.
+
+pub const SCHEMA_VERSION: u32 = 1;
+pub const OBSERVATION_SIZE: usize = 591;
+// Els valors d'abans d'aquest offset només poden ser 0 o 1
+pub const BINARY_SIZE: usize = 584;
+
+// (camp, offset, mida)
+pub const SCHEMA: [(&str, usize, usize); 13] = [
+    ("hand", 0, 40),
+    ("trumfo_card", 40, 40),
+    ("trumfo_pal", 80, 4),
+    ("current_baza", 84, 160),
+    ("played", 244, 160),
+    ("shown", 404, 160),
+    ("voids", 564, 16),
+    ("cantes", 580, 4),
+    ("points", 584, 2),
+    ("round_points", 586, 2),
+    ("deck_size", 588, 1),
+    ("arrastre", 589, 1),
+    ("baza_progress", 590, 1)
+];
+
+const HAND: usize = 0;
+const TRUMFO_CARD: usize = 40;
+const TRUMFO_PAL: usize = 80;
+const CURRENT_BAZA: usize = 84;
+const PLAYED: usize = 244;
+const SHOWN: usize = 404;
+const VOIDS: usize = 564;
+const CANTES: usize = 580;
+const POINTS: usize = 584;
+const ROUND_POINTS: usize = 586;
+const DECK_SIZE: usize = 588;
+const ARRASTRE: usize = 589;
+const BAZA_PROGRESS: usize = 590;
+
+pub fn encode(observation: &Observation) -> [f32; OBSERVATION_SIZE] {
+    let mut encoded: [f32; OBSERVATION_SIZE] = [0.0; OBSERVATION_SIZE];
+    let seat: usize = observation.seat;
+    let team: usize = seat % 2;
+
+    encode_cards(&mut encoded, HAND, observation.hand);
+    encoded[TRUMFO_CARD + observation.trumfo.index()] = 1.0;
+    encoded[TRUMFO_PAL + observation.trumfo.pal as usize] = 1.0;
+
+    for position in 0..4 {
+        let player: usize = (seat + position) % 4;
+
+        if let Some(card) = observation.current_baza[player] {
+            encoded[CURRENT_BAZA + position * 40 + card.index()] = 1.0;
+        }
+        encode_cards(&mut encoded, PLAYED + position * 40, observation.card_tracker.get_played(player));
+        encode_cards(&mut encoded, SHOWN + position * 40, observation.card_tracker.get_shown(player));
+
+        for pal in Pal::iter() {
+            if observation.card_tracker.is_void(player, pal) {
+                encoded[VOIDS + position * 4 + pal as usize] = 1.0;
+            }
+        }
+    }
+
+    for (pal, &sung) in observation.cantes.iter().enumerate() {
+        if sung {
+            encoded[CANTES + pal] = 1.0;
+        }
+    }
+
+    for relative_team in 0..2 {
+        let absolute_team: usize = (team + relative_team) % 2;
+        encoded[POINTS + relative_team] = (observation.points[absolute_team] as f32 / 101.0).min(1.0);
+        encoded[ROUND_POINTS + relative_team] = observation.won_cards[absolute_team].points() as f32 / 130.0;
+    }
+
+    encoded[DECK_SIZE] = observation.deck_size as f32 / 40.0;
+    encoded[ARRASTRE] = if observation.deck_size == 0 { 1.0 } else { 0.0 };
+    encoded[BAZA_PROGRESS] = observation.current_baza.iter().flatten().count() as f32 / 4.0;
+
+    encoded
+}
+
+// Codificació i màscara d'accions de `seat` (la màscara és buida si no li toca jugar)
+pub fn encode_board(board: &Board, seat: usize) -> ([f32; OBSERVATION_SIZE], [bool; ACTION_COUNT]) {
+    let observation: Observation = Observation::new(board, seat);
+    (encode(&observation), observation.legal_mask)
+}
+
+fn encode_cards(encoded: &mut [f32; OBSERVATION_SIZE], offset: usize, cards: CardSet) {
+    for card in cards {
+        encoded[offset + card.index()] = 1.0;
+    }
+}
@@ -0,0 +1,349 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use crate::card_set::CardSet;
+use crate::game::{Board, Card, Pal};
+use crate::tablebase::Tablebase;
+use crate::zobrist::KEYS;
+
+// Solver exacte (double dummy) de l'arrastre: amb totes les cartes a la vista, alpha-beta sobre
+// les jugades que queden fins al final de la ronda. Internament el valor d'una posició són els
+// punts de carta que l'equip 0 s'emporta d'aquí al final (10 d'últimes incloses); l'equip 1 es
+// queda la resta. Els cantes no hi entren perquè el recompte de final de ronda només mira les bazas.
+// Amb una taula de finals, les posicions de l'inici d'una baza que hi són no es busquen.
+
+pub struct EndgameSolution {
+    pub card: Card,
+    // Punts de carta de cada equip al final de la ronda si tothom juga perfecte
+    pub points: [u32; 2]
+}
+
+pub struct EndgameSolver {
+    table: HashMap<u64, TableEntry>,
+    tablebase: Option<Tablebase>,
+    nodes: u64
+}
+
+impl Default for EndgameSolver {
+    fn default() -> Self {
+        EndgameSolver::new()
+    }
+}
+
+impl EndgameSolver {
+    pub fn new() -> Self {
+        EndgameSolver {
+            table: HashMap::new(),
+            tablebase: None,
+            nodes: 0
+        }
+    }
+
+    // Abans de buscar, solve genera a la taula el bloc de la baza en curs si hi cap
+    pub fn with_tablebase(tablebase: Tablebase) -> Self {
+        EndgameSolver {
+            tablebase: Some(tablebase),
+            ..EndgameSolver::new()
+        }
+    }
+
+    pub fn get_tablebase(&self) -> Option<&Tablebase> {
+        self.tablebase.as_ref()
+    }
+
+    // La taula de transposició es conserva entre crides: les posicions d'un mateix final es repeteixen
+    pub fn solve(&mut self, board: &Board) -> Result<EndgameSolution, String> {
+        if !board.is_arrastre() {
+            return Err("El solver només funciona en arrastre.".to_string());
+        }
+        if board.get_current_player_hand().cards.is_empty() {
+            return Err("El jugador actual no té cartes.".to_string());
+        }
+
+        let position: Position = Position::new(board);
+        if let Some(tablebase) = self.tablebase.as_mut() {
+            // Cartes que quedaven a l'inici de la baza en curs
+            let remaining: CardSet = position.current_baza.into_iter().flatten().chain(position.hands.iter().flat_map(CardSet::iter)).collect();
+            if position.trumfo == tablebase.get_trumfo() && remaining.len() <= 4 * tablebase.get_cards_per_player() {
+                tablebase.generate_block(remaining)?;
+            }
+        }
+
+        let remaining_points: i32 = position.remaining_points();
+        let value: i32 = self.search(&position, -1, remaining_points + 1);
+        let card: Card = self.table[&position.hash].best_card.unwrap();     // Can't panic, the root always has legal cards
+
+        let bazas: [CardSet; 2] = board.get_bazas();
+        Ok(EndgameSolution {
+            card,
+            points: [bazas[0].points() + value as u32, bazas[1].points() + (remaining_points - value) as u32]
+        })
+    }
+
+    // Punts de carta que l'equip 0 s'emporta des de `position` fins al final de la ronda
+    pub(crate) fn solve_position(&mut self, position: &Position) -> u32 {
+        self.search(position, -1, position.remaining_points() + 1) as u32
+    }
+
+    // Nodes visitats des de la creació del solver
+    pub fn get_nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    pub fn clear(&mut self) {
+        self.table.clear();
+    }
+
+    // Alpha-beta fail-soft. L'equip 0 maximitza i l'equip 1 minimitza
+    fn search(&mut self, position: &Position, mut alpha: i32, mut beta: i32) -> i32 {
+        self.nodes += 1;
+
+        // Final de la ronda
+        if position.hands[position.current_player].is_empty() {
+            return 0;
+        }
+
+        let original_alpha: i32 = alpha;
+        let original_beta: i32 = beta;
+        let mut table_card: Option<Card> = None;
+        if let Some(entry) = self.table.get(&position.hash) {
+            match entry.bound {
+                Bound::Exact => return entry.value,
+                Bound::Lower => alpha = alpha.max(entry.value),
+                Bound::Upper => beta = beta.min(entry.value)
+            }
+            if alpha >= beta {
+                return entry.value;
+            }
+
+            table_card = entry.best_card;
+        }
+
+        let (moves, moves_len): ([Card; 10], usize) = position.ordered_moves(table_card);
+        let team: usize = position.current_player % 2;
+        let maximizing: bool = team == 0;
+        let mut best_value: i32 = if maximizing { i32::MIN } else { i32::MAX };
+        let mut best_card: Card = moves[0];
+
+        for &card in moves[..moves_len].iter() {
+            let mut child: Position = *position;
+            let gain: i32 = child.play(card);
+            let tablebase_value: Option<u32> = match (&self.tablebase, Board::is_baza_empty(child.current_baza)) {
+                (Some(tablebase), true) => tablebase.value(&child),
+                _ => None
+            };
+            let value: i32 = gain + match tablebase_value {
+                Some(value) => value as i32,
+                None => self.search(&child, alpha - gain, beta - gain)
+            };
+
+            if maximizing {
+                if value > best_value {
+                    best_value = value;
+                    best_card = card;
+                }
+                alpha = alpha.max(value);
+            } else {
+                if value < best_value {
+                    best_value = value;
+                    best_card = card;
+                }
+                beta = beta.min(value);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound: Bound = if best_value <= original_alpha {
+            Bound::Upper
+        } else if best_value >= original_beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table.insert(position.hash, TableEntry { value: best_value, bound, best_card: Some(best_card) });
+
+        best_value
+    }
+}
+
+
+#[derive(Clone, Copy)]
+enum Bound {
+    Exact,
+    // El valor real és >= value
+    Lower,
+    // El valor real és <= value
+    Upper
+}
+
+#[derive(Clone, Copy)]
+struct TableEntry {
+    value: i32,
+    bound: Bound,
+    best_card: Option<Card>
+}
+
+
+// Estat mínim de l'arrastre. Es copia a cada jugada en lloc de desfer-la.
+// El solver CFR també el fa servir per recórrer els finals
+#[derive(Clone, Copy)]
+pub(crate) struct Position {
+    pub(crate) hands: [CardSet; 4],
+    pub(crate) current_baza: [Option<Card>; 4],
+    pub(crate) current_player: usize,
+    pub(crate) trumfo: Pal,
+    // Com el del Board però sense les bazas guanyades: el valor de la resta de la ronda no en depèn
+    hash: u64
+}
+
+impl Position {
+    pub(crate) fn new(board: &Board) -> Self {
+        let hands: [CardSet; 4] = [0, 1, 2, 3].map(|player| board.get_hand(player).cards);
+        let mut position: Position = Position {
+            hands,
+            current_baza: board.get_current_baza(),
+            current_player: board.current_player,
+            trumfo: board.current_trumfo.pal,
+            hash: 0
+        };
+        position.hash = position.compute_hash(board.current_trumfo);
+
+        position
+    }
+
+    // Inici d'una baza de l'arrastre. El solver només necessita el pal del trumfo; la carta
+    // que es fa servir per al hash és indiferent mentre sigui sempre la mateixa
+    pub(crate) fn from_hands(hands: [CardSet; 4], current_player: usize, trumfo: Pal) -> Self {
+        let mut position: Position = Position {
+            hands,
+            current_baza: [None; 4],
+            current_player,
+            trumfo,
+            hash: 0
+        };
+        position.hash = position.compute_hash(Card::from_index(trumfo as usize * 10));
+
+        position
+    }
+
+    // La mateixa posició amb unes altres mans (de les mateixes mides)
+    pub(crate) fn with_hands(&self, hands: [CardSet; 4]) -> Self {
+        let mut position: Position = *self;
+        for (player, (old_hand, new_hand)) in self.hands.iter().zip(hands.iter()).enumerate() {
+            for card in old_hand.iter() {
+                position.hash ^= KEYS.hand(player, card);
+            }
+            for card in new_hand.iter() {
+                position.hash ^= KEYS.hand(player, card);
+            }
+        }
+        position.hands = hands;
+
+        position
+    }
+
+    fn compute_hash(&self, trumfo: Card) -> u64 {
+        let mut hash: u64 = KEYS.trumfo(trumfo) ^ KEYS.current_player(self.current_player);
+
+        for (player, hand) in self.hands.iter().enumerate() {
+            for card in hand.iter() {
+                hash ^= KEYS.hand(player, card);
+            }
+        }
+        for (player, card) in self.current_baza.into_iter().enumerate() {
+            if let Some(card) = card {
+                hash ^= KEYS.current_baza(player, card);
+            }
+        }
+
+        hash
+    }
+
+    pub(crate) fn legal_cards(&self) -> CardSet {
+        Board::arrastre_legal_cards(self.hands[self.current_player], self.current_baza, self.current_player, self.trumfo)
+    }
+
+    // Punts que encara es poden guanyar: cartes a les mans i a la baza en curs, i les 10 d'últimes
+    fn remaining_points(&self) -> i32 {
+        let mut points: u32 = self.hands.iter().map(|hand| hand.points()).sum::<u32>() + 10;
+        for card in self.current_baza.into_iter().flatten() {
+            points += card.value();
+        }
+
+        points as i32
+    }
+
+    // Juga la carta i retorna els punts que guanya l'equip 0 si amb ella es tanca una baza
+    pub(crate) fn play(&mut self, card: Card) -> i32 {
+        let player: usize = self.current_player;
+        self.hands[player].remove(card);
+        self.current_baza[player] = Some(card);
+        self.hash ^= KEYS.hand(player, card) ^ KEYS.current_baza(player, card) ^ KEYS.current_player(player);
+
+        let mut gain: i32 = 0;
+        let mut next_player: usize = Board::next_player(player);
+        if Board::is_baza_complete(self.current_baza) {
+            next_player = Board::determine_baza_winner(next_player, self.trumfo, self.current_baza);
+
+            let mut points: u32 = 0;
+            for (player, card) in self.current_baza.into_iter().enumerate() {
+                let card: Card = card.unwrap();
+                points += card.value();
+                self.hash ^= KEYS.current_baza(player, card);
+            }
+
+            // 10 de ultimas
+            if self.hands.iter().all(|hand| hand.is_empty()) {
+                points += 10;
+            }
+
+            let winner_team: usize = next_player % 2;
+            if winner_team == 0 {
+                gain = points as i32;
+            }
+            self.current_baza = [None; 4];
+        }
+
+        self.current_player = next_player;
+        self.hash ^= KEYS.current_player(next_player);
+
+        gain
+    }
+
+    // Cartes legals ordenades per provar primer les que solen ser millors: la de la taula de
+    // transposició, després les que guanyen la baza (les de més punts primer) i després les
+    // que la perden (les de menys punts primer)
+    fn ordered_moves(&self, table_card: Option<Card>) -> ([Card; 10], usize) {
+        let legal_cards: CardSet = self.legal_cards();
+        let winning_card: Option<Card> = if Board::is_baza_empty(self.current_baza) {
+            None
+        } else {
+            let starting_player: usize = Board::baza_starting_player(self.current_player, self.current_baza);
+            self.current_baza[Board::determine_baza_winner(starting_player, self.trumfo, self.current_baza)]
+        };
+
+        let mut moves: [Card; 10] = [Card::from_index(0); 10];
+        let mut moves_len: usize = 0;
+        for card in legal_cards.iter() {
+            moves[moves_len] = card;
+            moves_len += 1;
+        }
+
+        moves[..moves_len].sort_by_key(|&card| {
+            if Some(card) == table_card {
+                return Reverse(1000);
+            }
+
+            let wins_baza: bool = winning_card.is_none_or(|winning_card| card.is_better_than(winning_card, self.trumfo));
+            if wins_baza {
+                Reverse(100 + card.value() as i32 * 10 + card.rank.strength() as i32)
+            } else {
+                Reverse(50 - card.value() as i32 * 10 - card.rank.strength() as i32)
+            }
+        });
+
+        (moves, moves_len)
+    }
+}
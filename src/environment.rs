@@ -0,0 +1,212 @@
+use crate::bot_behaviour::Behaviour;
+use crate::card_set::CardSet;
+use crate::card_tracking::CardTracker;
+use crate::driver::post_baza_actions;
+use crate::game::{Board, Card, GameState, Pal};
+use strum::IntoEnumIterator;
+
+// Entorn a l'estil Gym per entrenar agents: l'agent juga un seient i els altres tres els
+// porten bots. Una acció és l'índex (0..40) de la carta a jugar; els cantes i el canvi del 7
+// de l'agent els decideix el bot del seu seient. Un episodi és una partida sencera.
+
+pub const ACTION_COUNT: usize = 40;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RewardMode {
+    // +1 si l'equip de l'agent guanya la partida, -1 si la perd, 0 mentre no s'acaba
+    WinLoss,
+    // Punts de carta (10 d'últimes incloses) guanyats per l'equip de l'agent menys els dels rivals
+    PointDifference
+}
+
+pub struct EnvConfig {
+    pub seat: usize,
+    // Un bot per seient. El del seient de l'agent només fa els cantes i el canvi de trumfo
+    pub bots: Vec<Box<dyn Behaviour>>,
+    pub reward: RewardMode
+}
+
+// El que veu el seient de l'agent
+#[derive(Clone, Debug)]
+pub struct Observation {
+    pub seat: usize,
+    pub hand: CardSet,
+    pub current_baza: [Option<Card>; 4],
+    // Cartes guanyades per cada equip aquesta ronda
+    pub won_cards: [CardSet; 2],
+    pub trumfo: Card,
+    pub deck_size: usize,
+    // Pals cantats, indexats per Pal
+    pub cantes: [bool; 4],
+    pub points: [u32; 2],
+    pub card_tracker: CardTracker,
+    pub legal_mask: [bool; ACTION_COUNT]
+}
+
+impl Observation {
+    pub fn new(board: &Board, seat: usize) -> Self {
+        let mut legal_mask: [bool; ACTION_COUNT] = [false; ACTION_COUNT];
+        if board.current_player == seat {
+            for card in board.get_legal_cards() {
+                legal_mask[card.index()] = true;
+            }
+        }
+
+        let mut cantes: [bool; 4] = [false; 4];
+        for &pal in board.get_cantes() {
+            cantes[pal as usize] = true;
+        }
+
+        Observation {
+            seat,
+            hand: board.get_hand(seat).cards,
+            current_baza: board.get_current_baza(),
+            won_cards: board.get_bazas(),
+            trumfo: board.current_trumfo,
+            deck_size: board.get_deck_size(),
+            cantes,
+            points: board.get_points(),
+            card_tracker: *board.get_card_tracker(),
+            legal_mask
+        }
+    }
+
+    pub fn cantes(&self) -> Vec<Pal> {
+        Pal::iter().filter(|&pal| self.cantes[pal as usize]).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct StepInfo {
+    // Team0Won o Team1Won quan s'acaba la partida, None mentrestant
+    pub result: GameState,
+    // Punts de carta guanyats per cada equip durant el pas
+    pub points_won: [u32; 2]
+}
+
+pub struct Step {
+    pub observation: Observation,
+    pub reward: f64,
+    pub done: bool,
+    pub info: StepInfo
+}
+
+pub struct Environment {
+    pub config: EnvConfig,
+    board: Board,
+    // Guanyador quan la partida s'ha acabat; GameState::None si encara no ha començat o un bot ha fallat
+    result: Option<GameState>
+}
+
+impl Environment {
+    pub fn new(config: EnvConfig) -> Result<Self, String> {
+        if config.seat >= 4 {
+            return Err("Invalid player index.".to_string());
+        }
+        if config.bots.len() != 4 {
+            return Err("Calen quatre bots, un per seient.".to_string());
+        }
+
+        Ok(Environment {
+            config,
+            board: Board::new(0),
+            result: Some(GameState::None)
+        })
+    }
+
+    // Comença una partida nova amb repartiments fixats per `seed` i juga fins al primer torn de l'agent
+    pub fn reset(&mut self, seed: u64) -> Result<Observation, String> {
+        self.board = Board::new_seeded((seed % 4) as usize, seed);
+        self.result = None;
+
+        // La partida no es pot acabar abans que l'agent jugui la primera carta
+        self.play_bots()?;
+
+        Ok(Observation::new(&self.board, self.config.seat))
+    }
+
+    pub fn step(&mut self, action: usize) -> Result<Step, String> {
+        if self.is_done() {
+            return Err("La partida s'ha acabat, cal fer reset.".to_string());
+        }
+        if action >= ACTION_COUNT || !self.board.get_legal_cards().contains(Card::from_index(action)) {
+            return Err(format!("Acció il·legal: {}", action));
+        }
+
+        let card_index: usize = self.board.get_current_player_hand().get_index(&Card::from_index(action)).unwrap();
+        let before: [CardSet; 2] = self.board.get_bazas();
+        let game_state: GameState = self.board.play_card(card_index)?;
+        let agent_points: [u32; 2] = self.settle(game_state, before);
+        let bot_points: [u32; 2] = self.play_bots()?;
+        let points_won: [u32; 2] = [agent_points[0] + bot_points[0], agent_points[1] + bot_points[1]];
+
+        let team: usize = self.config.seat % 2;
+        let result: GameState = self.result.unwrap_or(GameState::None);
+        let reward: f64 = match self.config.reward {
+            RewardMode::WinLoss => match result {
+                GameState::Team0Won => if team == 0 { 1.0 } else { -1.0 },
+                GameState::Team1Won => if team == 1 { 1.0 } else { -1.0 },
+                _ => 0.0
+            },
+            RewardMode::PointDifference => points_won[team] as f64 - points_won[1 - team] as f64
+        };
+
+        Ok(Step {
+            observation: Observation::new(&self.board, self.config.seat),
+            reward,
+            done: self.is_done(),
+            info: StepInfo { result, points_won }
+        })
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.result.is_some()
+    }
+
+    // Juga els bots fins que torna a ser el torn de l'agent o s'acaba la partida.
+    // Retorna els punts de carta guanyats per cada equip mentrestant. Si un bot falla la partida
+    // no pot continuar: l'episodi s'acaba sense guanyador i cal fer reset
+    fn play_bots(&mut self) -> Result<[u32; 2], String> {
+        let mut points_won: [u32; 2] = [0; 2];
+
+        while !self.is_done() && self.board.current_player != self.config.seat {
+            let seat: usize = self.board.current_player;
+            let before: [CardSet; 2] = self.board.get_bazas();
+            match self.config.bots[seat].play_card(&mut self.board) {
+                Ok(game_state) => {
+                    let points: [u32; 2] = self.settle(game_state, before);
+                    points_won = [points_won[0] + points[0], points_won[1] + points[1]];
+                },
+                Err(error) => {
+                    self.result = Some(GameState::None);
+                    return Err(format!("El bot del seient {} ha fallat: {}", seat, error));
+                }
+            }
+        }
+
+        Ok(points_won)
+    }
+
+    // Accions d'entre bazas i final de partida després d'una jugada. Retorna els punts de carta
+    // guanyats amb la jugada (`before` són les bazas d'abans)
+    fn settle(&mut self, game_state: GameState, before: [CardSet; 2]) -> [u32; 2] {
+        let bazas: [CardSet; 2] = self.board.get_bazas();
+        let mut after: [u32; 2] = [bazas[0].points(), bazas[1].points()];
+
+        match game_state {
+            GameState::BazaEnded => post_baza_actions(&self.config.bots, &mut self.board),
+            // El Board ja ha comptat la ronda (amb les 10 d'últimes) i ha repartit de nou
+            GameState::Continuation => after = self.board.get_points(),
+            GameState::Team0Won | GameState::Team1Won => {
+                // Si era l'última baza, el guanyador (el jugador actual) s'emporta les 10 d'últimes
+                if (0..4).all(|player| self.board.get_hand(player).cards.is_empty()) {
+                    after[self.board.get_team(self.board.current_player)] += 10;
+                }
+                self.result = Some(game_state);
+            },
+            _ => ()
+        }
+
+        [after[0] - before[0].points(), after[1] - before[1].points()]
+    }
+}
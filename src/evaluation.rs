@@ -0,0 +1,19 @@
+use crate::game::{Board, GameState};
+
+// Valor entre 0 i 1 per a l'equip `team` d'una simulació acabada en `game_state`: guanyar la
+// partida val 1 i, si només s'ha acabat la ronda, la proporció de punts de la ronda
+pub fn outcome_reward(game_state: GameState, board: &Board, team: usize) -> f64 {
+    match game_state {
+        GameState::Team0Won => if team == 0 { 1.0 } else { 0.0 },
+        GameState::Team1Won => if team == 1 { 1.0 } else { 0.0 },
+        _ => {
+            let points: [u32; 2] = board.get_points();
+            let total: u32 = points[0] + points[1];
+            if total == 0 {
+                0.5
+            } else {
+                points[team] as f64 / total as f64
+            }
+        }
+    }
+}
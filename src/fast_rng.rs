@@ -0,0 +1,22 @@
+use std::cell::RefCell;
+use rand::{thread_rng, RngCore, SeedableRng};
+use rand::rngs::SmallRng;
+
+// Atzar no reproduïble del motor i dels bots senzills. thread_rng és criptogràfic i, cridat a cada
+// repartiment i a cada jugada, era una bona part del temps d'una partida. Aquest generador es
+// llavora de thread_rng una sola vegada per fil. Les partides amb llavor continuen fent servir
+// StdRng, que és reproduïble entre versions de rand.
+
+thread_local! {
+    static FAST_RNG: RefCell<SmallRng> = RefCell::new(SmallRng::from_rng(thread_rng()).unwrap());     // Can't fail, thread_rng never errs
+}
+
+pub fn with_fast_rng<T, F: FnOnce(&mut SmallRng) -> T>(f: F) -> T {
+    FAST_RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+// Índex uniforme entre 0 i `len` (sense incloure'l). Multiplica en lloc de rebutjar mostres com
+// gen_range; amb menys de 2^16 opcions el biaix és negligible
+pub fn index_below<R: RngCore + ?Sized>(rng: &mut R, len: usize) -> usize {
+    ((rng.next_u32() as u64 * len as u64) >> 32) as usize
+}
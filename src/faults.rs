@@ -0,0 +1,52 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+// Tolerància a bots que fallen. El driver torna a demanar la jugada a un bot que retorna Err o
+// que fa panic fins a `max_retries` vegades; si continua fallant, se n'encarrega la `policy`. Els
+// panics de post_baza_actions es capturen i la partida continua amb el que el bot hagi fet.
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FaultPolicy {
+    // Es juga una carta legal a l'atzar en lloc de la del bot
+    SubstituteMove,
+    // L'equip del seient perd la partida
+    Forfeit
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FaultConfig {
+    pub max_retries: usize,
+    pub policy: FaultPolicy
+}
+
+impl Default for FaultConfig {
+    fn default() -> Self {
+        FaultConfig {
+            max_retries: 2,
+            policy: FaultPolicy::SubstituteMove
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Fault {
+    Error(String),
+    Panic(String)
+}
+
+// Crida `call` capturant els panics
+pub fn guard<T, F: FnOnce() -> Result<T, String>>(call: F) -> Result<T, Fault> {
+    match panic::catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(error)) => Err(Fault::Error(error)),
+        Err(payload) => Err(Fault::Panic(panic_message(payload.as_ref())))
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "panic".to_string()
+    }
+}
@@ -1,19 +1,23 @@
-use rand::thread_rng;
-use rand::seq::SliceRandom;
-use std::array;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
 use std::fmt;
-use std::mem::swap;
 use std::str::FromStr;
-use crate::utils::intersect;
+use crate::card_set::CardSet;
+use crate::card_tracking::{CardTracker, GameEvent};
+use crate::fast_rng::{index_below, with_fast_rng};
+use crate::zobrist::KEYS;
 use strum_macros::EnumIter;
-use strum::IntoEnumIterator;
 
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, EnumIter, Debug)]
 pub enum Pal {
     Orus, Copes, Espases, Bastos
 }
 
+impl Pal {
+    const ALL: [Pal; 4] = [Pal::Orus, Pal::Copes, Pal::Espases, Pal::Bastos];
+}
+
 impl FromStr for Pal {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -29,107 +33,188 @@ impl FromStr for Pal {
 }
 
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, EnumIter, Debug)]
+pub enum Rank {
+    As, Dos, Tres, Cuatro, Cinco, Seis, Siete, Sota, Caballo, Rey
+}
+
+impl Rank {
+    // Figures ordenades de menys a més força a la baza
+    const BY_STRENGTH: [Rank; 10] = [
+        Rank::Dos, Rank::Cuatro, Rank::Cinco, Rank::Seis, Rank::Siete,
+        Rank::Caballo, Rank::Sota, Rank::Rey, Rank::Tres, Rank::As
+    ];
+
+    pub fn value(&self) -> u32 {
+        const VALUE: [u32; 10] = [11, 0, 10, 0, 0, 0, 0, 3, 2, 4];
+        VALUE[*self as usize]
+    }
+
+    // Posició dins del pal a la baza: 0 pel 2, 9 per l'as.
+    // Amb taules en lloc de match: el match es compilava com a salt indirecte, i comparar i
+    // indexar cartes (a cada jugada) pagava un salt mal predit cada vegada
+    pub fn strength(&self) -> usize {
+        const STRENGTH: [usize; 10] = [9, 0, 8, 1, 2, 3, 4, 6, 5, 7];
+        STRENGTH[*self as usize]
+    }
+
+    pub fn is_stronger_than(&self, other: Rank) -> bool {
+        self.strength() > other.strength()
+    }
+
+    // Numero impres a la carta
+    pub fn number(&self) -> u32 {
+        match self {
+            Rank::As => 1,
+            Rank::Dos => 2,
+            Rank::Tres => 3,
+            Rank::Cuatro => 4,
+            Rank::Cinco => 5,
+            Rank::Seis => 6,
+            Rank::Siete => 7,
+            Rank::Sota => 10,
+            Rank::Caballo => 11,
+            Rank::Rey => 12
+        }
+    }
+}
+
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct Card {
     pub pal: Pal,
-    pub number: u32
+    pub rank: Rank
 }
 
 impl Card {
-    const NULL_CARD: u32 = 0;
+    // Index de la carta dins d'un CardSet
+    pub fn index(&self) -> usize {
+        self.pal as usize * 10 + self.rank.strength()
+    }
 
-    fn default() -> Self {
-        Card {
-            pal: Pal::Orus,
-            number: Card::NULL_CARD
-        }
+    pub fn from_index(index: usize) -> Self {
+        // Taula en lloc de dividir per 10: es crida cada vegada que es treu una carta d'un CardSet
+        const CARDS: [Card; 40] = Card::by_index();
+        CARDS[index]
     }
 
-    fn value(&self) -> u32 {
-        match self.number {
-            1 => 11,
-            3 => 10,
-            12 => 4,
-            10 => 3,
-            11 => 2,
-            _ => 0
+    const fn by_index() -> [Card; 40] {
+        let mut cards: [Card; 40] = [Card { pal: Pal::Orus, rank: Rank::Dos }; 40];
+        let mut index: usize = 0;
+        while index < 40 {
+            cards[index] = Card { pal: Pal::ALL[index / 10], rank: Rank::BY_STRENGTH[index % 10] };
+            index += 1;
         }
+
+        cards
+    }
+
+    pub fn value(&self) -> u32 {
+        self.rank.value()
     }
 
     pub fn is_better_than(&self, other_card: Card, trumfo: Pal) -> bool {
         if self.pal == other_card.pal {
-            if self.value() != other_card.value() {
-                self.value() > other_card.value()
-            } else {
-                self.number > other_card.number
-            }
-        } else if other_card.pal != trumfo {
-            true
+            self.rank.is_stronger_than(other_card.rank)
         } else {
-            false
+            self.pal == trumfo
         }
     }
 }
 
 
+#[derive(Clone)]
 struct Deck {
-    cards: Vec<Card>
+    cards: [Card; 40],
+    len: usize
 }
 
 impl Deck {
     fn new() -> Self {
         Deck {
-            cards: Vec::new(),
+            // Només les primeres `len` cartes són vàlides
+            cards: [Card::from_index(0); 40],
+            len: 0
         }
     }
 
-    fn fill(mut self) -> Self {
-        self.cards.clear();
+    // Les 40 cartes per pal i, dins de cada pal, per número
+    const ORDERED: [Card; 40] = Deck::ordered();
 
-        for pal in [Pal::Orus, Pal::Copes, Pal::Espases, Pal::Bastos] {
-            for number in [1, 2, 3, 4, 5, 6, 7, 10, 11, 12] {
-                self.cards.push(Card { pal: pal.clone(), number });
-            }
+    const fn ordered() -> [Card; 40] {
+        let ranks: [Rank; 10] = [
+            Rank::As, Rank::Dos, Rank::Tres, Rank::Cuatro, Rank::Cinco,
+            Rank::Seis, Rank::Siete, Rank::Sota, Rank::Caballo, Rank::Rey
+        ];
+        let mut cards: [Card; 40] = [Card { pal: Pal::Orus, rank: Rank::As }; 40];
+
+        let mut i: usize = 0;
+        while i < 40 {
+            cards[i] = Card { pal: Pal::ALL[i / 10], rank: ranks[i % 10] };
+            i += 1;
         }
 
+        cards
+    }
+
+    fn fill(mut self) -> Self {
+        self.cards = Deck::ORDERED;
+        self.len = 40;
+
         self
     }
 
-    fn scramble(mut self) -> Self {
-        self.cards.shuffle(&mut thread_rng());
+    // Fisher-Yates amb index_below, que és més ràpid que el shuffle de rand
+    fn scramble<R: Rng>(mut self, rng: &mut R) -> Self {
+        for i in (1..self.len).rev() {
+            self.cards.swap(i, index_below(rng, i + 1));
+        }
 
         self
     }
 
+    // Mazo barrejat. Amb llavor el repartiment és reproduïble
+    fn shuffled(seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => Deck::new().fill().scramble(&mut StdRng::seed_from_u64(seed)),
+            None => with_fast_rng(|rng| Deck::new().fill().scramble(rng))
+        }
+    }
+
     fn draw_card(&mut self) -> Option<Card> {
-        self.cards.pop()
+        if self.is_empty() {
+            return None;
+        }
+
+        self.len -= 1;
+        Some(self.cards[self.len])
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
     }
 }
 
 
+#[derive(Clone, Copy)]
 pub struct Hand {
-    pub cards: Vec<Card>
+    pub cards: CardSet
 }
 
 impl Hand {
     fn new() -> Self {
         Hand {
-            cards: Vec::new()
-        }
-    }
-
-    fn clone(&self) -> Self {
-        Hand { 
-            cards: self.cards.clone() 
+            cards: CardSet::EMPTY
         }
     }
 
     pub fn get_index(&self, card: &Card) -> Option<usize> {
-        self.cards.iter().position(|c| c == card)
+        self.cards.position(*card)
     }
 }
 
 
+#[derive(Clone, Copy)]
 struct Player {
     player_id: usize,
     team_id: usize,
@@ -145,36 +230,59 @@ impl Player {
         }
     }
 
-    fn play_card(&mut self, index: usize) -> Card {
-        self.hand.cards.remove(index)
+    fn play_card(&mut self, card: Card) {
+        self.hand.cards.remove(card);
     }
 
     fn give_card(&mut self, card: Card) {
-        self.hand.cards.push(card);
+        self.hand.cards.insert(card);
     }
 }
 
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum GameState {
     None, BazaEnded, Continuation, Team0Won, Team1Won
 }
 
+#[derive(Clone)]
 pub struct Board {
     players: [Player; 4],
     deck: Deck,
 
-    bazas: [Vec<Card>; 2],
-    cantes: Vec<Pal>,
+    bazas: [CardSet; 2],
+    // Pals cantats, en ordre; només valen els `cantes_len` primers. Sense Vec clonar el Board no reserva memòria
+    cantes: [Pal; 4],
+    cantes_len: usize,
     points: [u32; 2],
 
     pub current_player: usize,
     pub current_trumfo: Card,
-    current_baza: [Card; 4]
+    current_baza: [Option<Card>; 4],
+    // Qui va guanyant la baza en curs; no vol dir res si la baza és buida
+    baza_winner: usize,
+
+    // Informació pública sobre les mans (cartes ensenyades i pals fallats)
+    card_tracker: CardTracker,
+
+    // Llavor del proper repartiment, si la partida és reproduïble
+    deal_seed: Option<u64>,
+
+    // Hash de Zobrist de la posició, actualitzat incrementalment
+    hash: u64
 }
 
 impl Board {
     pub fn new(starting_player: usize) -> Self {
+        Board::new_with_seed(starting_player, None)
+    }
+
+    // Amb llavor tots els repartiments de la partida (també els de les rondes següents) són reproduïbles
+    pub fn new_seeded(starting_player: usize, seed: u64) -> Self {
+        Board::new_with_seed(starting_player, Some(seed))
+    }
+
+    fn new_with_seed(starting_player: usize, deal_seed: Option<u64>) -> Self {
         let mut players: [Player; 4] = [
             Player::new(0, 0),
             Player::new(1, 1),
@@ -182,7 +290,7 @@ impl Board {
             Player::new(3, 1),
         ];
 
-        let mut deck: Deck = Deck::new().fill().scramble();
+        let mut deck: Deck = Deck::shuffled(deal_seed);
         
         // Robar cartes inicials (es fa a la manera guiñote, de 3 en 3 fins a 6 per jugador)
         // PD: totalment inutil ja que es un random, pero es gracios
@@ -197,73 +305,130 @@ impl Board {
         let current_trumfo: Card = deck.draw_card().unwrap();
 
         // Tauler inicial
-        Board {
+        let mut board: Board = Board {
             players,
             deck,
-            bazas: array::from_fn(|_| Vec::new()),
-            cantes: Vec::new(),
+            bazas: [CardSet::EMPTY; 2],
+            cantes: [Pal::Orus; 4],
+            cantes_len: 0,
             points: [0; 2],
             current_player: starting_player,
             current_trumfo,
-            current_baza: [Card::default(); 4]
-        }
+            current_baza: [None; 4],
+            baza_winner: starting_player,
+            card_tracker: CardTracker::default(),
+            deal_seed: deal_seed.map(next_seed),
+            hash: 0
+        };
+        board.hash = board.compute_hash();
+
+        board
     }
 
     /////////// GETTERS //////////
     
     pub fn get_current_player_hand(&self) -> Hand {
-        self.players[self.current_player].hand.clone()
+        self.players[self.current_player].hand
+    }
+
+    // Cards played in the current baza, indexed by player
+    pub fn get_current_baza(&self) -> [Option<Card>; 4] {
+        self.current_baza
+    }
+
+    pub fn is_arrastre(&self) -> bool {
+        self.deck.is_empty()
+    }
+
+    // Zobrist hash of the position: hands, current baza, played cards, trumfo, cantes and player to move
+    pub fn get_hash(&self) -> u64 {
+        self.hash
+    }
+
+    pub fn get_hand(&self, player: usize) -> Hand {
+        self.players[player].hand
+    }
+
+    pub fn get_team(&self, player: usize) -> usize {
+        self.players[player].team_id
+    }
+
+    // Cards won by each team in the current round
+    pub fn get_bazas(&self) -> [CardSet; 2] {
+        self.bazas
+    }
+
+    pub fn get_points(&self) -> [u32; 2] {
+        self.points
+    }
+
+    pub fn get_cantes(&self) -> &[Pal] {
+        &self.cantes[..self.cantes_len]
+    }
+
+    pub fn get_card_tracker(&self) -> &CardTracker {
+        &self.card_tracker
+    }
+
+    pub fn get_deck_size(&self) -> usize {
+        self.deck.len
     }
 
-    // Returns legal cards for the current player
-    pub fn get_legal_cards(&self) -> Vec<Card> {
-        let player_hand: Vec<Card> = self.players[self.current_player].hand.cards.clone();
+    // Returns legal cards for the current player.
+    // Es crida a cada jugada (bots i is_legal_movement), per tant no pot fer cap allocació
+    pub fn get_legal_cards(&self) -> CardSet {
+        let player_hand: CardSet = self.players[self.current_player].hand.cards;
 
         // Si no es arrastre tots els moviments son legals
-        if self.deck.cards.len() > 0 {
+        if !self.is_arrastre() {
             return player_hand;
         }
 
-        // Si es la primera carta d'una baza es legal
         if Board::is_baza_empty(self.current_baza) {
             return player_hand;
         }
 
+        let starting_pal: Pal = self.current_baza[Board::baza_starting_player(self.current_player, self.current_baza)].unwrap().pal;
+        Board::arrastre_rules(player_hand, starting_pal, self.baza_winner, self.current_baza[self.baza_winner].unwrap(), self.current_player, self.current_trumfo.pal)
+    }
+
+    // Regles de l'arrastre sense Board, perquè el solver de finals les pugui aplicar al seu propi estat
+    pub(crate) fn arrastre_legal_cards(player_hand: CardSet, current_baza: [Option<Card>; 4], current_player: usize, trumfo: Pal) -> CardSet {
+        // Si es la primera carta d'una baza es legal
+        if Board::is_baza_empty(current_baza) {
+            return player_hand;
+        }
+
         // ARRASTRE
         // 1. Obtenir la carta que comença la baza
-        let starting_card_index: usize = self.current_baza.iter()
-                                                    .position(|&card| card.number != Card::NULL_CARD)
-                                                    .unwrap();
-        let starting_card: Card = self.current_baza[starting_card_index];
+        let starting_player: usize = Board::baza_starting_player(current_player, current_baza);
+        let starting_card: Card = current_baza[starting_player].unwrap();
 
         // 2. Obtenir la carta que va guanyant la baza
-        let winning_card_index: usize = Board::determine_baza_winner(starting_card_index, self.current_trumfo.pal, self.current_baza);
-        let winning_card: Card = self.current_baza[winning_card_index];
+        let winning_card_index: usize = Board::determine_baza_winner(starting_player, trumfo, current_baza);
+        let winning_card: Card = current_baza[winning_card_index].unwrap();
+
+        Board::arrastre_rules(player_hand, starting_card.pal, winning_card_index, winning_card, current_player, trumfo)
+    }
 
+    // El Board ja sap qui va guanyant la baza i no cal tornar-ho a calcular a cada jugada
+    fn arrastre_rules(player_hand: CardSet, starting_pal: Pal, winning_card_index: usize, winning_card: Card, current_player: usize, trumfo: Pal) -> CardSet {
         // 3. ??!?
-        let current_player_team_winning: bool = self.players[winning_card_index].team_id == self.players[self.current_player].team_id;
-        let cards_with_baza_pal: Vec<Card> = player_hand.iter()
-                                                        .filter(|&card| card.pal == starting_card.pal)
-                                                        .cloned()
-                                                        .collect();
-        let cards_better_than_winning_card: Vec<Card> = player_hand.iter()
-                                                                    .filter(|&card| card.is_better_than(winning_card, self.current_trumfo.pal))
-                                                                    .cloned()
-                                                                    .collect();
-        let cards_with_baza_pal_better_than_winning_card: Vec<Card> = intersect(&cards_with_baza_pal, &cards_better_than_winning_card);
+        let current_player_team_winning: bool = winning_card_index % 2 == current_player % 2;
+        let cards_with_baza_pal: CardSet = player_hand & CardSet::suit(starting_pal);
+        let cards_better_than_winning_card: CardSet = player_hand & CardSet::better_than(winning_card, trumfo);
+        let cards_with_baza_pal_better_than_winning_card: CardSet = cards_with_baza_pal & cards_better_than_winning_card;
 
         if current_player_team_winning {
-            if cards_with_baza_pal.len() > 0 {
+            if !cards_with_baza_pal.is_empty() {
                 return cards_with_baza_pal;
             }
-        } else {
-            if cards_with_baza_pal_better_than_winning_card.len() > 0 {
-                return cards_with_baza_pal_better_than_winning_card;
-            } else if cards_with_baza_pal.len() > 0 {
-                return cards_with_baza_pal;
-            } else if cards_better_than_winning_card.len() > 0 {
-                return cards_better_than_winning_card;
-            }
+        } else if !cards_with_baza_pal_better_than_winning_card.is_empty() {
+            return cards_with_baza_pal_better_than_winning_card;
+        } else if !cards_with_baza_pal.is_empty() {
+            return cards_with_baza_pal;
+        } else if !cards_better_than_winning_card.is_empty() {
+            return cards_better_than_winning_card;
         }
 
         // 4. Profit
@@ -273,55 +438,60 @@ impl Board {
     // Returns available cantes for the player
     pub fn get_available_cantes(&self, player: usize) -> Vec<Pal> {
         let mut pals: Vec<Pal> = vec![];
+
+        // Tots els bots ho pregunten per cada jugador després de cada baza i gairebé mai es pot
+        if !self.player_team_won_last_baza(player) {
+            return pals;
+        }
         
-        for pal in Pal::iter() {
-            match self.is_legal_cante(player, pal) {
-                Ok(()) => pals.push(pal),
-                Err(_) => ()
+        for pal in Pal::ALL {
+            if self.is_legal_cante(player, pal).is_ok() {
+                pals.push(pal);
             }
         }
 
         pals
     }
 
-    // TODO: No s'ha de poder canviar trumfo si s'acaba de robar a l'ultima baza abans de l'arrastre
     pub fn is_canvi_trumfo_available(&self, player: usize) -> Result<(), String> {
-        if player >= 4 {
-            return Err("Invalid player index.".to_string());
-        }
-
-        // Nomes es pot canviar si s'ha guanyat l'ultima baza
-        if !self.player_team_won_last_baza(player) {
-            return Err("No es pot canviar trumfo si no és començament de baza o no ha guanyat la última baza.".to_string());
-        }
-
-        // No es pot fer en arrastre (be nomes abans de començarlo, per tant es pot saber pel nombre de cartes del jugador)
-        if self.players[player].hand.cards.len() < 6 {
-            return Err("No es pot canviar trumfo durant l'arrastre.".to_string());
-        }
-
-        // Comprovem que tingui la carta valida a la ma
-        let seven_card: Card = Card { pal: self.current_trumfo.pal, number: 7 };
-        if  !self.players[player].hand.cards.contains(&seven_card) {
-            return Err("No es pot canviar trumfo sense el 7 de trumfo a la mà.".to_string());
-        }
+        self.check_canvi_trumfo(player).map_err(str::to_string)
+    }
 
-        Ok(())
+    // Com is_canvi_trumfo_available, sense construir el missatge d'error
+    pub fn can_change_trumfo(&self, player: usize) -> bool {
+        self.check_canvi_trumfo(player).is_ok()
     }
 
     ////////// CARD PLAYS //////////
 
     pub fn play_card(&mut self, index: usize) -> Result<GameState, String> {
-        match self.is_legal_movement(index) {
-            Ok(()) => (),
-            Err(error) => return Err(error)
-        }
+        let result: Result<GameState, String> = self.apply_card(index);
+        self.debug_check_hash();
+
+        result
+    }
+
+    fn apply_card(&mut self, index: usize) -> Result<GameState, String> {
+        let played_card: Card = match self.is_legal_movement(index) {
+            Ok(card) => card,
+            Err(error) => return Err(error.to_string())
+        };
         
         // Play move
-        let player: &mut Player = &mut self.players[self.current_player];
-        let played_card: Card = player.play_card(index);
-        self.current_baza[self.current_player] = played_card;
-        self.current_player = Board::next_player(self.current_player);
+        self.players[self.current_player].play_card(played_card);
+        self.card_tracker.observe(GameEvent::CardPlayed {
+            player: self.current_player,
+            card: played_card,
+            baza: self.current_baza,
+            trumfo: self.current_trumfo.pal,
+            arrastre: self.is_arrastre()
+        });
+        if Board::is_baza_empty(self.current_baza) || played_card.is_better_than(self.current_baza[self.baza_winner].unwrap(), self.current_trumfo.pal) {
+            self.baza_winner = self.current_player;
+        }
+        self.current_baza[self.current_player] = Some(played_card);
+        self.hash ^= KEYS.hand(self.current_player, played_card) ^ KEYS.current_baza(self.current_player, played_card);
+        self.set_current_player(Board::next_player(self.current_player));
 
         // Early return si la baza encara no s'ha completat
         if !Board::is_baza_complete(self.current_baza) {
@@ -329,16 +499,20 @@ impl Board {
         }
 
         // Complete baza
-        self.current_player = Board::determine_baza_winner(self.current_player, self.current_trumfo.pal, self.current_baza);
+        self.set_current_player(self.baza_winner);
         let team_id: usize = self.players[self.current_player].team_id;
-        self.bazas[team_id].extend(self.current_baza.iter());
+        for (player, card) in self.current_baza.into_iter().enumerate() {
+            let card: Card = card.unwrap();
+            self.bazas[team_id].insert(card);
+            self.hash ^= KEYS.current_baza(player, card) ^ KEYS.baza(team_id, card);
+        }
         
         // Reset baza
-        self.current_baza = [Card::default(); 4];
+        self.current_baza = [None; 4];
 
         // Si es segona ronda comptar punts
         if self.points[0] > 0 || self.points[1] > 0 {
-            let second_round_points: [u32; 2] = Board::count_points(self.bazas.clone());
+            let second_round_points: [u32; 2] = Board::count_points(self.bazas);
             if self.points[0] + second_round_points[0] >= 101 {
                 return Ok(GameState::Team0Won);
             } else if self.points[1] + second_round_points[1] >= 101 {
@@ -347,23 +521,27 @@ impl Board {
         }
 
         // Draw cards
-        if self.deck.cards.len() > 0 {
+        if !self.deck.is_empty() {
             let mut draw_player: usize = self.current_player;
             for _ in 0..4 {
-                match self.deck.draw_card() {
-                    Some(card) => self.players[draw_player].give_card(card),
-                    // Si no queden cartes al mazo es canvia el trumfo
-                    None => self.players[draw_player].give_card(self.current_trumfo)
-                }
+                // Si no queden cartes al mazo es roba el trumfo girat
+                let card: Card = match self.deck.draw_card() {
+                    Some(card) => card,
+                    None => {
+                        self.card_tracker.observe(GameEvent::TrumfoDrawn { player: draw_player, trumfo: self.current_trumfo });
+                        self.current_trumfo
+                    }
+                };
+                self.players[draw_player].give_card(card);
+                self.hash ^= KEYS.hand(draw_player, card);
 
                 draw_player = Board::next_player(draw_player);
             }
         }
 
         // Check for game end
-        if self.players[0].hand.cards.len() == 0 {
-            // bazas hauria de ser una immutable reference pero no es pot pk self es &mut
-            self.points = Board::count_points(self.bazas.clone());
+        if self.players[0].hand.cards.is_empty() {
+            self.points = Board::count_points(self.bazas);
 
             // 10 de ultimas
             self.points[self.players[self.current_player].team_id] += 10;
@@ -376,7 +554,8 @@ impl Board {
             }
 
             // Reset game
-            self.deck = Deck::new().fill().scramble();
+            self.deck = Deck::shuffled(self.deal_seed);
+            self.deal_seed = self.deal_seed.map(next_seed);
 
             for i in 0..8 {
                 for _ in 0..3 {
@@ -387,10 +566,12 @@ impl Board {
             }
 
             self.current_trumfo = self.deck.draw_card().unwrap();
-            self.bazas = array::from_fn(|_| Vec::new());
-            self.cantes = Vec::new();
+            self.bazas = [CardSet::EMPTY; 2];
+            self.cantes_len = 0;
             self.current_player = Board::next_player(self.current_player);
-            self.current_baza = [Card::default(); 4];
+            self.current_baza = [None; 4];
+            self.card_tracker.observe(GameEvent::NewRound);
+            self.hash = self.compute_hash();
 
             return Ok(GameState::Continuation);
         }
@@ -401,10 +582,13 @@ impl Board {
     pub fn cantar(&mut self, player: usize, pal: Pal) -> Result<(), String> {
         match self.is_legal_cante(player, pal) {
             Ok(()) => (),
-            Err(error) => return Err(error)
+            Err(error) => return Err(error.to_string())
         }
 
-        self.cantes.push(pal);
+        self.cantes[self.cantes_len] = pal;
+        self.cantes_len += 1;
+        self.hash ^= KEYS.cante(pal);
+        self.card_tracker.observe(GameEvent::Cante { player, pal });
 
         let player_team: usize = self.players[player].team_id;
         if pal == self.current_trumfo.pal {
@@ -413,68 +597,195 @@ impl Board {
             self.points[player_team] += 20
         }
 
+        self.debug_check_hash();
         Ok(())
     }
 
     pub fn change_trumfo_card(&mut self, player: usize) -> Result<(), String> {
-        match self.is_canvi_trumfo_available(player) {
+        match self.check_canvi_trumfo(player) {
             Ok(()) => (),
-            Err(error) => return Err(error)
+            Err(error) => return Err(error.to_string())
+        }
+
+        let seven_card: Card = Card { pal: self.current_trumfo.pal, rank: Rank::Siete };
+        let hand: &mut CardSet = &mut self.players[player].hand.cards;
+        hand.remove(seven_card);
+        hand.insert(self.current_trumfo);
+        self.hash ^= KEYS.hand(player, seven_card) ^ KEYS.hand(player, self.current_trumfo);
+        self.hash ^= KEYS.trumfo(self.current_trumfo) ^ KEYS.trumfo(seven_card);
+        self.card_tracker.observe(GameEvent::CanviTrumfo { player, trumfo: self.current_trumfo });
+        self.current_trumfo = seven_card;
+
+        self.debug_check_hash();
+        Ok(())
+    }
+
+    ////////// DETERMINIZATION //////////
+
+    // Substitueix les mans i l'ordre del mazo (l'última carta és la propera a robar).
+    // Els bots de cerca ho fan servir per repartir les cartes que no veuen; per això
+    // s'exigeix que les mides i el conjunt de cartes repartides siguin els mateixos.
+    pub fn redeal_cards(&mut self, hands: [CardSet; 4], deck: &[Card]) -> Result<(), String> {
+        if deck.len() != self.deck.len {
+            return Err("El mazo ha de tenir el mateix nombre de cartes.".to_string());
         }
 
-        let mut seven_card: &Card = self.players[player].hand.cards
-                                                    .iter()
-                                                    .find(|&card| card == &Card { pal: self.current_trumfo.pal, number: 7 })
-                                                    .unwrap();
-        let mut trumfo_card: &Card = &self.current_trumfo;
+        let mut old_cards: CardSet = self.deck.cards[..self.deck.len].iter().copied().collect();
+        let mut new_cards: CardSet = deck.iter().copied().collect();
+        if new_cards.len() != deck.len() {
+            return Err("El mazo té cartes repetides.".to_string());
+        }
 
-        swap(&mut seven_card, &mut trumfo_card);
+        for (player, hand) in hands.iter().enumerate() {
+            if hand.len() != self.players[player].hand.cards.len() {
+                return Err("Les mans han de tenir el mateix nombre de cartes.".to_string());
+            }
+            if !(new_cards & *hand).is_empty() {
+                return Err("Una carta no pot estar repartida dues vegades.".to_string());
+            }
+
+            old_cards |= self.players[player].hand.cards;
+            new_cards |= *hand;
+        }
+
+        if old_cards != new_cards {
+            return Err("Només es poden repartir les cartes que hi ha a les mans i al mazo.".to_string());
+        }
+
+        for (player, hand) in hands.into_iter().enumerate() {
+            self.players[player].hand.cards = hand;
+        }
+        self.deck.cards[..deck.len()].copy_from_slice(deck);
+        self.hash = self.compute_hash();
 
         Ok(())
     }
 
+    // Torna a la mà l'última carta jugada de la baza en curs. El mostrejador de mans ho fa servir per
+    // veure què hauria jugat un model d'oponent; el card_tracker no es desfà.
+    pub(crate) fn take_back_card(&mut self) -> Option<Card> {
+        if Board::is_baza_empty(self.current_baza) {
+            return None;
+        }
+
+        let player: usize = (self.current_player + 3) % 4;
+        let card: Card = self.current_baza[player].take().unwrap();     // Can't panic, the previous player has played
+        self.players[player].give_card(card);
+        self.hash ^= KEYS.current_baza(player, card) ^ KEYS.hand(player, card);
+        self.set_current_player(player);
+        if !Board::is_baza_empty(self.current_baza) {
+            self.baza_winner = Board::determine_baza_winner(Board::baza_starting_player(player, self.current_baza), self.current_trumfo.pal, self.current_baza);
+        }
+
+        self.debug_check_hash();
+        Some(card)
+    }
+
     /////////// PRIVATE METHODS //////////
 
-    fn is_legal_movement(&self, index: usize) -> Result<(), String> {
-        // Check for player hand size
-        if index >= self.players[self.current_player].hand.cards.len() {
-            return Err("Invalid card index.".to_string());
+    fn set_current_player(&mut self, player: usize) {
+        self.hash ^= KEYS.current_player(self.current_player) ^ KEYS.current_player(player);
+        self.current_player = player;
+    }
+
+    // Recalcula el hash des de zero. Només per inicialitzar i per comprovar l'incremental
+    fn compute_hash(&self) -> u64 {
+        let mut hash: u64 = KEYS.current_player(self.current_player) ^ KEYS.trumfo(self.current_trumfo);
+
+        for (player_id, player) in self.players.iter().enumerate() {
+            for card in player.hand.cards {
+                hash ^= KEYS.hand(player_id, card);
+            }
         }
 
-        let card_played: Card = self.players[self.current_player].hand.cards[index];
-        if !self.get_legal_cards().contains(&card_played) {
-            return Err("Renuncio. Carta invàlida.".to_string());
+        for (player, card) in self.current_baza.into_iter().enumerate() {
+            if let Some(card) = card {
+                hash ^= KEYS.current_baza(player, card);
+            }
+        }
+
+        for (team_id, baza) in self.bazas.iter().enumerate() {
+            for card in baza.iter() {
+                hash ^= KEYS.baza(team_id, card);
+            }
+        }
+
+        for &pal in self.get_cantes() {
+            hash ^= KEYS.cante(pal);
+        }
+
+        hash
+    }
+
+    fn debug_check_hash(&self) {
+        debug_assert_eq!(self.hash, self.compute_hash(), "Incremental Zobrist hash out of sync:\n{}", self);
+    }
+
+    // La carta de la posició `index` de la mà, si es pot jugar
+    fn is_legal_movement(&self, index: usize) -> Result<Card, &'static str> {
+        // Check for player hand size
+        let Some(card_played) = self.players[self.current_player].hand.cards.nth(index) else {
+            return Err("Invalid card index.");
+        };
+
+        if !self.get_legal_cards().contains(card_played) {
+            return Err("Renuncio. Carta invàlida.");
         }
         
+        Ok(card_played)
+    }
+
+    fn check_canvi_trumfo(&self, player: usize) -> Result<(), &'static str> {
+        if player >= 4 {
+            return Err("Invalid player index.");
+        }
+
+        // Nomes es pot canviar si s'ha guanyat l'ultima baza
+        if !self.player_team_won_last_baza(player) {
+            return Err("No es pot canviar trumfo si no és començament de baza o no ha guanyat la última baza.");
+        }
+
+        // No es pot fer en arrastre, ni just després d'haver robat l'última carta (el trumfo ja és a una mà)
+        if self.is_arrastre() {
+            return Err("No es pot canviar trumfo durant l'arrastre.");
+        }
+
+        // Comprovem que tingui la carta valida a la ma
+        let seven_card: Card = Card { pal: self.current_trumfo.pal, rank: Rank::Siete };
+        if  !self.players[player].hand.cards.contains(seven_card) {
+            return Err("No es pot canviar trumfo sense el 7 de trumfo a la mà.");
+        }
+
         Ok(())
     }
 
-    fn is_legal_cante(&self, player: usize, pal: Pal) -> Result<(), String> {
+    fn is_legal_cante(&self, player: usize, pal: Pal) -> Result<(), &'static str> {
         if player >= 4 {
-            return Err("Invalid player index.".to_string());
+            return Err("Invalid player index.");
         }
 
         // Nomes es pot cantar si s'ha guanyat l'ultima baza
         if !self.player_team_won_last_baza(player) {
-            return Err("No es pot cantar si no és començament de baza o no ha guanyat la última baza.".to_string());
+            return Err("No es pot cantar si no és començament de baza o no ha guanyat la última baza.");
         }
 
         // No es pot cantar si ja s'ha cantat en aquest pal
-        if self.cantes.contains(&pal) {
-            return Err("No es pot cantar si ja s'ha cantat en aquest pal.".to_string());
+        if self.get_cantes().contains(&pal) {
+            return Err("No es pot cantar si ja s'ha cantat en aquest pal.");
         }
 
         // Comprovem que tingui les cartes valides a la ma
-        let sota: Card = Card { pal, number: 10 };
-        let rey: Card = Card { pal, number: 12 };
-        if  !self.players[player].hand.cards.contains(&sota) ||
-            !self.players[player].hand.cards.contains(&rey) {
-            return Err("No es pot cantar sense la sota i el rey a la mà.".to_string());
+        let sota: Card = Card { pal, rank: Rank::Sota };
+        let rey: Card = Card { pal, rank: Rank::Rey };
+        if  !self.players[player].hand.cards.contains(sota) ||
+            !self.players[player].hand.cards.contains(rey) {
+            return Err("No es pot cantar sense la sota i el rey a la mà.");
         }
 
         Ok(())
     }
 
+
     fn player_team_won_last_baza(&self, player: usize) -> bool {
         // Invalid player index
         if player >= self.players.len() {
@@ -498,48 +809,38 @@ impl Board {
 
     /////////// STATIC HELPER METHODS //////////
 
-    fn count_points(bazas: [Vec<Card>; 2]) -> [u32; 2] {
-        let mut points: [u32; 2] = [0; 2];
-        
-        for (team_id, team_bazas) in bazas.iter().enumerate() {
-            for card in team_bazas {
-                points[team_id] += card.value();
-            }
-        }
-
-        points
+    fn count_points(bazas: [CardSet; 2]) -> [u32; 2] {
+        [bazas[0].points(), bazas[1].points()]
     }
 
-    fn is_baza_empty(baza: [Card; 4]) -> bool {
-        for card in baza {
-            if card.number != Card::NULL_CARD {
-                return false;
-            }
-        }
-
-        true
+    pub(crate) fn is_baza_empty(baza: [Option<Card>; 4]) -> bool {
+        baza.iter().all(|card| card.is_none())
     }
 
-    fn is_baza_complete(baza: [Card; 4]) -> bool {
-        for card in baza {
-            if card.number == Card::NULL_CARD {
-                return false;
-            }
-        }
+    pub(crate) fn is_baza_complete(baza: [Option<Card>; 4]) -> bool {
+        baza.iter().all(|card| card.is_some())
+    }
 
-        true
+    // El jugador que ha obert la baza en curs
+    pub(crate) fn baza_starting_player(current_player: usize, baza: [Option<Card>; 4]) -> usize {
+        let played_cards: usize = baza.iter()
+                                      .filter(|card| card.is_some())
+                                      .count();
+        (current_player + 4 - played_cards) % 4
     }
 
-    fn determine_baza_winner(starting_player: usize, current_trumfo: Pal, baza: [Card; 4]) -> usize {
+    pub(crate) fn determine_baza_winner(starting_player: usize, current_trumfo: Pal, baza: [Option<Card>; 4]) -> usize {
         let mut winner: usize = starting_player;
+        let mut winner_card: Card = baza[starting_player].unwrap();
         let mut player_to_check: usize = Board::next_player(starting_player);
 
         for _ in 0..3 {
-            let winner_card: Card = baza[winner];
-            let current_card: Card = baza[player_to_check];
-
-            if current_card.is_better_than(winner_card, current_trumfo) {
-                winner = player_to_check;
+            // Les bazas a mitges tenen posicions buides
+            if let Some(current_card) = baza[player_to_check] {
+                if current_card.is_better_than(winner_card, current_trumfo) {
+                    winner = player_to_check;
+                    winner_card = current_card;
+                }
             }
 
             player_to_check = Board::next_player(player_to_check);
@@ -548,12 +849,17 @@ impl Board {
         winner
     }
 
-    fn next_player(current_player: usize) -> usize {
+    pub(crate) fn next_player(current_player: usize) -> usize {
         (current_player + 1) % 4
     }
 
 }
 
+// Llavor del repartiment de la ronda següent
+fn next_seed(seed: u64) -> u64 {
+    seed.wrapping_add(0x9e37_79b9_7f4a_7c15)
+}
+
 /////////////////////// FORMATTERS ///////////////////////
 
 impl fmt::Display for Pal {
@@ -568,16 +874,23 @@ impl fmt::Display for Pal {
     }
 }
 
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())?;
+        Ok(())
+    }
+}
+
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {}", self.pal, self.number)?;
+        write!(f, "{} {}", self.pal, self.rank)?;
         Ok(())
     }
 }
 
 impl fmt::Display for Hand {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for &card in &self.cards {
+        for card in self.cards {
             write!(f, "{}, ", card)?;
         }
         Ok(())
@@ -586,7 +899,7 @@ impl fmt::Display for Hand {
 
 impl fmt::Display for Deck {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let card_strings: Vec<String> = self.cards.iter().map(|card: &Card| {
+        let card_strings: Vec<String> = self.cards[..self.len].iter().map(|card: &Card| {
             card.to_string()
         }).collect();
     
@@ -597,7 +910,7 @@ impl fmt::Display for Deck {
 
 impl fmt::Display for Player {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let card_strings: Vec<String> = self.hand.cards.iter().map(|card: &Card| {
+        let card_strings: Vec<String> = self.hand.cards.iter().map(|card: Card| {
             card.to_string()
         }).collect();
 
@@ -648,7 +961,10 @@ impl fmt::Display for Board {
         write!(f, "Current Baza: [")?;
         for (i, card) in self.current_baza.iter().enumerate() {
             if i > 0 { write!(f, ", ")? }
-            write!(f, "{}", card)?;
+            match card {
+                Some(card) => write!(f, "{}", card)?,
+                None => write!(f, "-")?
+            }
         }
         writeln!(f, "]")?;
         
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::time::Duration;
+use crate::faults::Fault;
+use crate::game::{Card, GameState};
+use crate::signals::Signal;
+
+// Registre d'una partida jugada pel driver, en ordre. Es pot escriure en text amb una línia per
+// esdeveniment:
+//   play <seient> <carta>
+//   signal <seient> <seña> seen <seients que la veuen>
+//   clock <seient> <ms gastats> <ms que queden>
+//   timeout <seient>
+//   fault <seient> error|panic <missatge>
+//   substitute <seient>
+//   forfeit <seient>
+//   result <estat final>
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum LogEvent {
+    Play { seat: usize, card: Card },
+    Signal { seat: usize, signal: Signal, seen_by: [bool; 4] },
+    // Temps de la jugada i el que queda al rellotge del seient després de l'increment
+    Clock { seat: usize, used: Duration, remaining: Duration },
+    // Al seient se li ha acabat el temps
+    Timeout { seat: usize },
+    // El bot del seient ha fallat en jugar o en les accions de després de la baza
+    Fault { seat: usize, fault: Fault },
+    // El driver juga una carta a l'atzar pel seient perquè el bot ha fallat massa vegades
+    Substitute { seat: usize },
+    // L'equip del seient perd per culpa de les fallades del bot
+    Forfeit { seat: usize },
+    Result { state: GameState }
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct GameLog {
+    events: Vec<LogEvent>
+}
+
+impl GameLog {
+    pub fn new() -> Self {
+        GameLog::default()
+    }
+
+    pub fn push(&mut self, event: LogEvent) {
+        self.events.push(event);
+    }
+
+    pub fn get_events(&self) -> &[LogEvent] {
+        &self.events
+    }
+
+    pub fn plays(&self) -> impl Iterator<Item = (usize, Card)> + '_ {
+        self.events.iter().filter_map(|event| match *event {
+            LogEvent::Play { seat, card } => Some((seat, card)),
+            _ => None
+        })
+    }
+
+    pub fn signals(&self) -> impl Iterator<Item = (usize, Signal, [bool; 4])> + '_ {
+        self.events.iter().filter_map(|event| match *event {
+            LogEvent::Signal { seat, signal, seen_by } => Some((seat, signal, seen_by)),
+            _ => None
+        })
+    }
+
+    // Temps total gastat per cada seient
+    pub fn time_used(&self) -> [Duration; 4] {
+        let mut used: [Duration; 4] = [Duration::ZERO; 4];
+        for event in self.events.iter() {
+            if let LogEvent::Clock { seat, used: time, .. } = *event {
+                used[seat] += time;
+            }
+        }
+
+        used
+    }
+
+    pub fn timeouts(&self) -> usize {
+        self.events.iter().filter(|event| matches!(event, LogEvent::Timeout { .. })).count()
+    }
+
+    // Fallades de cada seient
+    pub fn faults(&self) -> [usize; 4] {
+        let mut faults: [usize; 4] = [0; 4];
+        for event in self.events.iter() {
+            if let LogEvent::Fault { seat, .. } = *event {
+                faults[seat] += 1;
+            }
+        }
+
+        faults
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+impl fmt::Display for LogEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogEvent::Play { seat, card } => write!(f, "play {} {}", seat, card),
+            LogEvent::Signal { seat, signal, seen_by } => {
+                let seats: Vec<String> = (0..4).filter(|&seat| seen_by[seat]).map(|seat| seat.to_string()).collect();
+                write!(f, "signal {} {} seen {}", seat, signal, seats.join(" "))
+            },
+            LogEvent::Clock { seat, used, remaining } => {
+                write!(f, "clock {} {:.3} {:.3}", seat, used.as_secs_f64() * 1000.0, remaining.as_secs_f64() * 1000.0)
+            },
+            LogEvent::Timeout { seat } => write!(f, "timeout {}", seat),
+            LogEvent::Fault { seat, fault: Fault::Error(message) } => write!(f, "fault {} error {}", seat, message),
+            LogEvent::Fault { seat, fault: Fault::Panic(message) } => write!(f, "fault {} panic {}", seat, message),
+            LogEvent::Substitute { seat } => write!(f, "substitute {}", seat),
+            LogEvent::Forfeit { seat } => write!(f, "forfeit {}", seat),
+            LogEvent::Result { state } => write!(f, "result {}", state)
+        }
+    }
+}
+
+impl fmt::Display for GameLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for event in self.events.iter() {
+            writeln!(f, "{}", event)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+pub mod game;
+pub mod card_set;
+pub mod card_tracking;
+pub mod zobrist;
+pub mod fast_rng;
+pub mod determinization;
+pub mod evaluation;
+pub mod endgame_solver;
+pub mod cfr;
+pub mod tablebase;
+pub mod stats;
+pub mod tuning;
+pub mod bot_behaviour;
+pub mod driver;
+pub mod signals;
+pub mod game_log;
+pub mod clock;
+pub mod faults;
+pub mod environment;
+pub mod encoding;
+pub mod dataset;
+pub mod neural;
+pub mod modeling;
+pub mod bot_behaviours {
+    pub mod random_bot;
+    pub mod smart_bot;
+    pub mod expert_bot;
+    pub mod weighted_bot;
+    pub mod ismcts_bot;
+    pub mod pimc_bot;
+    pub mod neural_bot;
+    pub mod cfr_bot;
+    pub mod observed_bot;
+    pub mod adaptive_bot;
+    pub mod signal_bot;
+    pub mod ensemble_bot;
+    pub mod stochastic_bot;
+}
@@ -1,42 +1,118 @@
-mod game;
-mod bot_behaviour;
-mod utils;
-mod bot_behaviours {
-    pub mod random_bot;
-    pub mod smart_bot;
-}
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+use rust_guinote::bot_behaviours::{random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::weighted_bot::{WeightedBot, Weights};
+use rust_guinote::bot_behaviours::ismcts_bot::{IsmctsBot, IsmctsConfig};
+use rust_guinote::bot_behaviours::pimc_bot::{PimcBot, PimcConfig};
+use rust_guinote::bot_behaviours::neural_bot::{NeuralBot, NeuralConfig};
+use rust_guinote::bot_behaviours::cfr_bot::{CfrBot, CfrBotConfig};
+use rust_guinote::bot_behaviours::observed_bot::ObservedBot;
+use rust_guinote::bot_behaviours::adaptive_bot::{AdaptiveBot, AdaptiveConfig};
+use rust_guinote::bot_behaviours::ensemble_bot::{EnsembleBot, EnsembleConfig, EnsembleRule, Member};
+use rust_guinote::bot_behaviours::stochastic_bot::{EpsilonGreedyBot, EpsilonGreedyConfig, SoftmaxBot, SoftmaxConfig};
+use rust_guinote::modeling::{ModelStore, SharedModels};
+use rust_guinote::neural::Network;
+use rust_guinote::game::GameState;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::driver::{play_game_with, DriverOptions};
+use rust_guinote::clock::{ClockConfig, TimeoutPolicy};
+use rust_guinote::faults::{FaultConfig, FaultPolicy};
+use rust_guinote::game_log::GameLog;
+use rust_guinote::stats::WinRate;
+
+const USAGE: &str = "Usage: RustGuinote [team 0 bot] [team 1 bot] [games] [models file] [--clock=<seconds>+<increment>] [--timeout=forfeit|random] [--faults=substitute|forfeit]";
+const BOT_NAMES: &str = "random, smart, expert, weighted, weighted:<weights file>, ismcts, pimc, cfr, adaptive, neural:<network file>, ensemble:<bot>+<bot>+..., epsilon:<epsilon>:<bot>, softmax:<temperature>:<bot>";
+
+// Usage: RustGuinote [team 0 bot] [team 1 bot] [games] [models file] [--clock=<seconds>+<increment>] [--timeout=forfeit|random] [--faults=substitute|forfeit]
+fn main() -> ExitCode {
+    let (flags, args): (Vec<String>, Vec<String>) = env::args().partition(|arg| arg.starts_with("--"));
+    let options: DriverOptions = match parse_flags(&flags) {
+        Ok(options) => options,
+        Err(error) => {
+            println!("{}\n{}", error, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let team_0_bot: &str = args.get(1).map_or("random", String::as_str);
+    let team_1_bot: &str = args.get(2).map_or("smart", String::as_str);
+    let num_games: usize = match args.get(3).map(|games| games.parse::<usize>()) {
+        None => 1000,
+        Some(Ok(games)) => games,
+        Some(Err(_)) => {
+            println!("Invalid number of games. {}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Els models dels jugadors es carreguen del fitxer si existeix i s'hi desen en acabar
+    let models_path: Option<&str> = args.get(4).map(String::as_str);
+    let models: SharedModels = match models_path.filter(|path| Path::new(path).exists()).map(ModelStore::load) {
+        None => ModelStore::new().shared(),
+        Some(Ok(store)) => store.shared(),
+        Some(Err(error)) => {
+            println!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Team 0 juga als seients 0 i 2, Team 1 als 1 i 3
+    let mut bots: Vec<Box<dyn Behaviour>> = Vec::new();
+    for seat in 0..4 {
+        let name: &str = if seat % 2 == 0 { team_0_bot } else { team_1_bot };
+        // Els dos seients d'un equip són el mateix jugador per als models
+        let player: String = name.replace(char::is_whitespace, "_");
+        match create_bot(name, &models) {
+            Ok(bot) => bots.push(Box::new(ObservedBot::new(bot, &player, models.clone()))),
+            Err(error) => {
+                println!("{}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    // Cada partida es registra per saber el temps gastat i les fallades dels bots
+    let mut time_used: [Duration; 2] = [Duration::ZERO; 2];
+    let mut timeouts: usize = 0;
+    let mut faults: [usize; 4] = [0; 4];
+    let games: Vec<GameState> = (0..num_games).map(|_| {
+        let mut log: GameLog = GameLog::new();
+        let game_state: GameState = play_game_with(&bots, &options, &mut log);
+        for (seat, used) in log.time_used().iter().enumerate() {
+            time_used[seat % 2] += *used;
+        }
+        timeouts += log.timeouts();
+        for (seat, count) in log.faults().iter().enumerate() {
+            faults[seat] += count;
+        }
+        game_state
+    }).collect();
 
-use bot_behaviours::{random_bot::RandomBot, smart_bot::SmartBot};
-use game::{Board, GameState};
-use crate::bot_behaviour::Behaviour;
-use rand::{thread_rng, Rng};
-
-
-fn main() {
-    // Team 0
-    let bot_0: RandomBot = RandomBot {};
-    let bot_2: RandomBot = RandomBot {};
-
-    // Team 1
-    let bot_1: SmartBot = SmartBot {};
-    let bot_3: SmartBot = SmartBot {};
-    
-    let bots: Vec<Box<dyn Behaviour>> = vec![
-        Box::new(bot_0),
-        Box::new(bot_1), 
-        Box::new(bot_2), 
-        Box::new(bot_3)
-    ];
-
-    let mut games: [GameState; 1000] = [const { GameState::None }; 1000];
-
-    for i in 0..games.len() {
-        let game_state: GameState = play_game(&bots);
-        games[i] = game_state;
+    let team_0_wins: usize = games.iter().filter(|&g| g == &GameState::Team0Won).count();
+    let team_1_wins: usize = games.iter().filter(|&g| g == &GameState::Team1Won).count();
+    println!("Team {} won: {}", bots[0].name(), team_0_wins);
+    println!("Team {} won: {}", bots[1].name(), team_1_wins);
+
+    let win_rate: WinRate = WinRate::new(team_0_wins, team_0_wins + team_1_wins);
+    let (low, high): (f64, f64) = win_rate.confidence_interval(WinRate::Z_95);
+    println!("Team {} win rate: {:.1}% (95% CI {:.1}%-{:.1}%), p-value vs 50%: {:.4}",
+             bots[0].name(), win_rate.rate() * 100.0, low * 100.0, high * 100.0, win_rate.p_value());
+    if options.clock.is_some() {
+        println!("Time used: Team {} {:.2} s, Team {} {:.2} s, {} timeouts",
+                 bots[0].name(), time_used[0].as_secs_f64(), bots[1].name(), time_used[1].as_secs_f64(), timeouts);
+    }
+    for (seat, count) in faults.iter().enumerate().filter(|&(_, &count)| count > 0) {
+        println!("Faulty bot at seat {} ({}): {} faults", seat, bots[seat].name(), count);
     }
 
-    println!("Team {} won: {}", bots[0].name(), games.iter().filter(|&g| g == &GameState::Team0Won).count());
-    println!("Team {} won: {}", bots[1].name(), games.iter().filter(|&g| g == &GameState::Team1Won).count());
+    if let Some(path) = models_path {
+        if let Err(error) = models.borrow().save(path) {
+            println!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    }
 
     // Player driver
     /* loop {
@@ -94,35 +170,91 @@ fn main() {
             }
         }
     } */
+
+    ExitCode::SUCCESS
 }
 
-fn play_game(bots: &Vec<Box<dyn Behaviour>>) -> GameState {
-    let starting_player: usize = thread_rng().gen_range(0..=3);
-    let mut board: Board = Board::new(starting_player);
-
-    loop {
-        match board.current_player {
-            0..=3 => match bots[board.current_player].play_card(&mut board) {
-                Ok(game_state) => match game_state {
-                    GameState::BazaEnded => post_baza_actions(&bots, &mut board),
-                    GameState::Continuation => (),
-                    GameState::Team0Won | GameState::Team1Won => {
-                        return game_state;
-                    },
-                    _ => ()
-                },
-                Err(error) => println!("Error: {}", error)
-            }
-            _ => {
-                println!("Wrong player");
-                return GameState::None;
-            }
-        }
+fn create_bot(name: &str, models: &SharedModels) -> Result<Box<dyn Behaviour>, String> {
+    // Pesos d'un fitxer, p. ex. weighted:personalities/aggressive.weights
+    if let Some(path) = name.strip_prefix("weighted:") {
+        return Ok(Box::new(WeightedBot::new(Weights::load(path)?)));
+    }
+    if let Some(path) = name.strip_prefix("neural:") {
+        return Ok(Box::new(NeuralBot::new(Network::load(path)?, NeuralConfig::default())));
+    }
+    // Vot per majoria entre els bots, p. ex. ensemble:expert+pimc+smart. Els bots que no es
+    // poden consultar sense jugar (cfr, epsilon, softmax) no hi voten
+    if let Some(names) = name.strip_prefix("ensemble:") {
+        let members: Vec<Member> = names.split('+').map(|name| create_bot(name, models).map(Member::new)).collect::<Result<_, String>>()?;
+        return Ok(Box::new(EnsembleBot::new(EnsembleConfig { members, rule: EnsembleRule::Majority })?));
+    }
+    // Bots amb atzar, p. ex. epsilon:0.2:expert o softmax:0.5:weighted
+    if let Some(rest) = name.strip_prefix("epsilon:") {
+        let (epsilon, inner): (f64, &str) = parse_stochastic(rest)?;
+        return Ok(Box::new(EpsilonGreedyBot::new(create_bot(inner, models)?, EpsilonGreedyConfig { epsilon, seed: None })));
+    }
+    if let Some(rest) = name.strip_prefix("softmax:") {
+        let (temperature, inner): (f64, &str) = parse_stochastic(rest)?;
+        return Ok(Box::new(SoftmaxBot::new(create_bot(inner, models)?, SoftmaxConfig { temperature, seed: None })));
     }
+
+    match name {
+        "random" => Ok(Box::new(RandomBot {})),
+        "smart" => Ok(Box::new(SmartBot {})),
+        "expert" => Ok(Box::new(ExpertBot::new(ExpertConfig::default()))),
+        "weighted" => Ok(Box::new(WeightedBot::new(Weights::default()))),
+        "ismcts" => Ok(Box::new(IsmctsBot::new(IsmctsConfig::default()))),
+        "pimc" => Ok(Box::new(PimcBot::new(PimcConfig::default()))),
+        "cfr" => Ok(Box::new(CfrBot::new(CfrBotConfig::default()))),
+        "adaptive" => Ok(Box::new(AdaptiveBot::new(AdaptiveConfig::default(), models.clone()))),
+        _ => Err(format!("Unknown bot '{}'. Available bots: {}", name, BOT_NAMES))
+    }
+}
+
+// "<paràmetre>:<bot>"
+fn parse_stochastic(text: &str) -> Result<(f64, &str), String> {
+    let (parameter, inner): (&str, &str) = text.split_once(':').ok_or(format!("Expected <parameter>:<bot>, got '{}'", text))?;
+    let parameter: f64 = parameter.parse::<f64>().map_err(|_| format!("Invalid parameter '{}'", parameter))?;
+
+    Ok((parameter, inner))
 }
 
-fn post_baza_actions(bots: &Vec<Box<dyn Behaviour>>, board: &mut Board) {
-    for (player, bot) in bots.iter().enumerate() {
-        bot.post_baza_actions(board, player);
+// --clock=<segons>+<increment>, --timeout=forfeit|random i --faults=substitute|forfeit
+fn parse_flags(flags: &[String]) -> Result<DriverOptions, String> {
+    let mut clock: Option<ClockConfig> = None;
+    let mut policy: TimeoutPolicy = ClockConfig::default().policy;
+    let mut faults: FaultConfig = FaultConfig::default();
+
+    for flag in flags {
+        let (name, value): (&str, &str) = flag.split_once('=').ok_or(format!("Expected --<option>=<value>, got '{}'", flag))?;
+        match name {
+            "--clock" => {
+                let (initial, increment): (&str, &str) = value.split_once('+').unwrap_or((value, "0"));
+                let seconds = |text: &str| text.parse::<f64>().ok().and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                                                .ok_or(format!("Invalid time '{}'", text));
+                clock = Some(ClockConfig { initial: seconds(initial)?, increment: seconds(increment)?, ..ClockConfig::default() });
+            },
+            "--timeout" => {
+                policy = match value {
+                    "forfeit" => TimeoutPolicy::Forfeit,
+                    "random" => TimeoutPolicy::RandomMove,
+                    _ => return Err(format!("Unknown timeout policy '{}'", value))
+                };
+            },
+            "--faults" => {
+                faults.policy = match value {
+                    "substitute" => FaultPolicy::SubstituteMove,
+                    "forfeit" => FaultPolicy::Forfeit,
+                    _ => return Err(format!("Unknown fault policy '{}'", value))
+                };
+            },
+            _ => return Err(format!("Unknown option '{}'", name))
+        }
     }
-}
\ No newline at end of file
+
+    Ok(DriverOptions {
+        clock: clock.map(|clock| ClockConfig { policy, ..clock }),
+        faults,
+        ..DriverOptions::default()
+    })
+}
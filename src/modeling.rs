@@ -0,0 +1,222 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::rc::Rc;
+use crate::card_set::CardSet;
+use crate::game::{Board, Card, Pal, Rank};
+
+// Models dels jugadors al llarg d'una sessió. Per a cada jugador (identificat pel nom) es compta,
+// de les vegades que ha tingut l'ocasió de fer una cosa, quantes l'ha fet. Els bots embolcallats
+// amb ObservedBot alimenten els models, i qualsevol bot amb accés al ModelStore pot consultar
+// les tendències del jugador que seu a cada seient.
+//
+// Format de fitxer: una línia "<jugador> <tendència> <vegades> <ocasions>" per comptador,
+// amb # per comentaris.
+
+pub const TENDENCY_COUNT: usize = 6;
+
+pub const TENDENCIES: [&str; TENDENCY_COUNT] = [
+    // Surt de trumfo tenint-ne i podent sortir d'un altre pal
+    "lead_trumfo",
+    // No juga el 7 de trumfo mentre encara es pot canviar
+    "save_seven",
+    // Carrega (carta de 10 o més) quan el company va guanyant la baza
+    "load_partner",
+    // Guanya la baza dels rivals quan pot
+    "overtake",
+    // Canta quan pot
+    "cante",
+    // Canvia el 7 pel trumfo quan pot
+    "exchange"
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tendency {
+    LeadTrumfo,
+    SaveSeven,
+    LoadPartner,
+    Overtake,
+    Cante,
+    Exchange
+}
+
+// Mentre hi ha poques ocasions la taxa es queda a prop del 50%
+const PRIOR_RATE: f64 = 0.5;
+const PRIOR_WEIGHT: f64 = 4.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct Counter {
+    pub hits: u32,
+    pub opportunities: u32
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct PlayerModel {
+    pub counters: [Counter; TENDENCY_COUNT]
+}
+
+impl PlayerModel {
+    // Taxa suavitzada cap al 50%
+    pub fn rate(&self, tendency: Tendency) -> f64 {
+        let counter: Counter = self.counters[tendency as usize];
+        (counter.hits as f64 + PRIOR_RATE * PRIOR_WEIGHT) / (counter.opportunities as f64 + PRIOR_WEIGHT)
+    }
+
+    pub fn get_counter(&self, tendency: Tendency) -> Counter {
+        self.counters[tendency as usize]
+    }
+
+    fn record(&mut self, tendency: Tendency, hit: bool) {
+        let counter: &mut Counter = &mut self.counters[tendency as usize];
+        counter.opportunities += 1;
+        if hit {
+            counter.hits += 1;
+        }
+    }
+}
+
+pub type SharedModels = Rc<RefCell<ModelStore>>;
+
+#[derive(Clone, Default, Debug)]
+pub struct ModelStore {
+    models: HashMap<String, PlayerModel>,
+    // Qui seu a cada seient a la partida en curs, segons les últimes observacions
+    seats: [Option<String>; 4]
+}
+
+impl ModelStore {
+    pub fn new() -> Self {
+        ModelStore::default()
+    }
+
+    pub fn shared(self) -> SharedModels {
+        Rc::new(RefCell::new(self))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut store: ModelStore = ModelStore::new();
+
+        for (line_number, line) in text.lines().enumerate() {
+            let line: &str = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let [player, tendency, hits, opportunities] = tokens[..] else {
+                return Err(format!("Línia {}: s'esperava '<jugador> <tendència> <vegades> <ocasions>'.", line_number + 1));
+            };
+            let tendency: usize = TENDENCIES.iter().position(|&name| name == tendency)
+                                            .ok_or(format!("Línia {}: tendència desconeguda '{}'.", line_number + 1, tendency))?;
+            let counter: Counter = match (hits.parse::<u32>(), opportunities.parse::<u32>()) {
+                (Ok(hits), Ok(opportunities)) if hits <= opportunities => Counter { hits, opportunities },
+                _ => return Err(format!("Línia {}: comptadors invàlids.", line_number + 1))
+            };
+
+            store.models.entry(player.to_string()).or_default().counters[tendency] = counter;
+        }
+
+        Ok(store)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text: String = fs::read_to_string(path).map_err(|error| format!("No s'ha pogut llegir {}: {}", path, error))?;
+        ModelStore::parse(&text).map_err(|error| format!("{}: {}", path, error))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_string()).map_err(|error| format!("No s'ha pogut escriure {}: {}", path, error))
+    }
+
+    // Model d'un jugador; si no se n'ha vist cap jugada, el de per defecte
+    pub fn get(&self, player: &str) -> PlayerModel {
+        self.models.get(player).copied().unwrap_or_default()
+    }
+
+    pub fn at_seat(&self, seat: usize) -> PlayerModel {
+        self.seats[seat].as_ref().map(|player| self.get(player)).unwrap_or_default()
+    }
+
+    pub fn get_seat_player(&self, seat: usize) -> Option<&str> {
+        self.seats[seat].as_deref()
+    }
+
+    pub fn players(&self) -> Vec<&str> {
+        let mut players: Vec<&str> = self.models.keys().map(String::as_str).collect();
+        players.sort();
+        players
+    }
+
+    // `board` és la posició d'abans de jugar `card`
+    pub fn observe_play(&mut self, player: &str, board: &Board, card: Card) {
+        let seat: usize = board.current_player;
+        self.sit(player, seat);
+
+        let hand: CardSet = board.get_hand(seat).cards;
+        let legal_cards: CardSet = board.get_legal_cards();
+        let trumfo: Pal = board.current_trumfo.pal;
+        let baza: [Option<Card>; 4] = board.get_current_baza();
+        let model: &mut PlayerModel = self.models.entry(player.to_string()).or_default();
+
+        if Board::is_baza_empty(baza) {
+            let trumfos: CardSet = legal_cards & CardSet::suit(trumfo);
+            if !trumfos.is_empty() && trumfos != legal_cards {
+                model.record(Tendency::LeadTrumfo, card.pal == trumfo);
+            }
+        } else {
+            let winner: usize = Board::determine_baza_winner(Board::baza_starting_player(seat, baza), trumfo, baza);
+            let winning_card: Card = baza[winner].unwrap();     // Can't panic, the baza is not empty
+
+            if winner == (seat + 2) % 4 {
+                let loads: CardSet = legal_cards.iter().filter(|card| card.pal != trumfo && card.value() >= 10).collect();
+                if !loads.is_empty() && loads != legal_cards {
+                    model.record(Tendency::LoadPartner, loads.contains(card));
+                }
+            } else {
+                let winning_cards: CardSet = legal_cards & CardSet::better_than(winning_card, trumfo);
+                if !winning_cards.is_empty() && winning_cards != legal_cards {
+                    model.record(Tendency::Overtake, winning_cards.contains(card));
+                }
+            }
+        }
+
+        let seven: Card = Card { pal: trumfo, rank: Rank::Siete };
+        if !board.is_arrastre() && hand.contains(seven) && legal_cards.len() > 1 {
+            model.record(Tendency::SaveSeven, card != seven);
+        }
+    }
+
+    // `available` són els cantes que podia fer i `could_exchange` si podia canviar el 7; `board`
+    // és la posició de després de decidir
+    pub fn observe_post_baza(&mut self, player: &str, seat: usize, board: &Board, available: &[Pal], could_exchange: bool) {
+        self.sit(player, seat);
+        let model: &mut PlayerModel = self.models.entry(player.to_string()).or_default();
+
+        for pal in available {
+            model.record(Tendency::Cante, board.get_cantes().contains(pal));
+        }
+        if could_exchange {
+            model.record(Tendency::Exchange, !board.can_change_trumfo(seat));
+        }
+    }
+
+    fn sit(&mut self, player: &str, seat: usize) {
+        if self.seats[seat].as_deref() != Some(player) {
+            self.seats[seat] = Some(player.to_string());
+        }
+    }
+}
+
+impl fmt::Display for ModelStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# <jugador> <tendència> <vegades> <ocasions>")?;
+        for player in self.players() {
+            for (name, counter) in TENDENCIES.iter().zip(self.models[player].counters.iter()) {
+                writeln!(f, "{} {} {} {}", player, name, counter.hits, counter.opportunities)?;
+            }
+        }
+
+        Ok(())
+    }
+}
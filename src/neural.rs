@@ -0,0 +1,242 @@
+use std::fmt;
+use std::fs;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::encoding::{OBSERVATION_SIZE, SCHEMA_VERSION};
+use crate::environment::ACTION_COUNT;
+
+// Xarxa neuronal feed-forward per avaluar observacions codificades (veure encoding.rs) a la CPU.
+// Un tronc de capes denses alimenta dos caps: la política (un logit per carta) i el valor
+// (resultat esperat de la partida per a l'equip de l'observador, entre -1 i 1).
+//
+// Format de fitxer de text, separat per espais i amb # per comentaris:
+//   guinote-net 1              versió del format
+//   schema 1                   SCHEMA_VERSION de les observacions
+//   dense <in> <out> <act>     capa del tronc (act: relu, tanh o linear), seguida de
+//                              <out> files de <in> pesos i <out> biaixos
+//   ...
+//   policy <in> 40 <act>       cap de política, amb els pesos igual que una capa
+//   value <in> 1 <act>         cap de valor
+// Els valors es llegeixen en ordre, així que el salt de línia no importa.
+
+pub const NETWORK_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Activation {
+    Relu,
+    Tanh,
+    Linear
+}
+
+impl Activation {
+    fn apply(&self, value: f32) -> f32 {
+        match self {
+            Activation::Relu => value.max(0.0),
+            Activation::Tanh => value.tanh(),
+            Activation::Linear => value
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Dense {
+    pub inputs: usize,
+    pub outputs: usize,
+    // Fila per sortida: weights[output * inputs + input]
+    pub weights: Vec<f32>,
+    pub biases: Vec<f32>,
+    pub activation: Activation
+}
+
+impl Dense {
+    // Inicialització de He/Glorot uniforme
+    pub fn random<R: Rng>(inputs: usize, outputs: usize, activation: Activation, rng: &mut R) -> Self {
+        let limit: f32 = match activation {
+            Activation::Relu => (6.0 / inputs as f32).sqrt(),
+            _ => (6.0 / (inputs + outputs) as f32).sqrt()
+        };
+
+        Dense {
+            inputs,
+            outputs,
+            weights: (0..inputs * outputs).map(|_| rng.gen_range(-limit..=limit)).collect(),
+            biases: vec![0.0; outputs],
+            activation
+        }
+    }
+
+    pub fn forward(&self, input: &[f32]) -> Vec<f32> {
+        self.weights.chunks_exact(self.inputs)
+                    .zip(self.biases.iter())
+                    .map(|(row, bias)| {
+                        let sum: f32 = row.iter().zip(input.iter()).map(|(weight, value)| weight * value).sum();
+                        self.activation.apply(sum + bias)
+                    })
+                    .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Network {
+    trunk: Vec<Dense>,
+    policy: Dense,
+    value: Dense
+}
+
+impl Network {
+    pub fn new(trunk: Vec<Dense>, policy: Dense, value: Dense) -> Result<Self, String> {
+        let mut inputs: usize = OBSERVATION_SIZE;
+        for (index, layer) in trunk.iter().enumerate() {
+            Network::check_layer(layer, inputs, layer.outputs, &format!("dense {}", index))?;
+            inputs = layer.outputs;
+        }
+        Network::check_layer(&policy, inputs, ACTION_COUNT, "policy")?;
+        Network::check_layer(&value, inputs, 1, "value")?;
+
+        Ok(Network { trunk, policy, value })
+    }
+
+    // Xarxa amb pesos aleatoris i capes ocultes de mida `hidden`
+    pub fn random(hidden: &[usize], seed: u64) -> Self {
+        let mut rng: StdRng = StdRng::seed_from_u64(seed);
+        let mut inputs: usize = OBSERVATION_SIZE;
+        let mut trunk: Vec<Dense> = Vec::new();
+        for &outputs in hidden {
+            trunk.push(Dense::random(inputs, outputs, Activation::Relu, &mut rng));
+            inputs = outputs;
+        }
+        let policy: Dense = Dense::random(inputs, ACTION_COUNT, Activation::Linear, &mut rng);
+        let value: Dense = Dense::random(inputs, 1, Activation::Tanh, &mut rng);
+
+        Network { trunk, policy, value }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut tokens = text.lines()
+                             .flat_map(|line| line.split('#').next().unwrap_or("").split_whitespace());
+
+        match (tokens.next(), tokens.next().map(str::parse::<u32>)) {
+            (Some("guinote-net"), Some(Ok(NETWORK_VERSION))) => (),
+            (Some("guinote-net"), Some(Ok(version))) => return Err(format!("Versió de xarxa no suportada: {}", version)),
+            _ => return Err("No és un fitxer de xarxa (falta 'guinote-net <versió>').".to_string())
+        }
+        match (tokens.next(), tokens.next().map(str::parse::<u32>)) {
+            (Some("schema"), Some(Ok(SCHEMA_VERSION))) => (),
+            (Some("schema"), Some(Ok(version))) => return Err(format!("La xarxa espera l'esquema {} i el codificador és el {}", version, SCHEMA_VERSION)),
+            _ => return Err("Falta 'schema <versió>'.".to_string())
+        }
+
+        let mut trunk: Vec<Dense> = Vec::new();
+        let mut policy: Option<Dense> = None;
+        let mut value: Option<Dense> = None;
+
+        while let Some(kind) = tokens.next() {
+            let layer: Dense = Network::parse_layer(kind, &mut tokens)?;
+            match kind {
+                "dense" if policy.is_none() && value.is_none() => trunk.push(layer),
+                "policy" if policy.is_none() => policy = Some(layer),
+                "value" if value.is_none() => value = Some(layer),
+                _ => return Err(format!("Capa '{}' inesperada", kind))
+            }
+        }
+
+        Network::new(trunk, policy.ok_or("Falta el cap de política.")?, value.ok_or("Falta el cap de valor.")?)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text: String = fs::read_to_string(path).map_err(|error| format!("No s'ha pogut llegir {}: {}", path, error))?;
+        Network::parse(&text).map_err(|error| format!("{}: {}", path, error))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_string()).map_err(|error| format!("No s'ha pogut escriure {}: {}", path, error))
+    }
+
+    // Logits de política (un per Card::index) i valor
+    pub fn evaluate(&self, observation: &[f32; OBSERVATION_SIZE]) -> ([f32; ACTION_COUNT], f32) {
+        let features: Vec<f32> = self.features(observation);
+        let mut logits: [f32; ACTION_COUNT] = [0.0; ACTION_COUNT];
+        logits.copy_from_slice(&self.policy.forward(&features));
+
+        (logits, self.value.forward(&features)[0])
+    }
+
+    pub fn value(&self, observation: &[f32; OBSERVATION_SIZE]) -> f32 {
+        self.value.forward(&self.features(observation))[0]
+    }
+
+    fn features(&self, observation: &[f32; OBSERVATION_SIZE]) -> Vec<f32> {
+        self.trunk.iter().fold(observation.to_vec(), |input, layer| layer.forward(&input))
+    }
+
+    fn parse_layer<'a, I: Iterator<Item = &'a str>>(kind: &str, tokens: &mut I) -> Result<Dense, String> {
+        let mut next_size = |name: &str| -> Result<usize, String> {
+            tokens.next()
+                  .and_then(|token| token.parse::<usize>().ok())
+                  .ok_or(format!("{}: falta {}", kind, name))
+        };
+        let inputs: usize = next_size("el nombre d'entrades")?;
+        let outputs: usize = next_size("el nombre de sortides")?;
+
+        let activation: Activation = match tokens.next() {
+            Some("relu") => Activation::Relu,
+            Some("tanh") => Activation::Tanh,
+            Some("linear") => Activation::Linear,
+            other => return Err(format!("{}: activació invàlida {:?}", kind, other))
+        };
+
+        let count: usize = inputs.checked_add(1).and_then(|row| row.checked_mul(outputs)).ok_or(format!("{}: massa pesos", kind))?;
+        let mut values: Vec<f32> = Vec::new();
+        for _ in 0..count {
+            match tokens.next().map(str::parse::<f32>) {
+                Some(Ok(value)) if value.is_finite() => values.push(value),
+                Some(_) => return Err(format!("{}: pes invàlid", kind)),
+                None => return Err(format!("{}: falten pesos", kind))
+            }
+        }
+        let biases: Vec<f32> = values.split_off(inputs * outputs);
+
+        Ok(Dense { inputs, outputs, weights: values, biases, activation })
+    }
+
+    fn check_layer(layer: &Dense, inputs: usize, outputs: usize, name: &str) -> Result<(), String> {
+        if layer.inputs != inputs || layer.outputs != outputs || outputs == 0 {
+            return Err(format!("{}: és {}x{} i s'esperava {}x{}", name, layer.inputs, layer.outputs, inputs, outputs));
+        }
+        if layer.weights.len() != inputs * outputs || layer.biases.len() != outputs {
+            return Err(format!("{}: mida de pesos incorrecta", name));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Activation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Activation::Relu => write!(f, "relu"),
+            Activation::Tanh => write!(f, "tanh"),
+            Activation::Linear => write!(f, "linear")
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "guinote-net {}", NETWORK_VERSION)?;
+        writeln!(f, "schema {}", SCHEMA_VERSION)?;
+
+        let trunk = self.trunk.iter().map(|layer| ("dense", layer));
+        for (kind, layer) in trunk.chain([("policy", &self.policy), ("value", &self.value)]) {
+            writeln!(f, "{} {} {} {}", kind, layer.inputs, layer.outputs, layer.activation)?;
+            for row in layer.weights.chunks_exact(layer.inputs) {
+                let values: Vec<String> = row.iter().map(f32::to_string).collect();
+                writeln!(f, "{}", values.join(" "))?;
+            }
+            let biases: Vec<String> = layer.biases.iter().map(f32::to_string).collect();
+            writeln!(f, "{}", biases.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use crate::card_set::CardSet;
+use crate::game::{Board, Card, Pal, Rank};
+
+// Senyes entre companys. Abans de cada baza, començant pel jugador que surt, cada seient pot fer
+// una seña del vocabulari fix al seu company. El company la veu sempre i cada rival, de manera
+// independent, amb probabilitat `visibility`. El driver les passa als bots i les apunta al GameLog.
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Signal {
+    // Té l'as de trumfo
+    AsTrumfo,
+    // Té el 3 de trumfo
+    TresTrumfo,
+    // Pot cantar les 40
+    Quaranta,
+    // Pot cantar 20 en algun pal
+    Vint,
+    // No té cap trumfo
+    SenseTrumfo,
+    // Res a dir
+    Res
+}
+
+impl Signal {
+    // En ordre de prioritat per a `honest`
+    pub const ALL: [Signal; 6] = [Signal::AsTrumfo, Signal::TresTrumfo, Signal::Quaranta, Signal::Vint, Signal::SenseTrumfo, Signal::Res];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Signal::AsTrumfo => "as_trumfo",
+            Signal::TresTrumfo => "tres_trumfo",
+            Signal::Quaranta => "quaranta",
+            Signal::Vint => "vint",
+            Signal::SenseTrumfo => "sense_trumfo",
+            Signal::Res => "res"
+        }
+    }
+
+    // Si la seña és certa per a la mà de `player`
+    pub fn holds(&self, board: &Board, player: usize) -> bool {
+        let hand: CardSet = board.get_hand(player).cards;
+        let trumfo: Pal = board.current_trumfo.pal;
+        let pair = |pal: Pal| !board.get_cantes().contains(&pal)
+                              && hand.contains(Card { pal, rank: Rank::Sota })
+                              && hand.contains(Card { pal, rank: Rank::Rey });
+
+        match self {
+            Signal::AsTrumfo => hand.contains(Card { pal: trumfo, rank: Rank::As }),
+            Signal::TresTrumfo => hand.contains(Card { pal: trumfo, rank: Rank::Tres }),
+            Signal::Quaranta => pair(trumfo),
+            Signal::Vint => Pal::iter().any(|pal| pal != trumfo && pair(pal)),
+            Signal::SenseTrumfo => (hand & CardSet::suit(trumfo)).is_empty(),
+            Signal::Res => true
+        }
+    }
+
+    // La seña certa més important de la mà de `player`
+    pub fn honest(board: &Board, player: usize) -> Signal {
+        Signal::ALL.into_iter().find(|signal| signal.holds(board, player)).unwrap()     // Can't fail, Res always holds
+    }
+}
+
+impl FromStr for Signal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Signal::ALL.into_iter().find(|signal| signal.name() == s).ok_or(format!("Seña desconeguda '{}'", s))
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SignalConfig {
+    // Probabilitat que cada rival vegi una seña
+    pub visibility: f64
+}
+
+impl Default for SignalConfig {
+    fn default() -> Self {
+        SignalConfig {
+            visibility: 0.25
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// Estadístiques per comparar bots a partir de partides simulades
+
+#[derive(Clone, Copy, Debug)]
+pub struct WinRate {
+    pub wins: usize,
+    pub games: usize
+}
+
+impl WinRate {
+    // z per a un interval de confiança del 95%
+    pub const Z_95: f64 = 1.959964;
+
+    pub fn new(wins: usize, games: usize) -> Self {
+        WinRate { wins, games }
+    }
+
+    pub fn rate(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+
+        self.wins as f64 / self.games as f64
+    }
+
+    // Interval de Wilson, que es comporta bé amb poques partides o taxes extremes
+    pub fn confidence_interval(&self, z: f64) -> (f64, f64) {
+        if self.games == 0 {
+            return (0.0, 1.0);
+        }
+
+        let n: f64 = self.games as f64;
+        let p: f64 = self.rate();
+        let denominator: f64 = 1.0 + z * z / n;
+        let center: f64 = (p + z * z / (2.0 * n)) / denominator;
+        let margin: f64 = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt() / denominator;
+
+        ((center - margin).max(0.0), (center + margin).min(1.0))
+    }
+
+    // p-valor bilateral de la hipòtesi nul·la "els dos equips guanyen el 50% de les partides"
+    // (aproximació normal de la binomial)
+    pub fn p_value(&self) -> f64 {
+        if self.games == 0 {
+            return 1.0;
+        }
+
+        let n: f64 = self.games as f64;
+        let z: f64 = (self.wins as f64 - n / 2.0) / (n / 4.0).sqrt();
+
+        erfc(z.abs() / std::f64::consts::SQRT_2)
+    }
+}
+
+// Funció d'error complementària (Numerical Recipes, error relatiu < 1.2e-7)
+fn erfc(x: f64) -> f64 {
+    let z: f64 = x.abs();
+    let t: f64 = 1.0 / (1.0 + 0.5 * z);
+    let polynomial: f64 = -z * z - 1.26551223 + t * (1.00002368 + t * (0.37409196 + t * (0.09678418
+                        + t * (-0.18628806 + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587
+                        + t * (-0.82215223 + t * 0.17087277))))))));
+    let result: f64 = t * polynomial.exp();
+
+    if x >= 0.0 { result } else { 2.0 - result }
+}
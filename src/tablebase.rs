@@ -0,0 +1,421 @@
+use std::collections::HashMap;
+use std::fs;
+use std::iter;
+use rand::Rng;
+use rand::seq::index::sample;
+use crate::card_set::CardSet;
+use crate::endgame_solver::{EndgameSolver, Position};
+use crate::game::{Board, Card, Pal};
+
+// Taula de finals de l'arrastre per a un pal de trumfo. Guarda, en un byte, els punts de carta
+// que l'equip 0 s'emporta fins al final de la ronda (10 d'últimes incloses) si tothom juga
+// veient totes les cartes, per a posicions a l'inici d'una baza.
+//
+// La taula està dividida en blocs, un per cada conjunt de cartes que queden a les mans (les
+// jugades ja són públiques). Un bloc té una entrada per a cada jugador que surt i cada manera de
+// repartir aquestes cartes entre els quatre seients, i l'índex és el jugador que surt i, per a
+// cada seient en ordre, el rang colexicogràfic de la seva mà entre les cartes que queden i no
+// tenen els seients anteriors. Així la consulta és un accés al HashMap i un càlcul, sense cerca.
+// Les posicions de dins d'una baza es resolen jugant la baza fins al final i consultant la taula.
+//
+// Un bloc amb 2 cartes per jugador té 10.080 entrades i amb 3 en té 1.478.400. Generar un bloc
+// genera també els de les bazas següents, jugant cada baza i consultant el bloc de les cartes
+// que queden després.
+//
+// Amb 1 carta per jugador es poden generar tots els blocs (91.390, de 96 entrades). Amb més n'hi
+// ha massa (C(40, 8) = 76.904.685 amb 2) i la taula és parcial: només té els blocs triats a
+// l'atzar i els de les seves bazas següents, i probe retorna None per a la resta de posicions.
+//
+// Format (little endian): "GTBL", TABLEBASE_VERSION u32, pal del trumfo u8, cartes per jugador u8,
+// 2 bytes reservats, blocs u64 i després, per a cada bloc, les cartes que queden u64 i un byte
+// per entrada en ordre d'índex.
+
+pub const TABLEBASE_VERSION: u32 = 2;
+pub const MAX_BLOCK_ENTRIES: u64 = 1 << 24;
+pub const MAX_ENUMERATED_BLOCKS: u64 = 1 << 17;
+
+const MAGIC: &[u8; 4] = b"GTBL";
+const HEADER_SIZE: usize = 20;
+
+pub struct Tablebase {
+    trumfo: Pal,
+    cards_per_player: usize,
+    blocks: HashMap<CardSet, Vec<u8>>
+}
+
+impl Tablebase {
+    // Nombre d'entrades d'un bloc, o None si passa de MAX_BLOCK_ENTRIES
+    pub fn block_entries(cards_per_player: usize) -> Option<u64> {
+        if cards_per_player == 0 || cards_per_player > 10 {
+            return None;
+        }
+
+        let per_leader: u64 = (0..4).try_fold(1u64, |entries, seat| entries.checked_mul(binomial((4 - seat) * cards_per_player, cards_per_player)))?;
+        per_leader.checked_mul(4).filter(|&entries| entries <= MAX_BLOCK_ENTRIES)
+    }
+
+    // Taula buida: els blocs s'hi afegeixen amb generate_block
+    pub fn new(trumfo: Pal, cards_per_player: usize) -> Result<Self, String> {
+        Tablebase::block_entries(cards_per_player)
+                  .ok_or(format!("Un bloc amb {} cartes per jugador passa del límit de {} entrades.", cards_per_player, MAX_BLOCK_ENTRIES))?;
+
+        Ok(Tablebase {
+            trumfo,
+            cards_per_player,
+            blocks: HashMap::new()
+        })
+    }
+
+    // Taula completa: tots els blocs de `cards_per_player` cartes, si no passen de
+    // MAX_ENUMERATED_BLOCKS
+    pub fn generate_all(trumfo: Pal, cards_per_player: usize) -> Result<Self, String> {
+        let mut tablebase: Tablebase = Tablebase::new(trumfo, cards_per_player)?;
+        let blocks: u64 = binomial(40, 4 * cards_per_player);
+        if blocks > MAX_ENUMERATED_BLOCKS {
+            return Err(format!("Amb {} cartes per jugador hi ha {} blocs, més del límit de {}.", cards_per_player, blocks, MAX_ENUMERATED_BLOCKS));
+        }
+
+        for remaining in card_sets(4 * cards_per_player) {
+            tablebase.generate_block(remaining)?;
+        }
+
+        Ok(tablebase)
+    }
+
+    // Taula parcial amb `blocks` conjunts de cartes a l'atzar. Amb el mateix `rng` dona la mateixa
+    // taula
+    pub fn generate<R: Rng>(trumfo: Pal, cards_per_player: usize, blocks: usize, rng: &mut R) -> Result<Self, String> {
+        let mut tablebase: Tablebase = Tablebase::new(trumfo, cards_per_player)?;
+        let cards: Vec<Card> = CardSet::FULL.iter().collect();
+
+        for _ in 0..blocks {
+            let remaining: CardSet = sample(rng, cards.len(), 4 * cards_per_player).into_iter().map(|index| cards[index]).collect();
+            tablebase.generate_block(remaining)?;
+        }
+
+        Ok(tablebase)
+    }
+
+    // Genera el bloc de les cartes `remaining` (i els de les bazas següents) si encara no hi és
+    pub fn generate_block(&mut self, remaining: CardSet) -> Result<(), String> {
+        let cards_per_player: usize = remaining.len() / 4;
+        if 4 * cards_per_player != remaining.len() || cards_per_player == 0 || cards_per_player > self.cards_per_player {
+            return Err(format!("La taula no pot tenir un bloc de {} cartes.", remaining.len()));
+        }
+
+        self.ensure_block(remaining);
+        Ok(())
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let bytes: Vec<u8> = fs::read(path).map_err(|error| format!("No s'ha pogut llegir {}: {}", path, error))?;
+        if bytes.len() < HEADER_SIZE || &bytes[0..4] != MAGIC {
+            return Err(format!("{}: no és una taula de finals", path));
+        }
+
+        let version: u32 = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if version != TABLEBASE_VERSION {
+            return Err(format!("{}: versió no suportada {}", path, version));
+        }
+        let trumfo: Pal = match bytes[8] {
+            0 => Pal::Orus,
+            1 => Pal::Copes,
+            2 => Pal::Espases,
+            3 => Pal::Bastos,
+            pal => return Err(format!("{}: pal invàlid {}", path, pal))
+        };
+        let mut tablebase: Tablebase = Tablebase::new(trumfo, bytes[9] as usize).map_err(|error| format!("{}: {}", path, error))?;
+        let blocks: u64 = u64::from_le_bytes(bytes[12..20].try_into().unwrap());
+
+        let mut offset: usize = HEADER_SIZE;
+        for _ in 0..blocks {
+            let remaining: CardSet = match bytes.get(offset..offset + 8) {
+                Some(key) => CardSet::from_bits(u64::from_le_bytes(key.try_into().unwrap())),
+                None => return Err(format!("{}: mida incorrecta", path))
+            };
+            let cards_per_player: usize = remaining.len() / 4;
+            if remaining.bits() & !CardSet::FULL.bits() != 0 || 4 * cards_per_player != remaining.len() || cards_per_player == 0 || cards_per_player > tablebase.cards_per_player {
+                return Err(format!("{}: bloc invàlid", path));
+            }
+
+            let entries: usize = Tablebase::block_entries(cards_per_player).unwrap() as usize;     // Can't fail, it's smaller than the table's blocks
+            let values: &[u8] = bytes.get(offset + 8..offset + 8 + entries).ok_or(format!("{}: mida incorrecta", path))?;
+            tablebase.blocks.insert(remaining, values.to_vec());
+            offset += 8 + entries;
+        }
+        if offset != bytes.len() {
+            return Err(format!("{}: mida incorrecta", path));
+        }
+
+        Ok(tablebase)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(HEADER_SIZE + self.blocks.len() * 8 + self.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&TABLEBASE_VERSION.to_le_bytes());
+        bytes.push(self.trumfo as u8);
+        bytes.push(self.cards_per_player as u8);
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(&(self.blocks.len() as u64).to_le_bytes());
+
+        // En ordre perquè la mateixa taula doni sempre el mateix fitxer
+        let mut keys: Vec<CardSet> = self.blocks.keys().copied().collect();
+        keys.sort_by_key(CardSet::bits);
+        for remaining in keys {
+            bytes.extend_from_slice(&remaining.bits().to_le_bytes());
+            bytes.extend_from_slice(&self.blocks[&remaining]);
+        }
+
+        fs::write(path, bytes).map_err(|error| format!("No s'ha pogut escriure {}: {}", path, error))
+    }
+
+    pub fn get_trumfo(&self) -> Pal {
+        self.trumfo
+    }
+
+    pub fn get_cards_per_player(&self) -> usize {
+        self.cards_per_player
+    }
+
+    pub fn get_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn contains_block(&self, remaining: CardSet) -> bool {
+        self.blocks.contains_key(&remaining)
+    }
+
+    // Entrades de tots els blocs
+    pub fn len(&self) -> usize {
+        self.blocks.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    // Punts de carta de cada equip d'aquí al final de la ronda, o None si la posició no és de
+    // la taula: ha de ser arrastre, amb el mateix trumfo i amb els blocs de les bazas que queden
+    pub fn probe(&self, board: &Board) -> Option<[u32; 2]> {
+        let position: Position = self.board_position(board)?;
+        let value: u32 = self.search(&position)?;
+
+        Some([value, position_points(&position) - value])
+    }
+
+    // Carta que treu més punts per a l'equip del jugador actual
+    pub fn best_card(&self, board: &Board) -> Option<Card> {
+        let position: Position = self.board_position(board)?;
+        let team: usize = position.current_player % 2;
+
+        let mut best: Option<(Card, i64)> = None;
+        for card in position.legal_cards().iter() {
+            let mut child: Position = position;
+            let value: u32 = child.play(card) as u32 + self.search(&child)?;
+            let value: i64 = if team == 0 { value as i64 } else { -(value as i64) };
+            if best.is_none_or(|(_, best_value)| value > best_value) {
+                best = Some((card, value));
+            }
+        }
+
+        best.map(|(card, _)| card)
+    }
+
+    // Valor d'una posició de l'inici d'una baza si el seu bloc és a la taula
+    pub(crate) fn value(&self, position: &Position) -> Option<u32> {
+        if position.trumfo != self.trumfo {
+            return None;
+        }
+
+        let remaining: CardSet = position.hands.iter().fold(CardSet::EMPTY, |remaining, &hand| remaining | hand);
+        let values: &Vec<u8> = self.blocks.get(&remaining)?;
+
+        Some(values[Tablebase::index(remaining, position) as usize] as u32)
+    }
+
+    // Torna a resoldre `samples` entrades a l'atzar i comprova el valor i l'índex.
+    // Retorna el primer error trobat
+    pub fn verify<R: Rng>(&self, samples: usize, rng: &mut R) -> Result<(), String> {
+        if self.is_empty() {
+            return Err("La taula no té cap bloc.".to_string());
+        }
+
+        let mut keys: Vec<CardSet> = self.blocks.keys().copied().collect();
+        keys.sort_by_key(CardSet::bits);
+        for _ in 0..samples {
+            // Totes les entrades amb la mateixa probabilitat, no tots els blocs
+            let mut index: u64 = rng.gen_range(0..self.len() as u64);
+            let mut block: usize = 0;
+            while index >= self.blocks[&keys[block]].len() as u64 {
+                index -= self.blocks[&keys[block]].len() as u64;
+                block += 1;
+            }
+
+            let remaining: CardSet = keys[block];
+            let values: &Vec<u8> = &self.blocks[&remaining];
+            let position: Position = self.position(remaining, index);
+            if Tablebase::index(remaining, &position) != index {
+                return Err(format!("L'entrada {} del bloc {} no torna al mateix índex", index, remaining));
+            }
+
+            let value: u32 = EndgameSolver::new().solve_position(&position);
+            if value != values[index as usize] as u32 {
+                return Err(format!("L'entrada {} del bloc {} val {} i el solver diu {}", index, remaining, values[index as usize], value));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn board_position(&self, board: &Board) -> Option<Position> {
+        let position: Position = Position::new(board);
+        let valid: bool = board.is_arrastre()
+                          && position.trumfo == self.trumfo
+                          && !position.hands[position.current_player].is_empty();
+
+        valid.then_some(position)
+    }
+
+    // Minimax fins a l'inici de la baza següent, on es consulta la taula
+    fn search(&self, position: &Position) -> Option<u32> {
+        if position.hands[position.current_player].is_empty() {
+            return Some(0);
+        }
+        if Board::is_baza_empty(position.current_baza) {
+            return self.value(position);
+        }
+
+        let team: usize = position.current_player % 2;
+        let mut best: Option<u32> = None;
+        for card in position.legal_cards().iter() {
+            let mut child: Position = *position;
+            let value: u32 = child.play(card) as u32 + self.search(&child)?;
+            best = Some(match (best, team == 0) {
+                (None, _) => value,
+                (Some(best), true) => best.max(value),
+                (Some(best), false) => best.min(value)
+            });
+        }
+
+        best
+    }
+
+    fn ensure_block(&mut self, remaining: CardSet) {
+        if self.blocks.contains_key(&remaining) {
+            return;
+        }
+
+        let entries: u64 = Tablebase::block_entries(remaining.len() / 4).unwrap();     // Can't fail, the size was checked by generate_block
+        let mut values: Vec<u8> = Vec::with_capacity(entries as usize);
+        for index in 0..entries {
+            let position: Position = self.position(remaining, index);
+            values.push(self.solve_baza(&position) as u8);
+        }
+
+        self.blocks.insert(remaining, values);
+    }
+
+    // Com search, però generant els blocs que falten a l'inici de la baza següent
+    fn solve_baza(&mut self, position: &Position) -> u32 {
+        let team: usize = position.current_player % 2;
+        let mut best: Option<u32> = None;
+
+        for card in position.legal_cards().iter() {
+            let mut child: Position = *position;
+            let gain: u32 = child.play(card) as u32;
+            let value: u32 = gain + if child.hands[child.current_player].is_empty() {
+                0
+            } else if Board::is_baza_empty(child.current_baza) {
+                let remaining: CardSet = child.hands.iter().fold(CardSet::EMPTY, |remaining, &hand| remaining | hand);
+                self.ensure_block(remaining);
+                self.value(&child).unwrap()     // Can't fail, the block was just generated
+            } else {
+                self.solve_baza(&child)
+            };
+
+            best = Some(match (best, team == 0) {
+                (None, _) => value,
+                (Some(best), true) => best.max(value),
+                (Some(best), false) => best.min(value)
+            });
+        }
+
+        best.unwrap()     // Can't panic, the current player has cards
+    }
+
+    fn index(remaining: CardSet, position: &Position) -> u64 {
+        let k: usize = remaining.len() / 4;
+        let mut available: CardSet = remaining;
+        let mut index: u64 = 0;
+
+        for (seat, hand) in position.hands.iter().enumerate() {
+            let mut rank: u64 = 0;
+            for (i, card) in hand.iter().enumerate() {
+                let compressed: usize = (available.bits() & ((1 << card.index()) - 1)).count_ones() as usize;
+                rank += binomial(compressed, i + 1);
+            }
+            index = index * binomial((4 - seat) * k, k) + rank;
+            available = available - *hand;
+        }
+
+        let per_leader: u64 = Tablebase::block_entries(k).unwrap() / 4;     // Can't fail, the block is in the table
+        position.current_player as u64 * per_leader + index
+    }
+
+    fn position(&self, remaining: CardSet, index: u64) -> Position {
+        let k: usize = remaining.len() / 4;
+        let per_leader: u64 = Tablebase::block_entries(k).unwrap() / 4;     // Can't fail, the block is in the table
+        let leader: usize = (index / per_leader) as usize;
+        let mut rest: u64 = index % per_leader;
+
+        let mut ranks: [u64; 4] = [0; 4];
+        for seat in (0..4).rev() {
+            let combinations: u64 = binomial((4 - seat) * k, k);
+            ranks[seat] = rest % combinations;
+            rest /= combinations;
+        }
+
+        let mut available: CardSet = remaining;
+        let mut hands: [CardSet; 4] = [CardSet::EMPTY; 4];
+        for seat in 0..4 {
+            let mut rank: u64 = ranks[seat];
+            for i in (1..=k).rev() {
+                let mut compressed: usize = i - 1;
+                while binomial(compressed + 1, i) <= rank {
+                    compressed += 1;
+                }
+                rank -= binomial(compressed, i);
+                hands[seat].insert(available.nth(compressed).unwrap());     // Can't panic, the rank is smaller than C(available, k)
+            }
+            available = available - hands[seat];
+        }
+
+        Position::from_hands(hands, leader, self.trumfo)
+    }
+}
+
+// Punts de carta que queden a la posició, 10 d'últimes incloses
+fn position_points(position: &Position) -> u32 {
+    let baza_points: u32 = position.current_baza.iter().flatten().map(|card| card.value()).sum();
+    position.hands.iter().map(|hand| hand.points()).sum::<u32>() + baza_points + 10
+}
+
+// Tots els conjunts de `cards` cartes, en ordre de bits (Gosper)
+fn card_sets(cards: usize) -> impl Iterator<Item = CardSet> {
+    let first: u64 = (1 << cards) - 1;
+    iter::successors(Some(first), |&bits| {
+        let lowest: u64 = bits & bits.wrapping_neg();
+        let ripple: u64 = bits + lowest;
+        let next: u64 = (((ripple ^ bits) >> 2) / lowest) | ripple;
+        (next <= CardSet::FULL.bits()).then_some(next)
+    }).map(CardSet::from_bits)
+}
+
+fn binomial(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+
+    (0..k).fold(1u64, |result, i| result * (n - i) as u64 / (i + 1) as u64)
+}
@@ -0,0 +1,234 @@
+use std::fs;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::bot_behaviour::Behaviour;
+use crate::driver::play_seeded_game;
+use crate::game::GameState;
+use crate::stats::WinRate;
+
+// Algorisme genètic per ajustar paràmetres numèrics de bots. Cada candidat juga les mateixes
+// partides amb llavor contra els bots de referència, des dels dos costats de la taula, i els
+// millors passen a la generació següent. L'atzar de cada generació surt de la llavor del tuner,
+// per tant reprendre des d'un checkpoint dona el mateix resultat que no haver parat.
+
+// Crea el bot d'un candidat a partir dels seus paràmetres
+pub type CandidateFactory = Box<dyn Fn(&[f64]) -> Box<dyn Behaviour>>;
+pub type ReferenceFactory = Box<dyn Fn() -> Box<dyn Behaviour>>;
+
+// Llavors de validació lluny de les d'entrenament
+const VALIDATION_SEED_OFFSET: u64 = 1 << 63;
+const TOURNAMENT_SIZE: usize = 3;
+
+pub struct TunerConfig {
+    pub population: usize,
+    // Candidats que passen sense canvis a la generació següent
+    pub elite: usize,
+    // Repartiments per candidat i generació; cada un es juga dues vegades canviant de seients
+    pub games: usize,
+    pub mutation_rate: f64,
+    pub mutation_scale: f64,
+    pub bounds: (f64, f64),
+    pub seed: u64
+}
+
+impl Default for TunerConfig {
+    fn default() -> Self {
+        TunerConfig {
+            population: 16,
+            elite: 4,
+            games: 200,
+            mutation_rate: 0.3,
+            mutation_scale: 0.3,
+            bounds: (-5.0, 5.0),
+            seed: 0
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    pub params: Vec<f64>,
+    pub win_rate: WinRate
+}
+
+pub struct Tuner {
+    pub config: TunerConfig,
+    make_candidate: CandidateFactory,
+    references: Vec<ReferenceFactory>,
+    population: Vec<Vec<f64>>,
+    generation: usize
+}
+
+impl Tuner {
+    // La població inicial són `initial` i mutacions seves
+    pub fn new(config: TunerConfig, initial: &[f64], make_candidate: CandidateFactory, references: Vec<ReferenceFactory>) -> Result<Self, String> {
+        if references.is_empty() {
+            return Err("El tuner necessita almenys un bot de referència.".to_string());
+        }
+
+        let mut tuner: Tuner = Tuner {
+            config,
+            make_candidate,
+            references,
+            population: Vec::new(),
+            generation: 0
+        };
+
+        let mut rng: StdRng = StdRng::seed_from_u64(!tuner.config.seed);
+        tuner.population.push(initial.to_vec());
+        while tuner.population.len() < tuner.config.population.max(1) {
+            let params: Vec<f64> = tuner.mutate(initial.to_vec(), &mut rng);
+            tuner.population.push(params);
+        }
+
+        Ok(tuner)
+    }
+
+    // `param_count` és el nombre de paràmetres que espera `make_candidate`
+    pub fn resume(config: TunerConfig, checkpoint: &str, param_count: usize, make_candidate: CandidateFactory, references: Vec<ReferenceFactory>) -> Result<Self, String> {
+        if references.is_empty() {
+            return Err("El tuner necessita almenys un bot de referència.".to_string());
+        }
+
+        let text: String = fs::read_to_string(checkpoint).map_err(|error| format!("No s'ha pogut llegir {}: {}", checkpoint, error))?;
+        let mut generation: Option<usize> = None;
+        let mut population: Vec<Vec<f64>> = Vec::new();
+
+        for (line_number, line) in text.lines().enumerate() {
+            let line: &str = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            match line.split_once('=').map(|(key, value)| (key.trim(), value.trim())) {
+                Some(("generation", value)) => {
+                    generation = Some(value.parse::<usize>().map_err(|_| format!("{}:{}: generació invàlida", checkpoint, line_number + 1))?);
+                },
+                Some(("candidate", value)) => {
+                    let params: Result<Vec<f64>, _> = value.split_whitespace().map(str::parse::<f64>).collect();
+                    population.push(params.map_err(|_| format!("{}:{}: paràmetres invàlids", checkpoint, line_number + 1))?);
+                },
+                _ => return Err(format!("{}:{}: línia desconeguda", checkpoint, line_number + 1))
+            }
+        }
+
+        let generation: usize = generation.ok_or(format!("{}: falta la generació", checkpoint))?;
+        if population.is_empty() {
+            return Err(format!("{}: la població és buida", checkpoint));
+        }
+        if let Some(params) = population.iter().find(|params| params.len() != param_count) {
+            return Err(format!("{}: un candidat té {} paràmetres en lloc de {}", checkpoint, params.len(), param_count));
+        }
+
+        Ok(Tuner {
+            config,
+            make_candidate,
+            references,
+            population,
+            generation
+        })
+    }
+
+    pub fn save_checkpoint(&self, path: &str) -> Result<(), String> {
+        let mut text: String = format!("# Tuner checkpoint\ngeneration = {}\n", self.generation);
+        for params in self.population.iter() {
+            let values: Vec<String> = params.iter().map(f64::to_string).collect();
+            text.push_str(&format!("candidate = {}\n", values.join(" ")));
+        }
+
+        fs::write(path, text).map_err(|error| format!("No s'ha pogut escriure {}: {}", path, error))
+    }
+
+    pub fn get_generation(&self) -> usize {
+        self.generation
+    }
+
+    // Avalua la generació actual, en cria la següent i retorna el millor candidat avaluat
+    pub fn step(&mut self) -> Candidate {
+        let seed: u64 = self.config.seed.wrapping_add((self.generation as u64) << 32);
+        let mut candidates: Vec<Candidate> = self.population.iter()
+                                                            .map(|params| Candidate {
+                                                                params: params.clone(),
+                                                                win_rate: self.play(params, seed, self.config.games)
+                                                            })
+                                                            .collect();
+        candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.win_rate.wins));
+
+        let mut rng: StdRng = self.generation_rng();
+        let mut next_population: Vec<Vec<f64>> = candidates.iter()
+                                                           .take(self.config.elite.max(1))
+                                                           .map(|candidate| candidate.params.clone())
+                                                           .collect();
+        while next_population.len() < self.config.population.max(1) {
+            let first: &Candidate = Tuner::tournament(&candidates, &mut rng);
+            let second: &Candidate = Tuner::tournament(&candidates, &mut rng);
+            let child: Vec<f64> = first.params.iter()
+                                              .zip(second.params.iter())
+                                              .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
+                                              .collect();
+            next_population.push(self.mutate(child, &mut rng));
+        }
+
+        self.population = next_population;
+        self.generation += 1;
+
+        candidates.swap_remove(0)
+    }
+
+    // Taxa de victòries amb partides que no s'han fet servir per seleccionar
+    pub fn validate(&self, params: &[f64], games: usize) -> WinRate {
+        self.play(params, self.config.seed.wrapping_add(VALIDATION_SEED_OFFSET), games)
+    }
+
+    fn play(&self, params: &[f64], seed: u64, games: usize) -> WinRate {
+        let mut wins: usize = 0;
+
+        for game in 0..games {
+            let game_seed: u64 = seed.wrapping_add(game as u64);
+            let reference: &ReferenceFactory = &self.references[game % self.references.len()];
+
+            for candidate_team in 0..2 {
+                let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| match seat % 2 == candidate_team {
+                    true => (self.make_candidate)(params),
+                    false => reference()
+                }).collect();
+
+                let winner: GameState = play_seeded_game(&bots, game_seed);
+                let candidate_won: GameState = if candidate_team == 0 { GameState::Team0Won } else { GameState::Team1Won };
+                if winner == candidate_won {
+                    wins += 1;
+                }
+            }
+        }
+
+        WinRate::new(wins, 2 * games)
+    }
+
+    fn tournament<'a, R: Rng>(candidates: &'a [Candidate], rng: &mut R) -> &'a Candidate {
+        (0..TOURNAMENT_SIZE).map(|_| &candidates[rng.gen_range(0..candidates.len())])
+                            .max_by_key(|candidate| candidate.win_rate.wins)
+                            .unwrap()
+    }
+
+    fn mutate<R: Rng>(&self, mut params: Vec<f64>, rng: &mut R) -> Vec<f64> {
+        for param in params.iter_mut() {
+            if rng.gen_bool(self.config.mutation_rate.clamp(0.0, 1.0)) {
+                *param = (*param + gaussian(rng) * self.config.mutation_scale).clamp(self.config.bounds.0, self.config.bounds.1);
+            }
+        }
+
+        params
+    }
+
+    fn generation_rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.config.seed ^ (self.generation as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+    }
+}
+
+// Normal estàndard (Box-Muller)
+fn gaussian<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen::<f64>();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
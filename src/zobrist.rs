@@ -0,0 +1,85 @@
+use crate::game::{Card, Pal};
+
+// Claus de Zobrist per identificar posicions (taules de transposició dels bots de cerca).
+// Es generen en temps de compilació amb splitmix64, per tant el hash d'una posició és
+// el mateix entre execucions.
+pub struct ZobristKeys {
+    hands: [[u64; 40]; 4],
+    current_baza: [[u64; 40]; 4],
+    bazas: [[u64; 40]; 2],
+    trumfo: [u64; 40],
+    cantes: [u64; 4],
+    current_player: [u64; 4]
+}
+
+pub static KEYS: ZobristKeys = ZobristKeys::generate(0x0067_7569_6e6f_7465);
+
+impl ZobristKeys {
+    const fn generate(seed: u64) -> Self {
+        let mut state: u64 = seed;
+        let mut keys: ZobristKeys = ZobristKeys {
+            hands: [[0; 40]; 4],
+            current_baza: [[0; 40]; 4],
+            bazas: [[0; 40]; 2],
+            trumfo: [0; 40],
+            cantes: [0; 4],
+            current_player: [0; 4]
+        };
+
+        let mut card: usize = 0;
+        while card < 40 {
+            let mut player: usize = 0;
+            while player < 4 {
+                keys.hands[player][card] = splitmix64(&mut state);
+                keys.current_baza[player][card] = splitmix64(&mut state);
+                player += 1;
+            }
+
+            keys.bazas[0][card] = splitmix64(&mut state);
+            keys.bazas[1][card] = splitmix64(&mut state);
+            keys.trumfo[card] = splitmix64(&mut state);
+            card += 1;
+        }
+
+        let mut i: usize = 0;
+        while i < 4 {
+            keys.cantes[i] = splitmix64(&mut state);
+            keys.current_player[i] = splitmix64(&mut state);
+            i += 1;
+        }
+
+        keys
+    }
+
+    pub fn hand(&self, player: usize, card: Card) -> u64 {
+        self.hands[player][card.index()]
+    }
+
+    pub fn current_baza(&self, player: usize, card: Card) -> u64 {
+        self.current_baza[player][card.index()]
+    }
+
+    pub fn baza(&self, team: usize, card: Card) -> u64 {
+        self.bazas[team][card.index()]
+    }
+
+    pub fn trumfo(&self, card: Card) -> u64 {
+        self.trumfo[card.index()]
+    }
+
+    pub fn cante(&self, pal: Pal) -> u64 {
+        self.cantes[pal as usize]
+    }
+
+    pub fn current_player(&self, player: usize) -> u64 {
+        self.current_player[player]
+    }
+}
+
+const fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z: u64 = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
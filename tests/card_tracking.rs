@@ -0,0 +1,62 @@
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::smart_bot::SmartBot;
+use rust_guinote::card_set::CardSet;
+use rust_guinote::card_tracking::HandBeliefs;
+use rust_guinote::driver::post_baza_actions;
+use rust_guinote::game::{Board, Card, GameState};
+
+mod common;
+
+use common::{mixed_bots, visit_positions};
+
+// El trumfo girat que es roba amb l'última carta del mazo és públic: els altres jugadors saben
+// qui el té fins que el juga
+#[test]
+fn drawn_trumfo_is_known() {
+    let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|_| Box::new(SmartBot {}) as Box<dyn Behaviour>).collect();
+
+    for seed in 0..20 {
+        let mut board: Board = Board::new_seeded((seed % 4) as usize, seed);
+        while !board.is_arrastre() {
+            match bots[board.current_player].play_card(&mut board).unwrap() {
+                GameState::BazaEnded => post_baza_actions(&bots, &mut board),
+                GameState::Team0Won | GameState::Team1Won => break,
+                _ => ()
+            }
+        }
+        if !board.is_arrastre() {
+            continue;
+        }
+
+        let trumfo: Card = board.current_trumfo;
+        let holder: usize = (0..4).find(|&player| board.get_hand(player).cards.contains(trumfo)).unwrap();
+        assert!(board.get_card_tracker().get_shown(holder).contains(trumfo), "Seed {}: the drawn trumfo is not shown", seed);
+        for observer in (0..4).filter(|&observer| observer != holder) {
+            let beliefs: HandBeliefs = HandBeliefs::new(&board, observer);
+            assert!(beliefs.get_certain(holder).contains(trumfo), "Seed {}: player {} does not know who drew the trumfo", seed, observer);
+        }
+    }
+}
+
+// Les creences no contradiuen mai les mans reals: cada mà oculta està dins de les cartes
+// possibles i conté totes les segures
+#[test]
+fn beliefs_match_the_real_hands() {
+    let bots: Vec<Box<dyn Behaviour>> = mixed_bots();
+
+    for seed in 0..100 {
+        visit_positions(&bots, seed, |board| {
+            for observer in 0..4 {
+                let beliefs: HandBeliefs = HandBeliefs::new(board, observer);
+                for player in (0..4).filter(|&player| player != observer) {
+                    let hand: CardSet = board.get_hand(player).cards;
+                    assert!((hand - beliefs.get_possible(player)).is_empty() && (beliefs.get_certain(player) - hand).is_empty(),
+                            "Seed {}: wrong beliefs of player {} about player {}: possible {}, certain {}\n{}",
+                            seed, observer, player, beliefs.get_possible(player), beliefs.get_certain(player), board);
+                }
+            }
+
+            true
+        });
+    }
+}
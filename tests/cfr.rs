@@ -0,0 +1,58 @@
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{expert_bot::{ExpertBot, ExpertConfig}, random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::cfr::{CfrConfig, CfrSolver, Exploitability, StrategyTable};
+use rust_guinote::game::Board;
+
+mod common;
+
+use common::endgame;
+
+// Finals de 2 cartes per jugador amb pocs repartiments, perquè el test no trigui
+const POSITIONS: usize = 4;
+const MAX_DEALS: usize = 100;
+const CHECKPOINTS: [usize; 2] = [10, 60];
+
+// Les millors respostes no perden mai punts, l'explotabilitat de CFR baixa amb les iteracions i,
+// sumada a totes les posicions, acaba per sota de la dels bots heurístics (en alguna posició un bot
+// pot jugar exactament l'equilibri)
+#[test]
+fn cfr_converges_below_the_heuristic_bots() {
+    let heuristics: Vec<(&str, Box<dyn Behaviour>)> = vec![
+        ("random", Box::new(RandomBot {})),
+        ("smart", Box::new(SmartBot {})),
+        ("expert", Box::new(ExpertBot::new(ExpertConfig::default())))
+    ];
+    let config: CfrConfig = CfrConfig { max_deals: MAX_DEALS, ..CfrConfig::default() };
+    let mut cfr_total: f64 = 0.0;
+    let mut heuristic_totals: Vec<f64> = vec![0.0; heuristics.len()];
+
+    let mut solved: usize = 0;
+    let mut seed: u64 = 0;
+    while solved < POSITIONS {
+        let board: Option<Board> = endgame(seed);
+        seed += 1;
+        let Some(Ok(mut solver)) = board.map(|board| CfrSolver::new(&board, config)) else {
+            continue;
+        };
+        solved += 1;
+
+        let mut measured: Vec<f64> = Vec::new();
+        for &iterations in CHECKPOINTS.iter() {
+            solver.run(iterations - solver.get_iterations());
+            let exploitability: Exploitability = solver.exploitability(&solver.strategy());
+            assert!(exploitability.gains.iter().all(|&gain| gain >= -1e-6), "Seed {}: negative best response gain", seed);
+            measured.push(exploitability.total());
+        }
+        assert!(measured[measured.len() - 1] <= measured[0], "Seed {}: exploitability went up: {:?}", seed, measured);
+
+        cfr_total += measured[measured.len() - 1];
+        for ((_, bot), total) in heuristics.iter().zip(heuristic_totals.iter_mut()) {
+            let table: StrategyTable = solver.bot_strategy(bot.as_ref());
+            *total += solver.exploitability(&table).total();
+        }
+    }
+
+    for ((name, _), total) in heuristics.iter().zip(heuristic_totals.iter()) {
+        assert!(cfr_total < *total, "CFR ({:.2}) is more exploitable than {} ({:.2})", cfr_total, name, total);
+    }
+}
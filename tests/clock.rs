@@ -0,0 +1,163 @@
+use std::thread;
+use std::time::Duration;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::ismcts_bot::{IsmctsBot, IsmctsConfig};
+use rust_guinote::bot_behaviours::stochastic_bot::{SoftmaxBot, SoftmaxConfig};
+use rust_guinote::clock::{ClockConfig, TimeoutPolicy};
+use rust_guinote::driver::{play_logged_game, DriverOptions};
+use rust_guinote::game::{Board, GameState};
+use rust_guinote::game_log::{GameLog, LogEvent};
+
+const GAMES: u64 = 20;
+const SEARCH_GAMES: u64 = 3;
+const SLOW_MOVE: Duration = Duration::from_millis(4);
+const SLOW_CLOCK: ClockConfig = ClockConfig { initial: Duration::from_millis(10), increment: Duration::from_millis(1), policy: TimeoutPolicy::Forfeit };
+
+// ExpertBot que tarda SLOW_MOVE per jugada
+struct SlowBot(ExpertBot);
+
+impl Behaviour for SlowBot {
+    fn name(&self) -> String {
+        "SlowBot".to_string()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        thread::sleep(SLOW_MOVE);
+        self.0.play_card(board)
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        self.0.post_baza_actions(board, player);
+    }
+}
+
+// ExpertBot que juga de seguida però tarda SLOW_MOVE després de cada baza
+struct SlowPostBazaBot(ExpertBot);
+
+impl Behaviour for SlowPostBazaBot {
+    fn name(&self) -> String {
+        "SlowPostBazaBot".to_string()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        self.0.play_card(board)
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        thread::sleep(SLOW_MOVE);
+        self.0.post_baza_actions(board, player);
+    }
+}
+
+// Equip 0 amb SlowBots i equip 1 amb ExpertBots
+fn slow_bots() -> Vec<Box<dyn Behaviour>> {
+    (0..4).map(|seat| match seat % 2 {
+        0 => Box::new(SlowBot(ExpertBot::new(ExpertConfig::default()))) as Box<dyn Behaviour>,
+        _ => Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>
+    }).collect()
+}
+
+fn assert_forfeit_on_time(log: &GameLog) {
+    let events: &[LogEvent] = log.get_events();
+    assert!(log.timeouts() == 1 && matches!(events[events.len() - 4], LogEvent::Timeout { .. }), "Forfeit without a timeout:\n{}", log);
+    assert!(matches!(events[events.len() - 2], LogEvent::Forfeit { seat: 0 | 2 }), "Timeout without a forfeit:\n{}", log);
+}
+
+// El que queda a cada rellotge ha de ser el d'abans menys el gastat més l'increment
+fn check_clocks(log: &GameLog, clock: &ClockConfig) {
+    let mut remaining: [Duration; 4] = [clock.initial; 4];
+    for event in log.get_events() {
+        if let LogEvent::Clock { seat, used, remaining: after } = *event {
+            let expected: Duration = remaining[seat].saturating_sub(used) + clock.increment;
+            assert_eq!(after, expected, "Seat {} has the wrong time left:\n{}", seat, log);
+            remaining[seat] = after;
+        }
+    }
+}
+
+#[test]
+fn slow_team_forfeits_on_time() {
+    let slow: Vec<Box<dyn Behaviour>> = slow_bots();
+    let options: DriverOptions = DriverOptions { clock: Some(SLOW_CLOCK), ..DriverOptions::default() };
+
+    for seed in 0..GAMES {
+        let mut log: GameLog = GameLog::new();
+        assert_eq!(play_logged_game(&slow, seed, &options, &mut log), GameState::Team1Won, "Seed {}: the slow team did not lose on time", seed);
+        assert_forfeit_on_time(&log);
+        check_clocks(&log, &SLOW_CLOCK);
+    }
+}
+
+// El temps de després de la baza també es descompta
+#[test]
+fn slow_post_baza_actions_forfeit_on_time() {
+    let slow: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| match seat % 2 {
+        0 => Box::new(SlowPostBazaBot(ExpertBot::new(ExpertConfig::default()))) as Box<dyn Behaviour>,
+        _ => Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>
+    }).collect();
+    let options: DriverOptions = DriverOptions { clock: Some(SLOW_CLOCK), ..DriverOptions::default() };
+
+    for seed in 0..GAMES {
+        let mut log: GameLog = GameLog::new();
+        assert_eq!(play_logged_game(&slow, seed, &options, &mut log), GameState::Team1Won, "Seed {}: the slow team did not lose on time", seed);
+        assert_forfeit_on_time(&log);
+        check_clocks(&log, &SLOW_CLOCK);
+    }
+}
+
+#[test]
+fn slow_team_plays_at_random_after_a_timeout() {
+    let slow: Vec<Box<dyn Behaviour>> = slow_bots();
+    let clock: ClockConfig = ClockConfig { policy: TimeoutPolicy::RandomMove, ..SLOW_CLOCK };
+    let options: DriverOptions = DriverOptions { clock: Some(clock), ..DriverOptions::default() };
+
+    for seed in 0..GAMES {
+        let mut log: GameLog = GameLog::new();
+        let game_state: GameState = play_logged_game(&slow, seed, &options, &mut log);
+        assert!(log.timeouts() > 0 && game_state != GameState::None, "The slow team should keep playing at random:\n{}", log);
+        check_clocks(&log, &clock);
+    }
+}
+
+// L'ISMCTS limitat només pel temps s'ajusta al rellotge
+#[test]
+fn time_limited_search_fits_the_clock() {
+    let clock: ClockConfig = ClockConfig { initial: Duration::from_millis(500), increment: Duration::from_millis(40), policy: TimeoutPolicy::Forfeit };
+    let search: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| match seat % 2 {
+        0 => Box::new(IsmctsBot::new(IsmctsConfig { iterations: usize::MAX, time_budget: Some(Duration::from_millis(80)), ..IsmctsConfig::default() })) as Box<dyn Behaviour>,
+        _ => Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>
+    }).collect();
+
+    for seed in 0..SEARCH_GAMES {
+        let mut log: GameLog = GameLog::new();
+        play_logged_game(&search, seed, &DriverOptions { clock: Some(clock), ..DriverOptions::default() }, &mut log);
+        assert_eq!(log.timeouts(), 0, "IsmctsBot ran out of time:\n{}", log);
+        check_clocks(&log, &clock);
+    }
+}
+
+// Els embolcalls amb atzar passen el temps del rellotge al bot de dins: sense, cada jugada
+// gastaria el time_budget sencer
+#[test]
+fn wrapped_search_fits_the_clock() {
+    let clock: ClockConfig = ClockConfig { initial: Duration::from_millis(200), increment: Duration::from_millis(20), policy: TimeoutPolicy::Forfeit };
+    let wrapped: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| match seat % 2 {
+        0 => Box::new(SoftmaxBot::new(Box::new(IsmctsBot::new(IsmctsConfig { iterations: usize::MAX, time_budget: Some(Duration::from_secs(2)), ..IsmctsConfig::default() })), SoftmaxConfig { temperature: 0.5, seed: Some(seat) })) as Box<dyn Behaviour>,
+        _ => Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>
+    }).collect();
+
+    let mut log: GameLog = GameLog::new();
+    play_logged_game(&wrapped, 0, &DriverOptions { clock: Some(clock), ..DriverOptions::default() }, &mut log);
+    assert_eq!(log.timeouts(), 0, "SoftmaxBot ran out of time:\n{}", log);
+    check_clocks(&log, &clock);
+}
+
+#[test]
+fn no_clock_events_without_a_clock() {
+    let experts: Vec<Box<dyn Behaviour>> = (0..4).map(|_| Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>).collect();
+
+    let mut log: GameLog = GameLog::new();
+    play_logged_game(&experts, 0, &DriverOptions::default(), &mut log);
+    assert_eq!(log.time_used(), [Duration::ZERO; 4]);
+}
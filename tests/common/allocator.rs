@@ -0,0 +1,30 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+// Compta les allocacions de cada fil per comprovar que la generació de jugades no en fa cap.
+// Els tests corren en paral·lel, per això el comptador no és global. Va a part de mod.rs perquè
+// només l'instal·lin els binaris que l'inclouen
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// Allocacions fetes fins ara en aquest fil
+pub fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
@@ -0,0 +1,229 @@
+// Fixtures compartides pels tests d'integració i pels benches, que les inclouen amb
+// #[path = "../tests/common/mod.rs"] per mesurar sobre les mateixes posicions que es comproven.
+// Cada binari en fa servir només una part
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::bot_behaviours::observed_bot::ObservedBot;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::weighted_bot::{WeightedBot, Weights};
+use rust_guinote::bot_behaviours::ensemble_bot::{EnsembleBot, EnsembleConfig, EnsembleRule, Member};
+use rust_guinote::driver::{play_recorded_game, post_baza_actions};
+use rust_guinote::card_set::CardSet;
+use rust_guinote::game::{Board, Card, GameState, Pal};
+use rust_guinote::modeling::SharedModels;
+
+pub fn random_bots() -> Vec<Box<dyn Behaviour>> {
+    (0..4).map(|_| Box::new(RandomBot {}) as Box<dyn Behaviour>).collect()
+}
+
+pub fn smart_bots() -> Vec<Box<dyn Behaviour>> {
+    (0..4).map(|_| Box::new(SmartBot {}) as Box<dyn Behaviour>).collect()
+}
+
+// RandomBot a l'equip 0 i SmartBot a l'equip 1
+pub fn mixed_bots() -> Vec<Box<dyn Behaviour>> {
+    (0..4).map(|seat| match seat % 2 {
+        0 => Box::new(RandomBot {}) as Box<dyn Behaviour>,
+        _ => Box::new(SmartBot {}) as Box<dyn Behaviour>
+    }).collect()
+}
+
+// Equip 0 amb el bot de `team_0`, equip 1 amb RandomBot, tots observats
+pub fn observed<F: Fn() -> Box<dyn Behaviour>>(models: &SharedModels, team_0: F, name: &str) -> Vec<Box<dyn Behaviour>> {
+    (0..4).map(|seat| {
+        let team: usize = seat % 2;
+        let (bot, player): (Box<dyn Behaviour>, &str) = if team == 0 { (team_0(), name) } else { (Box::new(RandomBot {}), "random") };
+        Box::new(ObservedBot::new(bot, player, models.clone())) as Box<dyn Behaviour>
+    }).collect()
+}
+
+// Perquè els dos seients de l'equip comparteixin les estadístiques
+pub struct Shared(pub Rc<EnsembleBot>);
+
+impl Behaviour for Shared {
+    fn name(&self) -> String {
+        self.0.name()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        self.0.play_card(board)
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        self.0.post_baza_actions(board, player);
+    }
+}
+
+// ExpertBot, SmartBot i WeightedBot amb els pesos `weights`
+pub fn ensemble(rule: EnsembleRule, weights: &[f64; 3]) -> EnsembleBot {
+    let bots: [Box<dyn Behaviour>; 3] = [
+        Box::new(ExpertBot::new(ExpertConfig::default())),
+        Box::new(SmartBot {}),
+        Box::new(WeightedBot::new(Weights::default()))
+    ];
+    let members: Vec<Member> = bots.into_iter().zip(weights.iter()).map(|(bot, &weight)| Member { bot, weight }).collect();
+
+    EnsembleBot::new(EnsembleConfig { members, rule }).unwrap()
+}
+
+// Jugades d'una partida amb el bot de `create(seient)` a tots els seients
+pub fn moves<F: Fn(usize) -> Box<dyn Behaviour>>(create: F, seed: u64) -> Vec<Card> {
+    let bots: Vec<Box<dyn Behaviour>> = (0..4).map(create).collect();
+    let mut played: Vec<Card> = Vec::new();
+    play_recorded_game(&bots, seed, &mut |_, card| played.push(card));
+    played
+}
+
+// Juga la partida de llavor `seed` entre `bots` i passa a `visit` el Board abans de cada jugada,
+// fins que `visit` retorna false o s'acaba la partida
+pub fn visit_positions<F: FnMut(&Board) -> bool>(bots: &[Box<dyn Behaviour>], seed: u64, mut visit: F) {
+    let mut board: Board = Board::new_seeded((seed % 4) as usize, seed);
+
+    while visit(&board) {
+        match bots[board.current_player].play_card(&mut board).unwrap() {
+            GameState::BazaEnded => post_baza_actions(bots, &mut board),
+            GameState::Team0Won | GameState::Team1Won => break,
+            _ => ()
+        }
+    }
+}
+
+// Totes les posicions de les partides de llavor 0..`games`, abans de cada jugada
+pub fn collect_positions(bots: &[Box<dyn Behaviour>], games: u64) -> Vec<Board> {
+    let mut positions: Vec<Board> = Vec::new();
+    for seed in 0..games {
+        visit_positions(bots, seed, |board| {
+            positions.push(board.clone());
+            true
+        });
+    }
+
+    positions
+}
+
+// Posicions d'arrastre de la primera ronda (sense cantes, així el recompte del Board és només de
+// cartes) de partides entre RandomBots. Retorna els inicis d'arrastre (6 cartes per jugador) i
+// totes les posicions
+pub fn arrastre_positions(games: u64) -> (Vec<Board>, Vec<Board>) {
+    let bots: Vec<Box<dyn Behaviour>> = random_bots();
+    let mut full_endgames: Vec<Board> = Vec::new();
+    let mut positions: Vec<Board> = Vec::new();
+
+    for seed in 0..games {
+        let mut endgame_started: bool = false;
+        visit_positions(&bots, seed, |board| {
+            if board.is_arrastre() && board.get_points() == [0, 0] {
+                if !endgame_started {
+                    full_endgames.push(board.clone());
+                    endgame_started = true;
+                }
+                positions.push(board.clone());
+            }
+
+            board.get_points() == [0, 0]
+        });
+    }
+
+    (full_endgames, positions)
+}
+
+// Primera posició amb 2 cartes per jugador i la baza buida d'una partida entre SmartBots, o None
+// si la partida s'acaba abans
+pub fn endgame(seed: u64) -> Option<Board> {
+    let mut endgame: Option<Board> = None;
+    visit_positions(&smart_bots(), seed, |board| {
+        if board.is_arrastre() && (0..4).all(|player| board.get_hand(player).cards.len() == 2) {
+            endgame = Some(board.clone());
+        }
+
+        endgame.is_none()
+    });
+
+    endgame
+}
+
+// Posicions de les últimes bazas, amb com a molt `cards_per_player` cartes per jugador (també a
+// mitja baza), de partides entre SmartBots amb trumfo d'ors
+pub fn last_baza_positions(games: u64, cards_per_player: usize) -> Vec<Board> {
+    let bots: Vec<Box<dyn Behaviour>> = smart_bots();
+    let mut positions: Vec<Board> = Vec::new();
+
+    for seed in 0..games {
+        visit_positions(&bots, seed, |board| {
+            let last_bazas: bool = board.is_arrastre() && (0..4).all(|player| board.get_hand(player).cards.len() <= cards_per_player);
+            if last_bazas && board.current_trumfo.pal == Pal::Orus {
+                positions.push(board.clone());
+            }
+
+            true
+        });
+    }
+
+    positions
+}
+
+// Cartes que quedaven a les mans a l'inici de la baza en curs
+pub fn baza_start_cards(board: &Board) -> CardSet {
+    let hands: CardSet = (0..4).fold(CardSet::EMPTY, |cards, player| cards | board.get_hand(player).cards);
+    board.get_current_baza().into_iter().flatten().fold(hands, |mut cards, card| {
+        cards.insert(card);
+        cards
+    })
+}
+
+// Implementació anterior al CardSet: Vec<Card> i interseccions amb HashSet
+pub fn reference_legal_cards(board: &Board) -> Vec<Card> {
+    let player_hand: Vec<Card> = board.get_current_player_hand().cards.iter().collect();
+    let baza: [Option<Card>; 4] = board.get_current_baza();
+    let played_cards: usize = baza.iter().filter(|card| card.is_some()).count();
+
+    if !board.is_arrastre() || played_cards == 0 {
+        return player_hand;
+    }
+
+    let trumfo: Pal = board.current_trumfo.pal;
+    let starting_player: usize = (board.current_player + 4 - played_cards) % 4;
+    let starting_card: Card = baza[starting_player].unwrap();
+
+    let mut winning_player: usize = starting_player;
+    let mut winning_card: Card = starting_card;
+    for offset in 1..played_cards {
+        let player: usize = (starting_player + offset) % 4;
+        let card: Card = baza[player].unwrap();
+        if card.is_better_than(winning_card, trumfo) {
+            winning_player = player;
+            winning_card = card;
+        }
+    }
+
+    let current_player_team_winning: bool = winning_player % 2 == board.current_player % 2;
+    let cards_with_baza_pal: Vec<Card> = player_hand.iter()
+                                                    .filter(|&card| card.pal == starting_card.pal)
+                                                    .cloned()
+                                                    .collect();
+    let cards_better_than_winning_card: Vec<Card> = player_hand.iter()
+                                                                .filter(|&card| card.is_better_than(winning_card, trumfo))
+                                                                .cloned()
+                                                                .collect();
+    let set1: HashSet<Card> = cards_with_baza_pal.iter().cloned().collect();
+    let set2: HashSet<Card> = cards_better_than_winning_card.iter().cloned().collect();
+    let cards_with_baza_pal_better_than_winning_card: Vec<Card> = set1.intersection(&set2).cloned().collect();
+
+    if current_player_team_winning {
+        if !cards_with_baza_pal.is_empty() {
+            return cards_with_baza_pal;
+        }
+    } else if !cards_with_baza_pal_better_than_winning_card.is_empty() {
+        return cards_with_baza_pal_better_than_winning_card;
+    } else if !cards_with_baza_pal.is_empty() {
+        return cards_with_baza_pal;
+    } else if !cards_better_than_winning_card.is_empty() {
+        return cards_better_than_winning_card;
+    }
+
+    player_hand
+}
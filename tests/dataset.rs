@@ -0,0 +1,57 @@
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::stochastic_bot::{EpsilonGreedyBot, EpsilonGreedyConfig};
+use rust_guinote::dataset::{generate_shard, DatasetReader, Record, SelfPlayConfig, HEADER_SIZE, RECORD_SIZE};
+use rust_guinote::game::Card;
+
+const GAMES: u64 = 20;
+
+fn expert(_seat: usize, _seed: u64) -> Result<Box<dyn Behaviour>, String> {
+    Ok(Box::new(ExpertBot::new(ExpertConfig::default())))
+}
+
+fn epsilon_expert(_seat: usize, seed: u64) -> Result<Box<dyn Behaviour>, String> {
+    Ok(Box::new(EpsilonGreedyBot::new(Box::new(ExpertBot::new(ExpertConfig::default())), EpsilonGreedyConfig { epsilon: 0.3, seed: Some(seed) })))
+}
+
+// Els registres d'un shard es llegeixen igual que s'han escrit i són coherents
+#[test]
+fn shard_round_trip() {
+    let config: SelfPlayConfig = SelfPlayConfig { games_per_shard: GAMES, first_seed: 1000 };
+    let mut bytes: Vec<u8> = Vec::new();
+    let records: usize = generate_shard(&config, &expert, 3, &mut bytes).unwrap();
+    assert_eq!(bytes.len(), HEADER_SIZE + records * RECORD_SIZE);
+
+    let mut reader: DatasetReader<&[u8]> = DatasetReader::new(bytes.as_slice()).unwrap();
+    assert_eq!(reader.get_header().seeds(), 1000 + 3 * GAMES..1000 + 4 * GAMES);
+
+    let mut read: usize = 0;
+    let mut games_seen: Vec<bool> = vec![false; GAMES as usize];
+    for record in reader.by_ref() {
+        let record: Record = record.unwrap();
+        assert!(record.legal_mask[record.action], "Action outside the legal mask");
+        // L'acció és a la mà codificada de l'observació
+        assert!(record.observation[record.action] == 1.0, "Action {} not in hand", Card::from_index(record.action));
+        assert!(record.outcome == 1 || record.outcome == -1, "Unfinished game");
+        games_seen[record.game as usize] = true;
+        read += 1;
+    }
+    assert_eq!(read, records);
+    assert!(games_seen.iter().all(|&seen| seen), "Missing games");
+}
+
+// Un shard surt igual generat sol que després d'uns altres, també amb bots amb atzar propi
+#[test]
+fn shards_are_reproducible_on_their_own() {
+    let config: SelfPlayConfig = SelfPlayConfig { games_per_shard: GAMES, first_seed: 0 };
+
+    let mut alone: Vec<u8> = Vec::new();
+    generate_shard(&config, &epsilon_expert, 2, &mut alone).unwrap();
+
+    let mut after_others: Vec<u8> = Vec::new();
+    for shard in 0..2 {
+        generate_shard(&config, &epsilon_expert, shard, &mut Vec::new()).unwrap();
+    }
+    generate_shard(&config, &epsilon_expert, 2, &mut after_others).unwrap();
+    assert!(alone == after_others, "Shard 2 depends on the shards generated before it");
+}
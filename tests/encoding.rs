@@ -0,0 +1,50 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::encoding::{encode, BINARY_SIZE, OBSERVATION_SIZE, SCHEMA};
+use rust_guinote::environment::{EnvConfig, Environment, Observation, RewardMode, Step, ACTION_COUNT};
+
+// Les seccions de l'esquema són contigües i cobreixen tot el vector
+#[test]
+fn schema_covers_the_observation() {
+    let mut offset: usize = 0;
+    for &(name, section_offset, size) in SCHEMA.iter() {
+        assert_eq!(section_offset, offset, "Section {} starts at the wrong offset", name);
+        offset += size;
+    }
+    assert_eq!(offset, OBSERVATION_SIZE);
+}
+
+// Al llarg d'episodis d'un agent aleatori els valors són dins de [0, 1], els binaris són 0 o 1 i
+// la secció de la mà coincideix amb la mà de l'observació
+#[test]
+fn encoded_observations_are_in_range() {
+    let mut rng: StdRng = StdRng::seed_from_u64(0);
+    let mut environment: Environment = Environment::new(EnvConfig {
+        seat: 1,
+        bots: (0..4).map(|seat| match seat == 1 {
+            true => Box::new(RandomBot {}) as Box<dyn Behaviour>,
+            false => Box::new(SmartBot {}) as Box<dyn Behaviour>
+        }).collect(),
+        reward: RewardMode::PointDifference
+    }).unwrap();
+
+    for seed in 0..200 {
+        let mut observation: Observation = environment.reset(seed).unwrap();
+        loop {
+            let encoded: [f32; OBSERVATION_SIZE] = encode(&observation);
+            assert!(encoded.iter().all(|&value| (0.0..=1.0).contains(&value)), "Seed {}: encoded value out of range", seed);
+            assert!(encoded[..BINARY_SIZE].iter().all(|&value| value == 0.0 || value == 1.0), "Seed {}: binary value not 0 or 1", seed);
+            assert!((0..ACTION_COUNT).all(|index| (encoded[index] == 1.0) == (observation.hand.bits() & (1 << index) != 0)),
+                    "Seed {}: encoded hand differs", seed);
+
+            let legal_actions: Vec<usize> = (0..ACTION_COUNT).filter(|&action| observation.legal_mask[action]).collect();
+            let step: Step = environment.step(legal_actions[rng.gen_range(0..legal_actions.len())]).unwrap();
+            if step.done {
+                break;
+            }
+            observation = step.observation;
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use rust_guinote::endgame_solver::{EndgameSolution, EndgameSolver};
+use rust_guinote::game::{Board, GameState};
+
+mod common;
+
+use common::arrastre_positions;
+
+const GAMES: u64 = 100;
+// Posicions comprovades contra el minimax sense poda (no passa de 4 cartes per jugador)
+const REFERENCE_POSITIONS: usize = 100;
+const REFERENCE_MAX_CARDS: usize = 4;
+
+// Valor i carta han de coincidir amb el minimax sobre el Board
+#[test]
+fn solver_matches_plain_minimax() {
+    let (_, positions): (Vec<Board>, Vec<Board>) = arrastre_positions(GAMES);
+    let mut checked: usize = 0;
+
+    for board in positions.iter().filter(|board| board.get_current_player_hand().cards.len() <= REFERENCE_MAX_CARDS)
+                                 .take(REFERENCE_POSITIONS) {
+        let solution: EndgameSolution = EndgameSolver::new().solve(board).unwrap();
+        let reference: [u32; 2] = reference_points(board);
+        assert!(solution.points == reference, "Solver {:?} differs from minimax {:?}:\n{}", solution.points, reference, board);
+
+        let team: usize = board.get_team(board.current_player);
+        let mut after_card: Board = board.clone();
+        let card_index: usize = after_card.get_current_player_hand().get_index(&solution.card).unwrap();
+        after_card.play_card(card_index).unwrap();
+        let card_points: [u32; 2] = match after_card.is_arrastre() && after_card.get_points() == [0, 0] {
+            true => reference_points(&after_card),
+            false => after_card.get_points()
+        };
+        assert!(card_points[team] == reference[team], "Solver card {} is not optimal:\n{}", solution.card, board);
+
+        checked += 1;
+    }
+
+    assert_eq!(checked, REFERENCE_POSITIONS);
+}
+
+// La taula de transposició que es conserva entre crides no canvia els resultats
+#[test]
+fn reused_solver_gives_the_same_points() {
+    let (full_endgames, _): (Vec<Board>, Vec<Board>) = arrastre_positions(GAMES);
+    let mut reused: EndgameSolver = EndgameSolver::new();
+
+    for board in full_endgames.iter().take(20) {
+        let fresh: EndgameSolution = EndgameSolver::new().solve(board).unwrap();
+        assert!(reused.solve(board).unwrap().points == fresh.points, "Reused solver differs:\n{}", board);
+    }
+}
+
+// Minimax sense poda jugant sobre el Board: cada jugador maximitza els punts del seu equip
+fn reference_points(board: &Board) -> [u32; 2] {
+    let team: usize = board.get_team(board.current_player);
+    let mut best: Option<[u32; 2]> = None;
+
+    for card in board.get_legal_cards().iter() {
+        let mut child: Board = board.clone();
+        let card_index: usize = child.get_current_player_hand().get_index(&card).unwrap();
+        let points: [u32; 2] = match child.play_card(card_index).unwrap() {
+            GameState::None | GameState::BazaEnded => reference_points(&child),
+            _ => child.get_points()
+        };
+
+        if best.is_none_or(|best| points[team] > best[team]) {
+            best = Some(points);
+        }
+    }
+
+    best.unwrap()
+}
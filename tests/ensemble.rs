@@ -0,0 +1,161 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::ensemble_bot::{EnsembleBot, EnsembleConfig, EnsembleRule, EnsembleStats, Member};
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::observed_bot::ObservedBot;
+use rust_guinote::bot_behaviours::smart_bot::SmartBot;
+use rust_guinote::bot_behaviours::stochastic_bot::{EpsilonGreedyBot, EpsilonGreedyConfig};
+use rust_guinote::driver::play_seeded_game;
+use rust_guinote::game::{Board, Card, GameState};
+use rust_guinote::modeling::{ModelStore, SharedModels};
+
+mod common;
+
+use common::{ensemble, moves, Shared};
+
+const GAMES: u64 = 30;
+
+const DEADLINE: Duration = Duration::from_millis(60);
+
+// Apunta el temps que li queda quan se li pregunta i el gasta tot, com una cerca amb límit
+struct GreedyBot(Rc<RefCell<Vec<Duration>>>);
+
+impl Behaviour for GreedyBot {
+    fn name(&self) -> String {
+        "GreedyBot".to_string()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        board.play_card(0)
+    }
+
+    fn post_baza_actions(&self, _board: &mut Board, _player: usize) {}
+
+    fn query_card(&self, board: &Board, deadline: Option<Instant>) -> Option<Card> {
+        let deadline: Instant = deadline?;
+        let now: Instant = Instant::now();
+        self.0.borrow_mut().push(deadline.saturating_duration_since(now));
+        thread::sleep(deadline.saturating_duration_since(now));
+
+        board.get_legal_cards().nth(0)
+    }
+}
+
+// `bot` als seients de l'equip 0 i SmartBot als de l'equip 1
+fn against_smart(seat: usize, bot: Box<dyn Behaviour>) -> Box<dyn Behaviour> {
+    match seat % 2 {
+        0 => bot,
+        _ => Box::new(SmartBot {})
+    }
+}
+
+// Un veto impossible i un líder amb més pes que la resta juguen igual que el líder sol
+#[test]
+fn ensemble_follows_its_leader() {
+    let weights: [f64; 3] = [3.0, 1.0, 1.0];
+    for seed in 0..GAMES {
+        let expected: Vec<Card> = moves(|seat| against_smart(seat, Box::new(ExpertBot::new(ExpertConfig::default()))), seed);
+        for rule in [EnsembleRule::Veto { vetoes: 3 }, EnsembleRule::Weighted] {
+            let played: Vec<Card> = moves(|seat| against_smart(seat, Box::new(ensemble(rule, &weights))), seed);
+            assert!(played == expected, "{:?} ensemble does not follow its leader in game {}", rule, seed);
+        }
+    }
+}
+
+#[test]
+fn tally_rules() {
+    let cards: Vec<Card> = Board::new_seeded(0, 0).get_current_player_hand().cards.iter().collect();
+    let (a, b): (Card, Card) = (cards[0], cards[1]);
+    let votes: [Option<Card>; 3] = [Some(a), Some(b), Some(b)];
+
+    assert_eq!(ensemble(EnsembleRule::Majority, &[1.0; 3]).tally(&votes), Some(b));
+    assert_eq!(ensemble(EnsembleRule::Majority, &[1.0; 3]).tally(&[Some(a), Some(b), None]), Some(a));
+    assert_eq!(ensemble(EnsembleRule::Weighted, &[3.0, 1.0, 1.0]).tally(&votes), Some(a));
+    assert_eq!(ensemble(EnsembleRule::Veto { vetoes: 2 }, &[1.0; 3]).tally(&votes), Some(b));
+    assert_eq!(ensemble(EnsembleRule::Veto { vetoes: 3 }, &[1.0; 3]).tally(&votes), Some(a));
+    assert_eq!(ensemble(EnsembleRule::Veto { vetoes: 0 }, &[1.0; 3]).tally(&votes), Some(a));
+    assert_eq!(ensemble(EnsembleRule::Veto { vetoes: 1 }, &[1.0; 3]).tally(&[None, Some(b), None]), Some(b));
+    assert_eq!(ensemble(EnsembleRule::Majority, &[1.0; 3]).tally(&[None; 3]), None);
+}
+
+// Consultar els membres no toca el Board, ni les estadístiques de l'ensemble
+#[test]
+fn votes_leave_the_board_and_stats_alone() {
+    let majority: EnsembleBot = ensemble(EnsembleRule::Majority, &[1.0; 3]);
+    let board: Board = Board::new_seeded(0, 0);
+    let hash: u64 = board.get_hash();
+
+    let votes: Vec<Option<Card>> = majority.votes(&board);
+    assert!(votes.iter().all(Option::is_some));
+    assert_eq!(majority.query_card(&board, None), majority.tally(&votes));
+    assert_eq!(board.get_hash(), hash);
+    assert_eq!(majority.get_stats().decisions, 0);
+}
+
+// Els membres amb estat no canvien per votar: l'ObservedBot no apunta les consultes al model i
+// el bot estocàstic no vota, així que el seu generador no avança
+#[test]
+fn votes_leave_the_members_alone() {
+    let models: SharedModels = ModelStore::new().shared();
+    let stochastic = || Box::new(EpsilonGreedyBot::new(Box::new(SmartBot {}), EpsilonGreedyConfig { epsilon: 0.5, seed: Some(3) }));
+    let members: Vec<Member> = vec![
+        Member::new(Box::new(ObservedBot::new(Box::new(ExpertBot::new(ExpertConfig::default())), "observed", models.clone()))),
+        Member::new(stochastic())
+    ];
+    let majority: EnsembleBot = EnsembleBot::new(EnsembleConfig { members, rule: EnsembleRule::Majority }).unwrap();
+
+    let mut board: Board = Board::new_seeded(0, 0);
+    for _ in 0..10 {
+        let votes: Vec<Option<Card>> = majority.votes(&board);
+        assert!(votes[0].is_some() && votes[1].is_none());
+    }
+    assert!(models.borrow().players().is_empty(), "Votes were recorded as plays");
+
+    // El membre estocàstic juga igual que un de nou amb la mateixa llavor
+    let mut copy: Board = board.clone();
+    majority.config.members[1].bot.play_card(&mut board).unwrap();
+    stochastic().play_card(&mut copy).unwrap();
+    assert_eq!(board.get_hash(), copy.get_hash());
+}
+
+// Amb límit de temps cada membre en té una part, encara que els d'abans gastin tota la seva
+#[test]
+fn members_share_the_deadline() {
+    let allotted: Rc<RefCell<Vec<Duration>>> = Rc::new(RefCell::new(Vec::new()));
+    let members: Vec<Member> = (0..3).map(|_| Member::new(Box::new(GreedyBot(allotted.clone())))).collect();
+    let majority: EnsembleBot = EnsembleBot::new(EnsembleConfig { members, rule: EnsembleRule::Majority }).unwrap();
+
+    let mut board: Board = Board::new_seeded(0, 0);
+    majority.play_card_until(&mut board, Instant::now() + DEADLINE).unwrap();
+
+    let allotted: Vec<Duration> = allotted.borrow().clone();
+    assert_eq!(allotted.len(), 3);
+    for (member, time) in allotted.into_iter().enumerate() {
+        assert!(time >= DEADLINE / 6, "Member {} got {:?} of {:?}", member, time, DEADLINE);
+    }
+}
+
+// Les estadístiques quadren després d'unes quantes partides
+#[test]
+fn stats_are_consistent() {
+    let majority: Rc<EnsembleBot> = Rc::new(ensemble(EnsembleRule::Majority, &[1.0; 3]));
+    let bots: Vec<Box<dyn Behaviour>> = vec![
+        Box::new(Shared(majority.clone())),
+        Box::new(ExpertBot::new(ExpertConfig::default())),
+        Box::new(Shared(majority.clone())),
+        Box::new(ExpertBot::new(ExpertConfig::default()))
+    ];
+    for seed in 0..GAMES {
+        play_seeded_game(&bots, seed);
+    }
+
+    let stats: EnsembleStats = majority.get_stats();
+    assert!(stats.decisions > 0);
+    assert!(stats.failures.iter().all(|&failures| failures == 0), "A member failed to vote");
+    assert!(stats.unanimous <= stats.decisions && stats.overruled == stats.decisions - stats.agreed[0], "Inconsistent stats");
+    // Amb tres membres la carta jugada sempre té almenys un vot
+    assert!(stats.agreed.iter().sum::<usize>() >= stats.decisions, "Some played cards had no votes");
+}
@@ -0,0 +1,132 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::environment::{EnvConfig, Environment, Observation, RewardMode, Step, ACTION_COUNT};
+use rust_guinote::game::{Board, GameState};
+
+// Bot que no juga mai
+struct FailingBot;
+
+impl Behaviour for FailingBot {
+    fn name(&self) -> String {
+        "FailingBot".to_string()
+    }
+
+    fn play_card(&self, _board: &mut Board) -> Result<GameState, String> {
+        Err("No vull jugar".to_string())
+    }
+
+    fn post_baza_actions(&self, _board: &mut Board, _player: usize) {}
+}
+
+fn failing_environment() -> Environment {
+    let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| match seat {
+        0 => Box::new(SmartBot {}) as Box<dyn Behaviour>,
+        _ => Box::new(FailingBot) as Box<dyn Behaviour>
+    }).collect();
+
+    Environment::new(EnvConfig { seat: 0, bots, reward: RewardMode::WinLoss }).unwrap()
+}
+
+// Si un bot falla abans del primer torn de l'agent, reset retorna l'error en lloc de tornar-ho
+// a provar per sempre
+#[test]
+fn reset_returns_bot_errors() {
+    let mut environment: Environment = failing_environment();
+
+    // Amb la llavor 1 surt el seient 1
+    assert!(environment.reset(1).is_err());
+    assert!(environment.is_done());
+    assert!(environment.step(0).is_err());
+}
+
+// Si un bot falla després de la jugada de l'agent, step retorna l'error i l'episodi s'acaba
+#[test]
+fn step_returns_bot_errors() {
+    let mut environment: Environment = failing_environment();
+
+    // Amb la llavor 0 surt l'agent
+    let observation: Observation = environment.reset(0).unwrap();
+    let action: usize = observation.legal_mask.iter().position(|&legal| legal).unwrap();
+    assert!(environment.step(action).is_err());
+    assert!(environment.is_done());
+    assert!(environment.reset(0).is_ok());
+}
+
+fn random_agent_config() -> EnvConfig {
+    EnvConfig {
+        seat: 1,
+        bots: (0..4).map(|seat| match seat == 1 {
+            true => Box::new(RandomBot {}) as Box<dyn Behaviour>,
+            false => Box::new(SmartBot {}) as Box<dyn Behaviour>
+        }).collect(),
+        reward: RewardMode::PointDifference
+    }
+}
+
+// Un agent aleatori contra SmartBots: les màscares són dins de la mà, les accions il·legals es
+// rebutgen i cada ronda reparteix 120 punts de carta més les 10 d'últimes
+#[test]
+fn random_agent_episodes_are_consistent() {
+    let mut rng: StdRng = StdRng::seed_from_u64(0);
+    let mut environment: Environment = Environment::new(random_agent_config()).unwrap();
+
+    for seed in 0..200 {
+        let mut observation: Observation = environment.reset(seed).unwrap();
+        let mut round_points: u32 = 0;
+
+        loop {
+            let legal_actions: Vec<usize> = (0..ACTION_COUNT).filter(|&action| observation.legal_mask[action]).collect();
+            assert!(!legal_actions.is_empty(), "Seed {}: empty legal mask", seed);
+            assert!(legal_actions.iter().all(|&action| observation.hand.bits() & (1 << action) != 0), "Seed {}: mask outside the hand", seed);
+
+            let illegal_action: Option<usize> = (0..ACTION_COUNT).find(|&action| !observation.legal_mask[action]);
+            if let Some(action) = illegal_action {
+                assert!(environment.step(action).is_err(), "Seed {}: illegal action {} accepted", seed, action);
+            }
+
+            let step: Step = environment.step(legal_actions[rng.gen_range(0..legal_actions.len())]).unwrap();
+
+            round_points += step.info.points_won[0] + step.info.points_won[1];
+            let new_round: bool = step.observation.won_cards[0].is_empty() && step.observation.won_cards[1].is_empty() && round_points > 0;
+            if new_round || step.done && step.observation.hand.is_empty() {
+                assert_eq!(round_points, 130, "Seed {}: wrong points in a round", seed);
+                round_points = 0;
+            }
+
+            if step.done {
+                break;
+            }
+            observation = step.observation;
+        }
+    }
+}
+
+fn deterministic_episode(seed: u64) -> Vec<f64> {
+    let mut environment: Environment = Environment::new(EnvConfig {
+        seat: 0,
+        bots: (0..4).map(|_| Box::new(SmartBot {}) as Box<dyn Behaviour>).collect(),
+        reward: RewardMode::PointDifference
+    }).unwrap();
+    let mut observation: Observation = environment.reset(seed).unwrap();
+    let mut rewards: Vec<f64> = Vec::new();
+
+    loop {
+        let action: usize = (0..ACTION_COUNT).find(|&action| observation.legal_mask[action]).unwrap();
+        let step: Step = environment.step(action).unwrap();
+        rewards.push(step.reward);
+        if step.done {
+            return rewards;
+        }
+        observation = step.observation;
+    }
+}
+
+// Amb bots i agent deterministes la mateixa llavor dona el mateix episodi
+#[test]
+fn seeded_episodes_repeat() {
+    for seed in [0, 42] {
+        assert_eq!(deterministic_episode(seed), deterministic_episode(seed), "Seed {}", seed);
+    }
+}
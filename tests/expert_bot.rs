@@ -0,0 +1,116 @@
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::driver::post_baza_actions;
+use rust_guinote::card_set::CardSet;
+use rust_guinote::game::{Board, Card, GameState, Rank};
+
+mod common;
+
+use common::visit_positions;
+
+const GAMES: u64 = 50;
+
+type Decision = (Card, &'static str);
+
+// Primera posició de les partides entre ExpertBots on l'ExpertBot amb `toggled` tria una altra
+// carta que el de per defecte i `changed` hi està d'acord
+fn changed_decision<F: Fn(&Board, Decision, Decision) -> bool>(toggled: ExpertConfig, changed: F) -> Option<Board> {
+    let default: ExpertBot = ExpertBot::new(ExpertConfig::default());
+    let toggled: ExpertBot = ExpertBot::new(toggled);
+    let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|_| Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>).collect();
+
+    let mut found: Option<Board> = None;
+    for seed in 0..GAMES {
+        visit_positions(&bots, seed, |board| {
+            let (expected, actual): (Decision, Decision) = (default.explain(board), toggled.explain(board));
+            if expected.0 != actual.0 && changed(board, expected, actual) {
+                found = Some(board.clone());
+            }
+
+            found.is_none()
+        });
+        if found.is_some() {
+            break;
+        }
+    }
+
+    found
+}
+
+// Quan el company va guanyant, amb low_when_partner_wins es tira la carta més baixa i sense
+// se li carreguen punts: mai menys que amb la regla activada, i de vegades més
+#[test]
+fn partner_wins_rule_changes_the_card() {
+    let low: ExpertBot = ExpertBot::new(ExpertConfig::default());
+    let load: ExpertBot = ExpertBot::new(ExpertConfig { low_when_partner_wins: false, ..ExpertConfig::default() });
+    let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|_| Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>).collect();
+    let mut loaded: usize = 0;
+
+    for seed in 0..50 {
+        let mut board: Board = Board::new_seeded((seed % 4) as usize, seed);
+        loop {
+            let (low_card, low_rule): (Card, &'static str) = low.explain(&board);
+            let (load_card, load_rule): (Card, &'static str) = load.explain(&board);
+            if low_rule == "el company guanya, carta baixa" {
+                assert_eq!(load_rule, "el company guanya, carregar punts");
+                assert!(load_card.value() >= low_card.value(), "Loaded {:?} instead of {:?}\n{}", load_card, low_card, board);
+                if load_card.value() > low_card.value() {
+                    loaded += 1;
+                }
+            } else {
+                assert_eq!((low_card, low_rule), (load_card, load_rule));
+            }
+
+            match bots[board.current_player].play_card(&mut board).unwrap() {
+                GameState::BazaEnded => post_baza_actions(&bots, &mut board),
+                GameState::Team0Won | GameState::Team1Won => break,
+                _ => ()
+            }
+        }
+    }
+
+    assert!(loaded > 0, "Turning low_when_partner_wins off never loaded points");
+}
+
+// Sense keep_trumfo_seven el 7 de trumfo es juga abans de poder-lo canviar
+#[test]
+fn trumfo_seven_rule_changes_the_card() {
+    let board: Option<Board> = changed_decision(ExpertConfig { keep_trumfo_seven: false, ..ExpertConfig::default() }, |board, _, (card, _)| {
+        !board.is_arrastre() && card == Card { pal: board.current_trumfo.pal, rank: Rank::Siete }
+    });
+
+    assert!(board.is_some(), "Turning keep_trumfo_seven off never played the trumfo 7");
+}
+
+// Sense lead_trumfos_in_arrastre no se surt de trumfo quan els rivals encara en poden tenir
+#[test]
+fn lead_trumfos_rule_changes_the_card() {
+    let board: Option<Board> = changed_decision(ExpertConfig { lead_trumfos_in_arrastre: false, ..ExpertConfig::default() }, |board, (_, rule), (card, _)| {
+        rule == "arrossegar trumfos" && card.pal != board.current_trumfo.pal
+    });
+
+    assert!(board.is_some(), "Turning lead_trumfos_in_arrastre off never stopped leading trumfos");
+}
+
+// Sense keep_cante_pairs es juga la sota o el rey d'una parella que encara es pot cantar
+#[test]
+fn cante_pairs_rule_changes_the_card() {
+    let board: Option<Board> = changed_decision(ExpertConfig { keep_cante_pairs: false, ..ExpertConfig::default() }, |board, _, (card, _)| {
+        let pair: CardSet = CardSet::single(Card { pal: card.pal, rank: Rank::Sota }) | CardSet::single(Card { pal: card.pal, rank: Rank::Rey });
+        let hand: CardSet = board.get_current_player_hand().cards;
+        pair.contains(card) && (pair - hand).is_empty() && !board.get_cantes().contains(&card.pal)
+    });
+
+    assert!(board.is_some(), "Turning keep_cante_pairs off never broke a cante pair");
+}
+
+// Sense careful_ultimas la penúltima baza es juga amb les regles generals, que de vegades gasten
+// la carta que s'havia de guardar
+#[test]
+fn careful_ultimas_rule_changes_the_card() {
+    let board: Option<Board> = changed_decision(ExpertConfig { careful_ultimas: false, ..ExpertConfig::default() }, |_, (_, rule), _| {
+        rule == "guardar la carta forta per a les 10 d'últimes"
+    });
+
+    assert!(board.is_some(), "Turning careful_ultimas off never changed the penultimate baza");
+}
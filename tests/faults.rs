@@ -0,0 +1,220 @@
+use std::cell::Cell;
+use std::panic;
+use std::sync::Once;
+use std::time::Duration;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::clock::ClockConfig;
+use rust_guinote::driver::{play_logged_game, play_seeded_game, DriverOptions};
+use rust_guinote::faults::{Fault, FaultConfig, FaultPolicy};
+use rust_guinote::game::{Board, GameState};
+use rust_guinote::game_log::{GameLog, LogEvent};
+use rust_guinote::signals::{Signal, SignalConfig};
+
+// El driver acaba les partides amb bots que fallen: els errors i els panics es reintenten fins a
+// max_retries, després se substitueix la jugada o es perd segons la política, i totes les
+// fallades queden al registre
+const GAMES: u64 = 5;
+const BOT_PANICS: [&str; 3] = ["always panics", "panics after playing", "panics after the baza"];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Misbehaviour {
+    // Sempre retorna Err
+    Error,
+    // Sempre fa panic
+    Panic,
+    // Retorna Ok sense jugar cap carta
+    Idle,
+    // Falla `failures` vegades a cada jugada i després juga
+    Flaky { failures: usize },
+    // Juga la carta i després fa panic
+    Corrupt,
+    // Juga bé però fa panic a post_baza_actions
+    PostBaza
+}
+
+struct FaultyBot {
+    expert: ExpertBot,
+    misbehaviour: Misbehaviour,
+    attempts: Cell<usize>
+}
+
+impl FaultyBot {
+    fn new(misbehaviour: Misbehaviour) -> Self {
+        FaultyBot { expert: ExpertBot::new(ExpertConfig::default()), misbehaviour, attempts: Cell::new(0) }
+    }
+}
+
+impl Behaviour for FaultyBot {
+    fn name(&self) -> String {
+        format!("FaultyBot({:?})", self.misbehaviour)
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        match self.misbehaviour {
+            Misbehaviour::Error => Err("always fails".to_string()),
+            Misbehaviour::Panic => panic!("always panics"),
+            Misbehaviour::Idle => Ok(GameState::None),
+            Misbehaviour::Flaky { failures } => {
+                let attempts: usize = self.attempts.get() + 1;
+                if attempts <= failures {
+                    self.attempts.set(attempts);
+                    return Err(format!("attempt {}", attempts));
+                }
+                self.attempts.set(0);
+                self.expert.play_card(board)
+            },
+            Misbehaviour::Corrupt => {
+                self.expert.play_card(board)?;
+                panic!("panics after playing")
+            },
+            Misbehaviour::PostBaza => self.expert.play_card(board)
+        }
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        if self.misbehaviour == Misbehaviour::PostBaza {
+            panic!("panics after the baza");
+        }
+        self.expert.post_baza_actions(board, player);
+    }
+
+    fn send_signal(&self, board: &Board, player: usize) -> Option<Signal> {
+        Some(Signal::honest(board, player))
+    }
+}
+
+// Team 0 és el bot que falla i Team 1 juga bé
+fn bots(misbehaviour: Misbehaviour) -> Vec<Box<dyn Behaviour>> {
+    (0..4).map(|seat| {
+        let team: usize = seat % 2;
+        match team {
+            0 => Box::new(FaultyBot::new(misbehaviour)) as Box<dyn Behaviour>,
+            _ => Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>
+        }
+    }).collect()
+}
+
+fn count(log: &GameLog, matches: fn(&LogEvent) -> bool) -> usize {
+    log.get_events().iter().filter(|event| matches(event)).count()
+}
+
+fn team_0_plays(log: &GameLog) -> usize {
+    count(log, |event| matches!(*event, LogEvent::Play { seat, .. } if seat == 0 || seat == 2))
+}
+
+// Els panics dels bots són esperats; no cal omplir la sortida amb els seus missatges, però sí
+// amb els de les comprovacions que fallen
+fn quiet_panics() {
+    static QUIET: Once = Once::new();
+    QUIET.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let expected: bool = info.payload().downcast_ref::<&str>().is_some_and(|message| BOT_PANICS.contains(message));
+            if !expected {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+#[test]
+fn failing_bots_are_substituted_or_forfeit() {
+    quiet_panics();
+    let retries: usize = FaultConfig::default().max_retries;
+    let substitute: DriverOptions = DriverOptions::default();
+    let forfeit: DriverOptions = DriverOptions { faults: FaultConfig { policy: FaultPolicy::Forfeit, ..FaultConfig::default() }, ..DriverOptions::default() };
+    let clocked: DriverOptions = DriverOptions { clock: Some(ClockConfig { initial: Duration::from_secs(5), ..ClockConfig::default() }), ..DriverOptions::default() };
+
+    for seed in 0..GAMES {
+        for (misbehaviour, panicked) in [(Misbehaviour::Error, false), (Misbehaviour::Panic, true), (Misbehaviour::Idle, false)] {
+            for options in [&substitute, &clocked] {
+                let mut log: GameLog = GameLog::new();
+                let game_state: GameState = play_logged_game(&bots(misbehaviour), seed, options, &mut log);
+                let plays: usize = team_0_plays(&log);
+                assert!(game_state == GameState::Team0Won || game_state == GameState::Team1Won, "{:?} did not finish:\n{}", misbehaviour, log);
+                assert!(plays > 0 && count(&log, |event| matches!(*event, LogEvent::Substitute { .. })) == plays, "Every move of {:?} should be substituted:\n{}", misbehaviour, log);
+                assert!(log.faults()[0] + log.faults()[2] == plays * (retries + 1) && log.faults()[1] + log.faults()[3] == 0, "Wrong fault count:\n{}", log);
+                assert!(log.get_events().iter().all(|event| match event {
+                    LogEvent::Fault { fault, .. } => matches!(fault, Fault::Panic(_)) == panicked,
+                    _ => true
+                }), "Wrong kind of fault:\n{}", log);
+            }
+
+            let mut log: GameLog = GameLog::new();
+            assert!(play_logged_game(&bots(misbehaviour), seed, &forfeit, &mut log) == GameState::Team1Won, "{:?} should forfeit", misbehaviour);
+            let events: &[LogEvent] = log.get_events();
+            assert!(matches!(events[events.len() - 2], LogEvent::Forfeit { .. }) && log.faults().iter().sum::<usize>() == retries + 1,
+                    "Forfeit without {} faults:\n{}", retries + 1, log);
+        }
+    }
+}
+
+// Sense registre ni recorder el driver tampoc es queda esperant la carta d'un bot que no juga
+#[test]
+fn idle_bots_do_not_stall_the_game() {
+    for seed in 0..GAMES {
+        let game_state: GameState = play_seeded_game(&bots(Misbehaviour::Idle), seed);
+        assert!(game_state == GameState::Team0Won || game_state == GameState::Team1Won, "Idle bots did not finish");
+    }
+}
+
+// Dins dels reintents la jugada és la del bot
+#[test]
+fn flaky_bots_keep_their_moves() {
+    let retries: usize = FaultConfig::default().max_retries;
+    let forfeit: DriverOptions = DriverOptions { faults: FaultConfig { policy: FaultPolicy::Forfeit, ..FaultConfig::default() }, ..DriverOptions::default() };
+
+    for seed in 0..GAMES {
+        let mut log: GameLog = GameLog::new();
+        play_logged_game(&bots(Misbehaviour::Flaky { failures: retries }), seed, &forfeit, &mut log);
+        assert!(count(&log, |event| matches!(*event, LogEvent::Substitute { .. } | LogEvent::Forfeit { .. })) == 0, "A flaky bot was replaced:\n{}", log);
+        assert!(log.faults()[0] + log.faults()[2] == team_0_plays(&log) * retries, "Wrong fault count:\n{}", log);
+    }
+}
+
+// Els reintents no tornen a fer les senyes de la baza: només en fan els seients 0 i 2, una
+// vegada per baza
+#[test]
+fn retries_do_not_repeat_signals() {
+    let options: DriverOptions = DriverOptions { signals: Some(SignalConfig { visibility: 0.5 }), ..DriverOptions::default() };
+
+    for seed in 0..GAMES {
+        let mut log: GameLog = GameLog::new();
+        play_logged_game(&bots(Misbehaviour::Flaky { failures: 2 }), seed, &options, &mut log);
+        assert!(log.faults()[0] + log.faults()[2] > 0);
+        assert!(log.signals().count() == log.plays().count() / 2, "Signals repeated on retries:\n{}", log);
+    }
+}
+
+#[test]
+fn corrupted_and_post_baza_faults() {
+    quiet_panics();
+    let substitute: DriverOptions = DriverOptions::default();
+    let forfeit: DriverOptions = DriverOptions { faults: FaultConfig { policy: FaultPolicy::Forfeit, ..FaultConfig::default() }, ..DriverOptions::default() };
+
+    for seed in 0..GAMES {
+        // Un panic a mitja jugada deixa el Board a mitges, i es perd sigui quina sigui la política
+        let mut log: GameLog = GameLog::new();
+        assert!(play_logged_game(&bots(Misbehaviour::Corrupt), seed, &substitute, &mut log) == GameState::Team1Won, "A corrupted game went on:\n{}", log);
+        assert!(count(&log, |event| matches!(*event, LogEvent::Forfeit { seat: 0 | 2 })) == 1 && log.faults().iter().sum::<usize>() == 1, "Wrong forfeit:\n{}", log);
+
+        let mut log: GameLog = GameLog::new();
+        let game_state: GameState = play_logged_game(&bots(Misbehaviour::PostBaza), seed, &forfeit, &mut log);
+        assert!(game_state == GameState::Team0Won || game_state == GameState::Team1Won, "Panics after the baza stopped the game:\n{}", log);
+        assert!(log.faults()[0] > 0 && log.faults()[2] > 0 && count(&log, |event| matches!(*event, LogEvent::Forfeit { .. })) == 0, "Post baza panics were not logged:\n{}", log);
+    }
+}
+
+// Amb bots que no fallen no canvia res
+#[test]
+fn fault_handling_does_not_change_normal_games() {
+    let forfeit: DriverOptions = DriverOptions { faults: FaultConfig { policy: FaultPolicy::Forfeit, ..FaultConfig::default() }, ..DriverOptions::default() };
+    let experts: Vec<Box<dyn Behaviour>> = (0..4).map(|_| Box::new(ExpertBot::new(ExpertConfig::default())) as Box<dyn Behaviour>).collect();
+
+    for seed in 0..GAMES {
+        let mut log: GameLog = GameLog::new();
+        assert!(play_logged_game(&experts, seed, &forfeit, &mut log) == play_seeded_game(&experts, seed), "Fault handling changed a normal game");
+        assert!(log.faults() == [0; 4], "Faults in a normal game:\n{}", log);
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::smart_bot::SmartBot;
+use rust_guinote::card_set::CardSet;
+use rust_guinote::card_tracking::CardTracker;
+use rust_guinote::determinization::{unseen_cards, HandSampler};
+use rust_guinote::game::{Board, Card};
+
+mod common;
+
+use common::{mixed_bots, visit_positions};
+
+// Comprova que el HandSampler és uniforme sobre els repartiments compatibles: en posicions
+// d'arrastre amb pals fallats s'enumeren tots els repartiments i es fa un test de chi quadrat.
+const POSITIONS: usize = 8;
+const MIN_DEALS: usize = 20;
+const MAX_DEALS: usize = 1000;
+const SAMPLES_PER_DEAL: usize = 200;
+// Quantil 99.99% de la normal: amb diverses posicions un fals positiu ha de ser molt improbable
+const Z: f64 = 3.72;
+
+#[test]
+fn sampler_is_uniform_over_consistent_deals() {
+    let mut rng: StdRng = StdRng::seed_from_u64(0);
+
+    for (board, observer) in void_positions() {
+        let sampler: HandSampler = HandSampler::new(&board, observer).unwrap();
+        let deals: Vec<[CardSet; 4]> = enumerate_deals(&board, observer);
+        let index: HashMap<[CardSet; 4], usize> = deals.iter().enumerate().map(|(i, &deal)| (deal, i)).collect();
+        assert!(sampler.count() == deals.len() as f64, "Sampler counts {} deals, enumeration {}:\n{}", sampler.count(), deals.len(), board);
+
+        let samples: usize = SAMPLES_PER_DEAL * deals.len();
+        let mut frequencies: Vec<usize> = vec![0; deals.len()];
+        for _ in 0..samples {
+            let sample: Board = sampler.sample(&mut rng);
+            let deal: [CardSet; 4] = [0, 1, 2, 3].map(|player| sample.get_hand(player).cards);
+            match index.get(&deal) {
+                Some(&i) => frequencies[i] += 1,
+                None => panic!("Inconsistent deal sampled for player {}:\n{}", observer, sample)
+            }
+        }
+
+        let expected: f64 = SAMPLES_PER_DEAL as f64;
+        let chi_square: f64 = frequencies.iter().map(|&frequency| (frequency as f64 - expected).powi(2) / expected).sum();
+        let critical: f64 = chi_square_critical(deals.len() - 1);
+        assert!(chi_square <= critical, "Not uniform over {} deals: chi² {:.1}, critical {:.1}\n{}",
+                deals.len(), chi_square, critical, board);
+
+        // El mostreig amb pesos ha de donar també repartiments compatibles
+        for _ in 0..10 {
+            let sample: Board = sampler.sample_weighted(&mut rng, &SmartBot {}, 8);
+            let deal: [CardSet; 4] = [0, 1, 2, 3].map(|player| sample.get_hand(player).cards);
+            assert!(index.contains_key(&deal), "Inconsistent weighted deal:\n{}", sample);
+        }
+    }
+}
+
+// Posicions d'arrastre on algun jugador ha fallat un pal i el nombre de repartiments és manejable,
+// com a molt una per partida
+fn void_positions() -> Vec<(Board, usize)> {
+    let bots: Vec<Box<dyn Behaviour>> = mixed_bots();
+    let mut positions: Vec<(Board, usize)> = Vec::new();
+
+    let mut seed: u64 = 0;
+    while positions.len() < POSITIONS {
+        visit_positions(&bots, seed, |board| {
+            let observer: usize = board.current_player;
+            let tracker: &CardTracker = board.get_card_tracker();
+            let has_voids: bool = (0..4).any(|player| player != observer && !tracker.get_excluded(player).is_empty());
+            if board.is_arrastre() && has_voids {
+                let deals: f64 = HandSampler::new(board, observer).unwrap().count();
+                if (MIN_DEALS as f64..=MAX_DEALS as f64).contains(&deals) {
+                    positions.push((board.clone(), observer));
+                    return false;
+                }
+            }
+
+            true
+        });
+        seed += 1;
+    }
+
+    positions
+}
+
+// Tots els repartiments de les cartes no vistes que respecten mides, cartes ensenyades i pals fallats
+fn enumerate_deals(board: &Board, observer: usize) -> Vec<[CardSet; 4]> {
+    let hidden_cards: Vec<Card> = unseen_cards(board, observer).iter().collect();
+    let mut deals: Vec<[CardSet; 4]> = Vec::new();
+    let mut deal: [CardSet; 4] = [CardSet::EMPTY; 4];
+    deal[observer] = board.get_hand(observer).cards;
+
+    assign(board, observer, &hidden_cards, &mut deal, &mut deals);
+
+    deals
+}
+
+fn assign(board: &Board, observer: usize, cards: &[Card], deal: &mut [CardSet; 4], deals: &mut Vec<[CardSet; 4]>) {
+    let tracker: &CardTracker = board.get_card_tracker();
+    let Some((&card, rest)) = cards.split_first() else {
+        let unseen: CardSet = unseen_cards(board, observer);
+        let consistent: bool = (0..4).filter(|&player| player != observer)
+                                     .all(|player| ((tracker.get_shown(player) & unseen) - deal[player]).is_empty());
+        if consistent {
+            deals.push(*deal);
+        }
+        return;
+    };
+
+    for player in (0..4).filter(|&player| player != observer) {
+        let full: bool = deal[player].len() == board.get_hand(player).cards.len();
+        if full || tracker.get_excluded(player).contains(card) {
+            continue;
+        }
+
+        deal[player].insert(card);
+        assign(board, observer, rest, deal, deals);
+        deal[player].remove(card);
+    }
+}
+
+// Quantil de la chi quadrat (aproximació de Wilson-Hilferty)
+fn chi_square_critical(degrees: usize) -> f64 {
+    let k: f64 = degrees as f64;
+    let term: f64 = 1.0 - 2.0 / (9.0 * k) + Z * (2.0 / (9.0 * k)).sqrt();
+    k * term.powi(3)
+}
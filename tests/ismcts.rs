@@ -0,0 +1,91 @@
+use std::time::{Duration, Instant};
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::ismcts_bot::{IsmctsBot, IsmctsConfig};
+use rust_guinote::bot_behaviours::stochastic_bot::{EpsilonGreedyBot, EpsilonGreedyConfig};
+use rust_guinote::bot_behaviours::smart_bot::SmartBot;
+use rust_guinote::driver::play_seeded_game;
+use rust_guinote::game::{Board, GameState};
+use rust_guinote::stats::WinRate;
+
+const LIMIT: Duration = Duration::from_millis(20);
+// Marge per al clon del Board i per l'última iteració, que no es talla a mitges
+const SLACK: Duration = Duration::from_millis(200);
+// Partides de cada enfrontament, amb les iteracions fixades perquè el resultat no depengui de la màquina
+const WIN_RATE_GAMES: u64 = 40;
+const WIN_RATE_ITERATIONS: usize = 30;
+const MAX_P_VALUE: f64 = 0.01;
+
+// Després de cada baza de partides entre SmartBots, els cantes i el canvi del 7 de l'ISMCTS
+// sense límit d'iteracions han d'acabar a temps, sigui pel `time_budget` o per `deadline`
+fn check_post_baza_time(bot: &IsmctsBot, deadline: Option<Duration>) {
+    for seed in 0..2 {
+        let mut board: Board = Board::new_seeded((seed % 4) as usize, seed);
+        loop {
+            let game_state: GameState = SmartBot {}.play_card(&mut board).unwrap();
+            match game_state {
+                GameState::Team0Won | GameState::Team1Won => break,
+                GameState::BazaEnded => {
+                    for player in 0..4 {
+                        let start: Instant = Instant::now();
+                        match deadline {
+                            Some(limit) => bot.post_baza_actions_until(&mut board, player, start + limit),
+                            None => bot.post_baza_actions(&mut board, player)
+                        }
+                        assert!(start.elapsed() < LIMIT + SLACK, "post_baza_actions took {:?}", start.elapsed());
+                    }
+                },
+                _ => ()
+            }
+        }
+    }
+}
+
+#[test]
+fn post_baza_search_keeps_to_the_time_budget() {
+    let bot: IsmctsBot = IsmctsBot::new(IsmctsConfig { iterations: usize::MAX, time_budget: Some(LIMIT), ..IsmctsConfig::default() });
+    check_post_baza_time(&bot, None);
+}
+
+#[test]
+fn post_baza_search_keeps_to_the_deadline() {
+    let bot: IsmctsBot = IsmctsBot::new(IsmctsConfig { iterations: usize::MAX, time_budget: None, ..IsmctsConfig::default() });
+    check_post_baza_time(&bot, Some(LIMIT));
+}
+
+// Bot de l'equip contrari, amb la llavor de la partida
+type Opponent = fn(u64) -> Box<dyn Behaviour>;
+
+// Percentatge de victòries de l'ISMCTS contra `opponent`. Cada llavor fixa el repartiment, la
+// cerca i l'atzar de l'oponent, i l'ISMCTS canvia d'equip a cada partida
+fn ismcts_win_rate(opponent: Opponent) -> WinRate {
+    let mut wins: usize = 0;
+    for seed in 0..WIN_RATE_GAMES {
+        let ismcts_team: usize = (seed % 2) as usize;
+        let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| match seat % 2 == ismcts_team {
+            true => Box::new(IsmctsBot::new(IsmctsConfig { iterations: WIN_RATE_ITERATIONS, seed: Some(seed), ..IsmctsConfig::default() })) as Box<dyn Behaviour>,
+            false => opponent(seed)
+        }).collect();
+
+        let winner: GameState = play_seeded_game(&bots, seed);
+        if winner == [GameState::Team0Won, GameState::Team1Won][ismcts_team] {
+            wins += 1;
+        }
+    }
+
+    WinRate::new(wins, WIN_RATE_GAMES as usize)
+}
+
+#[test]
+fn ismcts_beats_the_simple_bots() {
+    // El RandomBot fa servir l'atzar del fil; amb epsilon 1 l'EpsilonGreedyBot juga igual però amb llavor
+    let opponents: [(&str, Opponent); 2] = [
+        ("RandomBot", |seed| Box::new(EpsilonGreedyBot::new(Box::new(SmartBot {}), EpsilonGreedyConfig { epsilon: 1.0, seed: Some(seed) }))),
+        ("SmartBot", |_| Box::new(SmartBot {}))
+    ];
+
+    for (name, opponent) in opponents {
+        let win_rate: WinRate = ismcts_win_rate(opponent);
+        assert!(win_rate.rate() > 0.5 && win_rate.p_value() < MAX_P_VALUE,
+                "IsmctsBot won {} of {} games against {} (p = {:.4})", win_rate.wins, win_rate.games, name, win_rate.p_value());
+    }
+}
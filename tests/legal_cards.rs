@@ -0,0 +1,42 @@
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{random_bot::RandomBot, smart_bot::SmartBot};
+use rust_guinote::card_set::CardSet;
+use rust_guinote::game::Board;
+
+mod common;
+#[path = "common/allocator.rs"]
+mod allocator;
+
+use allocator::allocations;
+use common::{collect_positions, random_bots, reference_legal_cards};
+
+const GAMES: u64 = 50;
+
+// Les dues implementacions han de coincidir
+#[test]
+fn legal_cards_match_the_reference() {
+    for board in collect_positions(&random_bots(), GAMES).iter() {
+        let reference: CardSet = reference_legal_cards(board).into_iter().collect();
+        assert!(reference == board.get_legal_cards(), "Legal cards differ from reference:\n{}", board);
+    }
+}
+
+// Ni la generació de jugades ni una jugada sencera d'un bot senzill allocaten
+#[test]
+fn legal_cards_do_not_allocate() {
+    let positions: Vec<Board> = collect_positions(&random_bots(), GAMES);
+    let bots: [Box<dyn Behaviour>; 2] = [Box::new(RandomBot {}), Box::new(SmartBot {})];
+    let mut boards: Vec<Board> = positions.clone();
+
+    let before: usize = allocations();
+    for board in positions.iter() {
+        std::hint::black_box(board.get_legal_cards());
+    }
+    assert_eq!(allocations() - before, 0, "get_legal_cards allocated");
+
+    let before: usize = allocations();
+    for (i, board) in boards.iter_mut().enumerate() {
+        std::hint::black_box(bots[i % 2].play_card(board).is_ok());
+    }
+    assert_eq!(allocations() - before, 0, "A bot move allocated");
+}
@@ -0,0 +1,61 @@
+use std::env;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::adaptive_bot::{AdaptiveBot, AdaptiveConfig};
+use rust_guinote::driver::play_seeded_game;
+use rust_guinote::game::GameState;
+use rust_guinote::modeling::{ModelStore, PlayerModel, SharedModels, Tendency};
+
+mod common;
+
+use common::observed;
+
+const GAMES: u64 = 500;
+
+// Sessió entre ExpertBot i RandomBot observant els dos equips
+fn expert_session(games: u64) -> (SharedModels, usize) {
+    let models: SharedModels = ModelStore::new().shared();
+    let session: Vec<Box<dyn Behaviour>> = observed(&models, || Box::new(ExpertBot::new(ExpertConfig::default())), "expert");
+    let wins: usize = (0..games).filter(|&seed| play_seeded_game(&session, seed) == GameState::Team0Won).count();
+
+    (models, wins)
+}
+
+// Els models reflecteixen les regles de l'ExpertBot
+#[test]
+fn models_follow_the_expert_rules() {
+    let (models, _): (SharedModels, usize) = expert_session(100);
+    let expert: PlayerModel = models.borrow().get("expert");
+    let random: PlayerModel = models.borrow().get("random");
+
+    for tendency in [Tendency::SaveSeven, Tendency::Cante, Tendency::Exchange] {
+        assert!(expert.rate(tendency) > 0.95, "ExpertBot {:?} rate is {:.2}", tendency, expert.rate(tendency));
+    }
+    assert!(expert.rate(Tendency::LoadPartner) < 0.2, "ExpertBot loads its partner");
+    assert!(expert.rate(Tendency::Overtake) > random.rate(Tendency::Overtake), "RandomBot overtakes more than ExpertBot");
+}
+
+#[test]
+fn models_are_saved_and_loaded_unchanged() {
+    let (models, _): (SharedModels, usize) = expert_session(20);
+
+    let path: String = env::temp_dir().join(format!("guinote-test-{}.models", std::process::id())).to_string_lossy().into_owned();
+    models.borrow().save(&path).unwrap();
+    let loaded: ModelStore = ModelStore::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    for player in ["expert", "random"] {
+        assert!(loaded.get(player) == models.borrow().get(player), "Model of {} changed after saving", player);
+    }
+}
+
+// L'AdaptiveBot amb els models de la sessió guanya a RandomBot almenys com l'ExpertBot
+#[test]
+fn adaptive_bot_does_not_lose_strength() {
+    let (models, expert_wins): (SharedModels, usize) = expert_session(GAMES);
+
+    let adaptive: Vec<Box<dyn Behaviour>> = observed(&models, || Box::new(AdaptiveBot::new(AdaptiveConfig::default(), models.clone())), "adaptive");
+    let adaptive_wins: usize = (0..GAMES).filter(|&seed| play_seeded_game(&adaptive, seed) == GameState::Team0Won).count();
+
+    assert!(adaptive_wins + 15 >= expert_wins, "AdaptiveBot wins {}/{} and ExpertBot {}/{}", adaptive_wins, GAMES, expert_wins, GAMES);
+}
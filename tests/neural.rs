@@ -0,0 +1,79 @@
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::{neural_bot::{NeuralBot, NeuralConfig}, random_bot::RandomBot};
+use rust_guinote::driver::play_seeded_game;
+use rust_guinote::encoding::{encode_board, OBSERVATION_SIZE};
+use rust_guinote::card_set::CardSet;
+use rust_guinote::game::{Board, Card, GameState};
+use rust_guinote::neural::Network;
+
+const GAMES: u64 = 20;
+
+// Una xarxa desada i tornada a llegir dona les mateixes sortides
+#[test]
+fn saved_network_gives_the_same_outputs() {
+    let network: Network = Network::random(&[128, 64], 7);
+    let parsed: Network = Network::parse(&network.to_string()).unwrap();
+
+    let board: Board = Board::new_seeded(0, 1);
+    let observation: [f32; OBSERVATION_SIZE] = encode_board(&board, 0).0;
+    let (logits, value): ([f32; 40], f32) = network.evaluate(&observation);
+    let (parsed_logits, parsed_value): ([f32; 40], f32) = parsed.evaluate(&observation);
+    assert_eq!(logits, parsed_logits);
+    assert_eq!(value, parsed_value);
+    assert!((-1.0..=1.0).contains(&value), "Value {} out of range", value);
+}
+
+#[test]
+fn malformed_networks_are_rejected() {
+    let network: Network = Network::random(&[16], 7);
+
+    assert!(Network::parse("guinote-net 1\nschema 1\npolicy 591 40 linear\n").is_err(), "Truncated network accepted");
+    assert!(Network::parse(&network.to_string().replace("schema 1", "schema 2")).is_err(), "Wrong schema accepted");
+}
+
+// El bot acaba partides amb la política sola i amb el valor als cantes i al canvi
+#[test]
+fn neural_bot_finishes_games_in_every_mode() {
+    let network: Network = Network::random(&[32], 7);
+    let configs: [NeuralConfig; 2] = [
+        NeuralConfig::default(),
+        NeuralConfig { value_weight: 1.0, value_cantes: true, value_canvi: true, ..NeuralConfig::default() }
+    ];
+
+    for config in configs {
+        let bots: Vec<Box<dyn Behaviour>> = vec![
+            Box::new(NeuralBot::new(network.clone(), config)),
+            Box::new(RandomBot {}),
+            Box::new(NeuralBot::new(network.clone(), NeuralConfig::default())),
+            Box::new(RandomBot {})
+        ];
+        for seed in 0..GAMES {
+            let winner: GameState = play_seeded_game(&bots, seed);
+            assert!(winner == GameState::Team0Won || winner == GameState::Team1Won, "Seed {}: unfinished game", seed);
+        }
+    }
+}
+
+// El valor després de jugar la carta que tanca la baza no pot dependre de la carta que es roba,
+// que el bot no veu: amb el mazo en un altre ordre les puntuacions són les mateixes
+#[test]
+fn scores_do_not_depend_on_the_deck_order() {
+    let bot: NeuralBot = NeuralBot::new(Network::random(&[32], 7), NeuralConfig { policy_weight: 0.0, value_weight: 1.0, ..NeuralConfig::default() });
+
+    for seed in 0..GAMES {
+        let mut board: Board = Board::new_seeded((seed % 4) as usize, seed);
+        for _ in 0..3 {
+            board.play_card(0).unwrap();
+        }
+
+        let hands: [CardSet; 4] = [0, 1, 2, 3].map(|player| board.get_hand(player).cards);
+        let played: CardSet = board.get_current_baza().into_iter().flatten().collect();
+        let deck: CardSet = !(hands.iter().fold(played, |cards, &hand| cards | hand) | CardSet::single(board.current_trumfo));
+        let mut cards: Vec<Card> = deck.iter().collect();
+        let mut reordered: Board = board.clone();
+        cards.reverse();
+        reordered.redeal_cards(hands, &cards).unwrap();
+
+        assert_eq!(bot.score_cards(&board), bot.score_cards(&reordered), "Seed {}: the value sees the deck", seed);
+    }
+}
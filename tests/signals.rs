@@ -0,0 +1,113 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::signal_bot::SignalBot;
+use rust_guinote::driver::{play_logged_game, DriverOptions};
+use rust_guinote::game::{Board, GameState};
+use rust_guinote::game_log::GameLog;
+use rust_guinote::signals::{Signal, SignalConfig};
+
+const GAMES: u64 = 500;
+const VISIBILITY: f64 = 0.3;
+
+// SignalBot que comprova cada seña que rep
+struct CheckedBot {
+    inner: SignalBot,
+    // Senyes rebudes per tots els bots
+    received: Rc<Cell<usize>>
+}
+
+impl Behaviour for CheckedBot {
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn play_card(&self, board: &mut Board) -> Result<GameState, String> {
+        self.inner.play_card(board)
+    }
+
+    fn post_baza_actions(&self, board: &mut Board, player: usize) {
+        self.inner.post_baza_actions(board, player);
+    }
+
+    fn send_signal(&self, board: &Board, player: usize) -> Option<Signal> {
+        self.inner.send_signal(board, player)
+    }
+
+    fn receive_signal(&self, board: &Board, player: usize, from: usize, signal: Signal) {
+        assert!(from != player, "Seat {} received its own signal", player);
+        assert!(signal.holds(board, from), "Seat {} signalled {} without holding it", from, signal);
+        self.received.set(self.received.get() + 1);
+        self.inner.receive_signal(board, player, from, signal);
+        assert!(self.inner.get_received(from) == Some(signal));
+    }
+}
+
+fn checked_bots(received: &Rc<Cell<usize>>) -> Vec<Box<dyn Behaviour>> {
+    (0..4).map(|_| Box::new(CheckedBot {
+        inner: SignalBot::new(Box::new(ExpertBot::new(ExpertConfig::default()))),
+        received: received.clone()
+    }) as Box<dyn Behaviour>).collect()
+}
+
+fn signal_options() -> DriverOptions {
+    DriverOptions { signals: Some(SignalConfig { visibility: VISIBILITY }), ..DriverOptions::default() }
+}
+
+// Totes les senyes són certes quan arriben, cada seient en fa una per baza, el company les veu
+// sempre i qui la fa mai, els bots reben les que diu el registre i els rivals les veuen amb la
+// probabilitat configurada
+#[test]
+fn signals_are_true_and_reach_the_right_seats() {
+    let received: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+    let bots: Vec<Box<dyn Behaviour>> = checked_bots(&received);
+    let options: DriverOptions = signal_options();
+
+    let mut signals: usize = 0;
+    let mut rival_views: usize = 0;
+    for seed in 0..GAMES {
+        let mut log: GameLog = GameLog::new();
+        play_logged_game(&bots, seed, &options, &mut log);
+
+        let plays: usize = log.plays().count();
+        assert_eq!(plays / 4 * 4, plays, "Seed {}: incomplete baza in the log", seed);
+        assert_eq!(log.signals().count(), plays, "Seed {}: expected four signals per baza", seed);
+        for (seat, _, seen_by) in log.signals() {
+            assert!(seen_by[(seat + 2) % 4] && !seen_by[seat], "Seed {}: wrong receivers {:?} for seat {}", seed, seen_by, seat);
+            rival_views += [(seat + 1) % 4, (seat + 3) % 4].iter().filter(|&&rival| seen_by[rival]).count();
+            signals += 1;
+        }
+        assert_eq!(received.get(), signals + rival_views, "Seed {}: bots received a different number of signals than logged", seed);
+    }
+
+    let rate: f64 = rival_views as f64 / (2 * signals) as f64;
+    assert!((rate - VISIBILITY).abs() < 0.02, "Rival visibility {:.3} is far from {}", rate, VISIBILITY);
+}
+
+// La mateixa llavor dona el mateix registre, senyes incloses
+#[test]
+fn seeded_signal_games_are_reproducible() {
+    let received: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+    let bots: Vec<Box<dyn Behaviour>> = checked_bots(&received);
+    let options: DriverOptions = signal_options();
+
+    for seed in 0..20 {
+        let mut log: GameLog = GameLog::new();
+        play_logged_game(&bots, seed, &options, &mut log);
+        let mut replay: GameLog = GameLog::new();
+        play_logged_game(&bots, seed, &options, &mut replay);
+        assert_eq!(replay.to_string(), log.to_string(), "Game {} is not reproducible", seed);
+    }
+}
+
+#[test]
+fn no_signals_when_disabled() {
+    let received: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+    let bots: Vec<Box<dyn Behaviour>> = checked_bots(&received);
+
+    let mut log: GameLog = GameLog::new();
+    play_logged_game(&bots, 0, &DriverOptions::default(), &mut log);
+    assert_eq!(log.signals().count(), 0);
+    assert_eq!(received.get(), 0);
+}
@@ -0,0 +1,73 @@
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::smart_bot::SmartBot;
+use rust_guinote::bot_behaviours::expert_bot::{ExpertBot, ExpertConfig};
+use rust_guinote::bot_behaviours::weighted_bot::{WeightedBot, Weights};
+use rust_guinote::bot_behaviours::stochastic_bot::{EpsilonGreedyBot, EpsilonGreedyConfig, SoftmaxBot, SoftmaxConfig};
+use rust_guinote::driver::play_seeded_game;
+use rust_guinote::game::{Board, Card, GameState};
+
+mod common;
+
+use common::moves;
+
+const GAMES: u64 = 400;
+const SAMPLES: usize = 20000;
+
+// Amb epsilon 0 o temperatura 0 els embolcalls juguen com el bot de dins, també amb SmartBot,
+// que no puntua les cartes
+#[test]
+fn greedy_wrappers_play_like_the_inner_bot() {
+    for seed in 0..50 {
+        let smart: Vec<Card> = moves(|_| Box::new(SmartBot {}), seed);
+        let greedy: Vec<Card> = moves(|_| Box::new(EpsilonGreedyBot::new(Box::new(SmartBot {}), EpsilonGreedyConfig { epsilon: 0.0, seed: Some(seed) })), seed);
+        let cold: Vec<Card> = moves(|_| Box::new(SoftmaxBot::new(Box::new(SmartBot {}), SoftmaxConfig { temperature: 0.0, seed: Some(seed) })), seed);
+        assert_eq!(greedy, smart, "Game {}: epsilon 0 differs from SmartBot", seed);
+        assert_eq!(cold, smart, "Game {}: temperature 0 differs from SmartBot", seed);
+    }
+}
+
+#[test]
+fn seeded_exploration_is_reproducible() {
+    for seed in 0..50 {
+        let explore = |seat: usize| Box::new(EpsilonGreedyBot::new(Box::new(SmartBot {}), EpsilonGreedyConfig { epsilon: 0.5, seed: Some(seed * 4 + seat as u64) })) as Box<dyn Behaviour>;
+        assert_eq!(moves(explore, seed), moves(explore, seed), "Seeded game {} is not reproducible", seed);
+    }
+}
+
+// El softmax tria cada carta amb la probabilitat que diu
+#[test]
+fn softmax_samples_its_probabilities() {
+    let board: Board = Board::new_seeded(0, 7);
+    let softmax: SoftmaxBot = SoftmaxBot::new(Box::new(WeightedBot::new(Weights::default())), SoftmaxConfig { temperature: 0.5, seed: Some(1) });
+    let probabilities: Vec<(Card, f64)> = softmax.probabilities(&board, None);
+    assert!((probabilities.iter().map(|&(_, probability)| probability).sum::<f64>() - 1.0).abs() < 1e-9, "Probabilities do not add up to 1");
+
+    let mut counts: [usize; 40] = [0; 40];
+    for _ in 0..SAMPLES {
+        let mut copy: Board = board.clone();
+        softmax.play_card(&mut copy).unwrap();
+        let card: Card = (board.get_current_player_hand().cards - copy.get_hand(board.current_player).cards).nth(0).unwrap();
+        counts[card.index()] += 1;
+    }
+    for &(card, probability) in probabilities.iter() {
+        let frequency: f64 = counts[card.index()] as f64 / SAMPLES as f64;
+        assert!((frequency - probability).abs() < 0.02, "{} sampled {:.3} times instead of {:.3}", card, frequency, probability);
+    }
+}
+
+// La força baixa a mesura que puja epsilon: jugar a l'atzar és més fluix que l'ExpertBot
+#[test]
+fn exploration_weakens_the_bot() {
+    let wins = |epsilon: f64| {
+        let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|seat| {
+            let expert: Box<dyn Behaviour> = Box::new(ExpertBot::new(ExpertConfig::default()));
+            match seat % 2 {
+                0 => Box::new(EpsilonGreedyBot::new(expert, EpsilonGreedyConfig { epsilon, seed: Some(seat as u64) })),
+                _ => expert
+            }
+        }).collect();
+        (0..GAMES).filter(|&seed| play_seeded_game(&bots, seed) == GameState::Team0Won).count()
+    };
+
+    assert!(wins(1.0) < wins(0.0), "Random play is not weaker than ExpertBot");
+}
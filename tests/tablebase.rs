@@ -0,0 +1,118 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rust_guinote::card_set::CardSet;
+use rust_guinote::endgame_solver::{EndgameSolution, EndgameSolver};
+use rust_guinote::game::{Board, Card, Pal};
+use rust_guinote::tablebase::Tablebase;
+
+mod common;
+
+use common::{baza_start_cards, last_baza_positions};
+
+const CARDS_PER_PLAYER: usize = 2;
+
+fn solver_points(board: &Board) -> [u32; 2] {
+    let solution: EndgameSolution = EndgameSolver::new().solve(board).unwrap();
+    let bazas: [CardSet; 2] = board.get_bazas();
+
+    [solution.points[0] - bazas[0].points(), solution.points[1] - bazas[1].points()]
+}
+
+#[test]
+fn block_sizes() {
+    assert_eq!(Tablebase::block_entries(1), Some(96));
+    assert_eq!(Tablebase::block_entries(2), Some(10_080));
+    assert_eq!(Tablebase::block_entries(3), Some(1_478_400));
+    assert_eq!(Tablebase::block_entries(4), None);
+    assert!(Tablebase::new(Pal::Orus, 4).is_err());
+}
+
+// Les consultes de posicions reals coincideixen amb el solver exacte, dins i fora de la baza
+#[test]
+fn probe_matches_the_solver() {
+    let positions: Vec<Board> = last_baza_positions(60, CARDS_PER_PLAYER);
+    let mut tablebase: Tablebase = Tablebase::new(Pal::Orus, CARDS_PER_PLAYER).unwrap();
+    assert!(!positions.is_empty());
+
+    for board in positions.iter() {
+        tablebase.generate_block(baza_start_cards(board)).unwrap();
+
+        let expected: [u32; 2] = solver_points(board);
+        assert!(tablebase.probe(board) == Some(expected), "Probe {:?} differs from the solver {:?}:\n{}", tablebase.probe(board), expected, board);
+
+        let card: Card = tablebase.best_card(board).unwrap();
+        assert!(board.get_legal_cards().contains(card), "Illegal best card {}:\n{}", card, board);
+    }
+}
+
+// Amb la taula el solver dona els mateixos punts i busca menys
+#[test]
+fn solver_uses_the_tablebase() {
+    let positions: Vec<Board> = last_baza_positions(40, CARDS_PER_PLAYER);
+    let mut plain: EndgameSolver = EndgameSolver::new();
+    let mut with_tablebase: EndgameSolver = EndgameSolver::with_tablebase(Tablebase::new(Pal::Orus, CARDS_PER_PLAYER).unwrap());
+
+    for board in positions.iter() {
+        let expected: EndgameSolution = plain.solve(board).unwrap();
+        let solution: EndgameSolution = with_tablebase.solve(board).unwrap();
+        assert!(solution.points == expected.points, "Solver with tablebase {:?} differs from {:?}:\n{}", solution.points, expected.points, board);
+    }
+
+    assert!(with_tablebase.get_tablebase().unwrap().get_blocks() > 0);
+    assert!(with_tablebase.get_nodes() < plain.get_nodes(), "{} nodes with the tablebase, {} without", with_tablebase.get_nodes(), plain.get_nodes());
+}
+
+#[test]
+fn save_load_round_trip() {
+    let generated: Tablebase = Tablebase::generate(Pal::Copes, CARDS_PER_PLAYER, 3, &mut StdRng::seed_from_u64(5)).unwrap();
+    let path: PathBuf = env::temp_dir().join(format!("rust_guinote_{}.gtbl", std::process::id()));
+    generated.save(path.to_str().unwrap()).unwrap();
+    let loaded: Result<Tablebase, String> = Tablebase::load(path.to_str().unwrap());
+    let bytes: Vec<u8> = fs::read(&path).unwrap();
+    fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+    let truncated: Result<Tablebase, String> = Tablebase::load(path.to_str().unwrap());
+    fs::remove_file(&path).unwrap();
+
+    let loaded: Tablebase = loaded.unwrap();
+    assert_eq!(loaded.get_trumfo(), Pal::Copes);
+    assert_eq!(loaded.get_cards_per_player(), CARDS_PER_PLAYER);
+    assert_eq!(loaded.get_blocks(), generated.get_blocks());
+    assert_eq!(loaded.len(), generated.len());
+    loaded.verify(500, &mut StdRng::seed_from_u64(6)).unwrap();
+    assert!(truncated.is_err());
+}
+
+// La taula completa d'una carta per jugador té tots els blocs i respon a totes les posicions de
+// l'última baza sense generar-ne cap més
+#[test]
+fn full_table_answers_every_last_baza() {
+    let tablebase: Tablebase = Tablebase::generate_all(Pal::Orus, 1).unwrap();
+    assert_eq!(tablebase.get_blocks(), 91_390);
+    assert!(Tablebase::generate_all(Pal::Orus, CARDS_PER_PLAYER).is_err(), "Enumerated {} cards per player", CARDS_PER_PLAYER);
+
+    let positions: Vec<Board> = last_baza_positions(40, 1);
+    assert!(!positions.is_empty());
+    for board in positions.iter() {
+        let expected: [u32; 2] = solver_points(board);
+        assert!(tablebase.probe(board) == Some(expected), "Probe {:?} differs from the solver {:?}:\n{}", tablebase.probe(board), expected, board);
+    }
+}
+
+// La mateixa llavor dona el mateix fitxer i una altra dona uns altres blocs
+#[test]
+fn sampled_tables_are_reproducible() {
+    let path: PathBuf = env::temp_dir().join(format!("rust_guinote_{}_sampled.gtbl", std::process::id()));
+    let saved = |seed: u64| -> Vec<u8> {
+        let tablebase: Tablebase = Tablebase::generate(Pal::Orus, CARDS_PER_PLAYER, 2, &mut StdRng::seed_from_u64(seed)).unwrap();
+        tablebase.save(path.to_str().unwrap()).unwrap();
+        fs::read(&path).unwrap()
+    };
+    let (first, second, other): (Vec<u8>, Vec<u8>, Vec<u8>) = (saved(3), saved(3), saved(4));
+    fs::remove_file(&path).unwrap();
+
+    assert!(first == second, "The same seed generated different tables");
+    assert!(first != other, "Different seeds generated the same table");
+}
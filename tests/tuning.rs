@@ -0,0 +1,89 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::smart_bot::SmartBot;
+use rust_guinote::bot_behaviours::weighted_bot::{WeightedBot, Weights, FEATURE_COUNT};
+use rust_guinote::stats::WinRate;
+use rust_guinote::tuning::{Candidate, CandidateFactory, ReferenceFactory, Tuner, TunerConfig};
+
+const VALIDATION_GAMES: usize = 300;
+
+fn factories() -> (CandidateFactory, Vec<ReferenceFactory>) {
+    let make_candidate: CandidateFactory = Box::new(|params: &[f64]| {
+        let mut weights: [f64; FEATURE_COUNT] = [0.0; FEATURE_COUNT];
+        weights.copy_from_slice(params);
+        Box::new(WeightedBot::new(Weights(weights))) as Box<dyn Behaviour>
+    });
+    let references: Vec<ReferenceFactory> = vec![Box::new(|| Box::new(SmartBot {}) as Box<dyn Behaviour>)];
+
+    (make_candidate, references)
+}
+
+fn small_config() -> TunerConfig {
+    TunerConfig { population: 8, elite: 2, games: 30, mutation_rate: 0.5, mutation_scale: 1.0, seed: 7, ..TunerConfig::default() }
+}
+
+// Començant amb els pesos per defecte girats, que juguen malament, uns quants passos del tuner
+// han de trobar pesos que guanyen més partides noves que els inicials
+#[test]
+fn tuner_improves_on_the_initial_weights() {
+    let initial: Vec<f64> = Weights::default().0.iter().map(|weight| -weight).collect();
+    let (make_candidate, references) = factories();
+    let mut tuner: Tuner = Tuner::new(small_config(), &initial, make_candidate, references).unwrap();
+
+    let mut best: Option<Candidate> = None;
+    for _ in 0..4 {
+        best = Some(tuner.step());
+    }
+    let best: Candidate = best.unwrap();
+
+    let initial_rate: WinRate = tuner.validate(&initial, VALIDATION_GAMES);
+    let best_rate: WinRate = tuner.validate(&best.params, VALIDATION_GAMES);
+    assert!(best_rate.wins > initial_rate.wins, "Tuned {} wins, initial {} wins", best_rate.wins, initial_rate.wins);
+}
+
+fn checkpoint_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("rust_guinote_{}_{}.checkpoint", name, std::process::id()))
+}
+
+#[test]
+fn resume_checks_the_parameter_count() {
+    let path: PathBuf = checkpoint_path("short");
+    fs::write(&path, "generation = 3\ncandidate = 0.5 1 -2\n").unwrap();
+    let (make_candidate, references) = factories();
+    let result: Result<Tuner, String> = Tuner::resume(small_config(), path.to_str().unwrap(), FEATURE_COUNT, make_candidate, references);
+    fs::remove_file(&path).unwrap();
+
+    assert!(result.is_err(), "A checkpoint with 3 parameters resumed for {}", FEATURE_COUNT);
+}
+
+#[test]
+fn checkpoint_round_trip() {
+    let path: PathBuf = checkpoint_path("round_trip");
+    let (make_candidate, references) = factories();
+    let mut tuner: Tuner = Tuner::new(small_config(), &Weights::default().0, make_candidate, references).unwrap();
+    tuner.step();
+    tuner.save_checkpoint(path.to_str().unwrap()).unwrap();
+
+    let (make_candidate, references) = factories();
+    let resumed: Result<Tuner, String> = Tuner::resume(small_config(), path.to_str().unwrap(), FEATURE_COUNT, make_candidate, references);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(resumed.unwrap().get_generation(), 1);
+}
+
+// Sense referències no hi ha contra qui jugar
+#[test]
+fn empty_references_are_rejected() {
+    let (make_candidate, _) = factories();
+    assert!(Tuner::new(small_config(), &Weights::default().0, make_candidate, Vec::new()).is_err(), "A tuner without references was created");
+
+    let path: PathBuf = checkpoint_path("empty");
+    fs::write(&path, "generation = 0\ncandidate = 1 2 3\n").unwrap();
+    let (make_candidate, _) = factories();
+    let result: Result<Tuner, String> = Tuner::resume(small_config(), path.to_str().unwrap(), 3, make_candidate, Vec::new());
+    fs::remove_file(&path).unwrap();
+
+    assert!(result.is_err(), "A tuner without references was resumed");
+}
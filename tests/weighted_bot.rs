@@ -0,0 +1,84 @@
+use std::env;
+use std::fs;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::smart_bot::SmartBot;
+use rust_guinote::bot_behaviours::weighted_bot::{WeightedBot, Weights, FEATURE_COUNT};
+use rust_guinote::game::Card;
+
+mod common;
+
+use common::moves;
+
+const GAMES: u64 = 20;
+const PERSONALITIES: [&str; 2] = ["aggressive", "conservative"];
+
+fn personality(name: &str) -> Result<Weights, String> {
+    Weights::load(&format!("{}/personalities/{}.weights", env!("CARGO_MANIFEST_DIR"), name))
+}
+
+// Jugades d'una partida amb WeightedBot a l'equip 0 i SmartBot a l'equip 1
+fn weighted_moves(weights: Weights, seed: u64) -> Vec<Card> {
+    moves(|seat| match seat % 2 {
+        0 => Box::new(WeightedBot::new(weights)) as Box<dyn Behaviour>,
+        _ => Box::new(SmartBot {})
+    }, seed)
+}
+
+#[test]
+fn malformed_weights_are_rejected() {
+    let rejected: [(&str, &str); 5] = [
+        ("points_won 1.0", "line without '='"),
+        ("= 1.0", "missing feature name"),
+        ("points_won =", "missing weight"),
+        ("points_won = many", "non-numeric weight"),
+        ("points_won = inf", "non-finite weight"),
+    ];
+    for (text, reason) in rejected {
+        assert!(Weights::parse(text).is_err(), "Accepted {}: '{}'", reason, text);
+    }
+
+    let error: String = Weights::parse("points_won = 1.0\nbluffing = 2.0").unwrap_err();
+    assert!(error.contains("Línia 2") && error.contains("bluffing"), "Unknown feature error does not point to it: {}", error);
+}
+
+// Els comentaris i les línies buides s'ignoren i les característiques que falten es queden amb el
+// pes per defecte
+#[test]
+fn omitted_features_keep_the_default() {
+    let weights: Weights = Weights::parse("# Només canvia un pes\n\n  lead_trumfo = 2.5  # al final\n").unwrap();
+    let mut expected: Weights = Weights::default();
+    expected.0[FEATURE_COUNT - 1] = 2.5;
+
+    assert_eq!(weights, expected);
+}
+
+#[test]
+fn saved_weights_load_back() {
+    let mut weights: Weights = Weights::default();
+    for (feature, weight) in weights.0.iter_mut().enumerate() {
+        *weight += 0.1 * feature as f64 - 1.0 / 3.0;
+    }
+
+    let path: String = env::temp_dir().join(format!("rust_guinote_{}.weights", std::process::id())).to_string_lossy().into_owned();
+    weights.save(&path).unwrap();
+    let loaded: Result<Weights, String> = Weights::load(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.unwrap(), weights);
+    assert!(Weights::load(&path).is_err(), "Loaded a file that does not exist");
+}
+
+// Les personalitats que es distribueixen es carreguen, són diferents entre elles i del pes per
+// defecte, i juguen diferent
+#[test]
+fn personalities_load_and_play_differently() {
+    let personalities: Vec<Weights> = PERSONALITIES.iter().map(|name| personality(name).unwrap()).collect();
+    assert!(personalities.iter().all(|&weights| weights != Weights::default()), "A personality is the default weights");
+
+    let different_games: usize = (0..GAMES).filter(|&seed| {
+        let played: Vec<Vec<Card>> = personalities.iter().map(|&weights| weighted_moves(weights, seed)).collect();
+        played[0] != played[1]
+    }).count();
+
+    assert!(different_games == GAMES as usize, "The personalities play the same in {} of {} games", GAMES as usize - different_games, GAMES);
+}
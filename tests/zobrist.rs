@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+use rust_guinote::bot_behaviour::Behaviour;
+use rust_guinote::bot_behaviours::random_bot::RandomBot;
+use rust_guinote::card_set::CardSet;
+use rust_guinote::driver::post_baza_actions;
+use rust_guinote::game::{Board, GameState};
+
+const GAMES: u64 = 200;
+
+// Hash de cada posició d'una partida entre bots aleatoris, amb cantes i canvis del 7. En proves
+// el Board comprova a cada jugada que el hash incremental coincideix amb el recalculat.
+// A l'arrastre, tornar a repartir les mateixes mans el recalcula des de zero i no ha de canviar
+fn game_hashes(seed: u64) -> Vec<u64> {
+    let bots: Vec<Box<dyn Behaviour>> = (0..4).map(|_| Box::new(RandomBot {}) as Box<dyn Behaviour>).collect();
+    let mut board: Board = Board::new_seeded((seed % 4) as usize, seed);
+    let mut hashes: Vec<u64> = vec![board.get_hash()];
+
+    loop {
+        if board.is_arrastre() {
+            let hands: [CardSet; 4] = [0, 1, 2, 3].map(|player| board.get_hand(player).cards);
+            let mut redealt: Board = board.clone();
+            redealt.redeal_cards(hands, &[]).unwrap();
+            assert_eq!(redealt.get_hash(), board.get_hash(), "Incremental hash out of sync:\n{}", board);
+        }
+
+        match bots[board.current_player].play_card(&mut board).unwrap() {
+            GameState::BazaEnded => post_baza_actions(&bots, &mut board),
+            GameState::Team0Won | GameState::Team1Won => break,
+            _ => ()
+        }
+        hashes.push(board.get_hash());
+    }
+
+    hashes
+}
+
+#[test]
+fn incremental_hash_stays_in_sync() {
+    for seed in 0..GAMES {
+        let hashes: Vec<u64> = game_hashes(seed);
+
+        // Dins d'una partida no es repeteix cap posició
+        let distinct: HashSet<u64> = hashes.iter().copied().collect();
+        assert_eq!(distinct.len(), hashes.len(), "Repeated hash in game {}", seed);
+    }
+}
+
+#[test]
+fn hash_depends_only_on_the_position() {
+    let board: Board = Board::new_seeded(0, 7);
+    assert_eq!(board.clone().get_hash(), board.get_hash());
+    assert_eq!(Board::new_seeded(0, 7).get_hash(), board.get_hash());
+    assert_ne!(Board::new_seeded(1, 7).get_hash(), board.get_hash());
+    assert_ne!(Board::new_seeded(0, 8).get_hash(), board.get_hash());
+}